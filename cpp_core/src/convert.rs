@@ -1,5 +1,7 @@
 use crate::ptr::NullPtr;
-use crate::{CppBox, CppDeletable, Ptr, Ref, StaticUpcast};
+use crate::{CppBox, CppDeletable, CppFromBytes, CppFromStr, Ptr, Ref, RefOrBox, StaticUpcast};
+use std::ffi::CStr;
+use std::os::raw::c_char;
 
 /// Performs some of the conversions that are available implicitly in C++.
 ///
@@ -105,3 +107,57 @@ where
         Self::cast_from(Ptr::from_raw(value))
     }
 }
+
+impl<'a> CastFrom<&'a CStr> for Ptr<c_char> {
+    unsafe fn cast_from(value: &'a CStr) -> Self {
+        Ptr::from_raw(value.as_ptr())
+    }
+}
+
+impl<'a> CastFrom<&'a CStr> for Ref<c_char> {
+    unsafe fn cast_from(value: &'a CStr) -> Self {
+        Ref::from_raw(value.as_ptr()).expect("CStr::as_ptr returned null")
+    }
+}
+
+impl<T: CppDeletable, U> CastFrom<Ref<U>> for RefOrBox<T>
+where
+    U: StaticUpcast<T>,
+{
+    unsafe fn cast_from(value: Ref<U>) -> Self {
+        RefOrBox::Ref(Ref::cast_from(value))
+    }
+}
+
+impl<'a, T: CppDeletable, U: CppDeletable> CastFrom<&'a CppBox<U>> for RefOrBox<T>
+where
+    U: StaticUpcast<T>,
+{
+    unsafe fn cast_from(value: &'a CppBox<U>) -> Self {
+        RefOrBox::Ref(Ref::cast_from(value))
+    }
+}
+
+impl<T: CppDeletable> CastFrom<CppBox<T>> for RefOrBox<T> {
+    unsafe fn cast_from(value: CppBox<T>) -> Self {
+        RefOrBox::Box(value)
+    }
+}
+
+impl<'a, T: CppFromStr> CastFrom<&'a str> for RefOrBox<T> {
+    unsafe fn cast_from(value: &'a str) -> Self {
+        RefOrBox::Box(T::from_str(value))
+    }
+}
+
+impl<'a, T: CppFromStr> CastFrom<&'a String> for RefOrBox<T> {
+    unsafe fn cast_from(value: &'a String) -> Self {
+        RefOrBox::Box(T::from_str(value))
+    }
+}
+
+impl<'a, T: CppFromBytes> CastFrom<&'a [u8]> for RefOrBox<T> {
+    unsafe fn cast_from(value: &'a [u8]) -> Self {
+        RefOrBox::Box(T::from_bytes(value))
+    }
+}
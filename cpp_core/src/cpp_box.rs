@@ -18,6 +18,35 @@ pub trait CppDeletable: Sized {
     unsafe fn delete(&self);
 }
 
+/// Objects that can be copied using C++'s copy constructor.
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// that have an accessible copy constructor. If `T` implements this trait,
+/// `CppBox<T>` implements `Clone`.
+pub trait CppClone: CppDeletable {
+    /// Creates a copy of `self` on the heap using the copy constructor.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `self` contains a valid pointer. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn clone(&self) -> CppBox<Self>;
+}
+
+/// Objects that can be created using C++'s default constructor.
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// that have an accessible default constructor. If `T` implements this trait,
+/// `CppBox<T>` implements `Default`.
+pub trait CppDefault: CppDeletable {
+    /// Creates a new object on the heap using the default constructor.
+    ///
+    /// # Safety
+    ///
+    /// This function may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn default() -> CppBox<Self>;
+}
+
 /// An owning pointer to a C++ object.
 ///
 /// `CppBox` is automatically used in places where C++ class objects are passed by value
@@ -308,6 +337,20 @@ impl<T: CppDeletable> Deref for CppBox<T> {
     }
 }
 
+/// Creates a copy of the stored object using its copy constructor.
+impl<T: CppClone> Clone for CppBox<T> {
+    fn clone(&self) -> Self {
+        unsafe { CppClone::clone(&**self) }
+    }
+}
+
+/// Creates a new object using its default constructor.
+impl<T: CppDefault> Default for CppBox<T> {
+    fn default() -> Self {
+        unsafe { CppDefault::default() }
+    }
+}
+
 /// Deletes the stored object using C++'s `delete` operator.
 impl<T: CppDeletable> Drop for CppBox<T> {
     fn drop(&mut self) {
@@ -325,7 +368,7 @@ impl<T: CppDeletable> fmt::Debug for CppBox<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{CppBox, CppDeletable, Ptr};
+    use crate::{CppBox, CppClone, CppDefault, CppDeletable, Ptr};
     use std::cell::RefCell;
     use std::rc::Rc;
 
@@ -355,4 +398,43 @@ mod tests {
         }
         assert!(*value1.borrow() == 42);
     }
+
+    struct Struct2 {
+        value: i32,
+    }
+
+    impl CppDeletable for Struct2 {
+        unsafe fn delete(&self) {
+            drop(Box::from_raw(self as *const Struct2 as *mut Struct2));
+        }
+    }
+
+    impl CppClone for Struct2 {
+        unsafe fn clone(&self) -> CppBox<Self> {
+            let copy = Box::new(Struct2 { value: self.value });
+            CppBox::from_raw(Box::into_raw(copy)).unwrap()
+        }
+    }
+
+    impl CppDefault for Struct2 {
+        unsafe fn default() -> CppBox<Self> {
+            CppBox::from_raw(Box::into_raw(Box::new(Struct2 { value: 7 }))).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_default_calls_default_constructor() {
+        let object1: CppBox<Struct2> = Default::default();
+        assert_eq!(object1.value, 7);
+    }
+
+    #[test]
+    fn test_clone_calls_copy_constructor() {
+        unsafe {
+            let object1 = CppBox::from_raw(Box::into_raw(Box::new(Struct2 { value: 5 }))).unwrap();
+            let object2 = object1.clone();
+            assert_eq!(object2.value, 5);
+            assert_ne!(object1.as_raw_ptr(), object2.as_raw_ptr());
+        }
+    }
 }
@@ -0,0 +1,57 @@
+use std::error::Error;
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::c_char;
+
+/// An error produced by a C++ exception thrown in a wrapped C++ function.
+///
+/// Exceptions are only caught in functions for which it was enabled
+/// in the generator's configuration. Such functions return
+/// `Result<T, CppException>` instead of `T`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CppException {
+    what: String,
+}
+
+impl CppException {
+    /// Creates an error from the exception message produced by the C++ wrapper library
+    /// and releases the memory occupied by the message.
+    ///
+    /// ### Safety
+    ///
+    /// `message` must be a valid, null-terminated string allocated with `malloc`.
+    /// It must not be used after calling this function.
+    pub unsafe fn from_raw_message(message: *mut c_char) -> Self {
+        let what = CStr::from_ptr(message).to_string_lossy().into_owned();
+        libc::free(message as *mut libc::c_void);
+        CppException { what }
+    }
+
+    /// Returns the message of the exception, as returned by `std::exception::what()`.
+    pub fn what(&self) -> &str {
+        &self.what
+    }
+}
+
+impl fmt::Display for CppException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "C++ exception: {}", self.what)
+    }
+}
+
+impl Error for CppException {}
+
+#[test]
+fn from_raw_message() {
+    let text = b"vector::_M_range_check\0";
+    let exception = unsafe {
+        let message = libc::malloc(text.len()) as *mut c_char;
+        std::ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, message, text.len());
+        CppException::from_raw_message(message)
+    };
+    assert_eq!(exception.what(), "vector::_M_range_check");
+    assert_eq!(
+        exception.to_string(),
+        "C++ exception: vector::_M_range_check"
+    );
+}
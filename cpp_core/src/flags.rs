@@ -0,0 +1,146 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{BitAnd, BitOr, BitXor};
+use std::os::raw::c_int;
+
+/// An OR-combination of integer values of the enum type `E`.
+///
+/// This type is used for C++ functions that accept or return a bitmask
+/// of enum values as an integer. `ritual` uses it for enums and values marked
+/// as flags in the generator's configuration. `T` is the integer type used
+/// to store the value. `ritual` uses the underlying type of the C++ enum.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Flags<E, T = c_int> {
+    value: T,
+    _phantom_data: PhantomData<E>,
+}
+
+impl<E, T> From<T> for Flags<E, T> {
+    fn from(value: T) -> Self {
+        Self {
+            value,
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+macro_rules! impl_from_flags {
+    ($($int:ty),*) => {
+        $(
+            impl<E> From<Flags<E, $int>> for $int {
+                fn from(flags: Flags<E, $int>) -> Self {
+                    flags.value
+                }
+            }
+        )*
+    };
+}
+
+impl_from_flags!(i8, u8, i16, u16, i32, u32, i64, u64);
+
+impl<E, T: Copy> Flags<E, T> {
+    /// Returns the integer value of the flags.
+    pub fn to_int(&self) -> T {
+        self.value
+    }
+}
+
+impl<E, T> Flags<E, T>
+where
+    E: Into<Flags<E, T>>,
+    T: Copy + Default + PartialEq + BitAnd<Output = T>,
+{
+    /// Returns `true` if `flag` is enabled in `self`.
+    pub fn test_flag(&self, flag: E) -> bool {
+        self.value & flag.into().value != T::default()
+    }
+
+    /// Returns `true` if this value has no flags enabled.
+    pub fn is_empty(&self) -> bool {
+        self.value == T::default()
+    }
+}
+
+impl<E, T: BitOr<Output = T>, U: Into<Flags<E, T>>> BitOr<U> for Flags<E, T> {
+    type Output = Flags<E, T>;
+    fn bitor(self, rhs: U) -> Flags<E, T> {
+        Self {
+            value: self.value | rhs.into().value,
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<E, T: BitAnd<Output = T>, U: Into<Flags<E, T>>> BitAnd<U> for Flags<E, T> {
+    type Output = Flags<E, T>;
+    fn bitand(self, rhs: U) -> Flags<E, T> {
+        Self {
+            value: self.value & rhs.into().value,
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<E, T: BitXor<Output = T>, U: Into<Flags<E, T>>> BitXor<U> for Flags<E, T> {
+    type Output = Flags<E, T>;
+    fn bitxor(self, rhs: U) -> Flags<E, T> {
+        Self {
+            value: self.value ^ rhs.into().value,
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<E, T: Default> Default for Flags<E, T> {
+    fn default() -> Self {
+        Self {
+            value: T::default(),
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<E, T: fmt::Debug> fmt::Debug for Flags<E, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Flags({:?})", self.value)
+    }
+}
+
+#[test]
+fn flags() {
+    #[derive(Clone, Copy)]
+    struct Option1(c_int);
+
+    impl From<Option1> for Flags<Option1> {
+        fn from(value: Option1) -> Self {
+            Self::from(value.0)
+        }
+    }
+
+    let a = Option1(1);
+    let b = Option1(4);
+    let flags = Flags::<Option1>::from(a) | b;
+    assert_eq!(flags.to_int(), 5);
+    assert!(flags.test_flag(a));
+    assert!(flags.test_flag(b));
+    assert!(!flags.test_flag(Option1(2)));
+    assert!(!flags.is_empty());
+    assert!((flags & Option1(2)).is_empty());
+    assert_eq!((flags ^ a).to_int(), 4);
+    assert_eq!(Flags::<Option1>::default().to_int(), 0);
+
+    #[derive(Clone, Copy)]
+    struct Option2(u64);
+
+    impl From<Option2> for Flags<Option2, u64> {
+        fn from(value: Option2) -> Self {
+            Self::from(value.0)
+        }
+    }
+
+    let c = Option2(1 << 40);
+    let flags = Flags::<Option2, u64>::from(c) | Option2(0x8000_0000);
+    assert_eq!(flags.to_int(), (1 << 40) | 0x8000_0000);
+    assert!(flags.test_flag(c));
+    assert_eq!(u64::from(flags ^ c), 0x8000_0000);
+}
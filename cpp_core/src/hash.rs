@@ -0,0 +1,59 @@
+use crate::{CppBox, CppDeletable, Ref};
+use std::hash::{Hash, Hasher};
+
+/// Hashes the stored object using its `Hash` implementation.
+impl<T: CppDeletable + Hash> Hash for CppBox<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+/// Hashes the referenced object using its `Hash` implementation.
+impl<T: Hash> Hash for Ref<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+/// Marks C++ types whose `operator==` is an equivalence relation.
+///
+/// `ritual` implements this trait along with `Hash` for types that have
+/// a `std::hash<T>` specialization or a `qHash(const T&)` overload,
+/// so that `Ref<T>` can be used as a key in hash maps and sets.
+/// (Crates generated by `ritual` can't implement `Eq` for `Ref<T>` directly.)
+pub trait CppEq {}
+
+/// Allows using `Ref<T>` as a key in hash maps and sets.
+impl<T: CppEq + PartialEq<Ref<T>>> Eq for Ref<T> {}
+
+#[test]
+fn hash_set() {
+    use std::collections::HashSet;
+
+    struct Point(i32);
+
+    impl Hash for Point {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state)
+        }
+    }
+
+    impl PartialEq<Ref<Point>> for Point {
+        fn eq(&self, other: &Ref<Point>) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl CppEq for Point {}
+
+    let points = [Point(1), Point(2), Point(1)];
+    let mut set = HashSet::new();
+    unsafe {
+        for point in &points {
+            set.insert(Ref::from_raw(point).unwrap());
+        }
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Ref::from_raw(&Point(2)).unwrap()));
+        assert!(!set.contains(&Ref::from_raw(&Point(3)).unwrap()));
+    }
+}
@@ -50,26 +50,54 @@
 //! or even `Ptr<DerivedClass>` (where `DerivedClass` inherits `SomeClass`). You can also
 //! pass a null pointer object (`NullPtr`) if you don't have a value
 //! (`Ptr::null()` is also an option but it can cause type inference issues).
+//!
+//! `const char*` arguments accept `&CStr` through `CastInto`. Arguments of string types
+//! (e.g. `QString`) passed by value or by constant reference use `RefOrBox`, so you can
+//! pass `&str` (or `&[u8]` for byte arrays) and a temporary C++ object will be created
+//! for the duration of the call.
+//!
+//! # Smart pointers
+//!
+//! `SharedPtr`, `WeakPtr` and `UniquePtr` own C++'s `std::shared_ptr<T>`, `std::weak_ptr<T>`
+//! and `std::unique_ptr<T>` objects. `ritual` uses them in place of these types in function
+//! arguments and return values. `SharedPtr` and `UniquePtr` implement `Deref` to `T`,
+//! and `SharedPtr` can be converted to a base or derived class with `static_upcast`,
+//! `static_downcast` and `dynamic_cast` while keeping the shared ownership.
 
 #![deny(missing_docs)]
 
 pub use crate::casts::{DynamicCast, StaticDowncast, StaticUpcast};
 pub use crate::convert::{CastFrom, CastInto};
-pub use crate::cpp_box::{CppBox, CppDeletable};
+pub use crate::cpp_box::{CppBox, CppClone, CppDefault, CppDeletable};
+pub use crate::exception::CppException;
+pub use crate::flags::Flags;
+pub use crate::hash::CppEq;
 pub use crate::iterator::{cpp_iter, CppIterator, EndPtr};
+pub use crate::ostream::string_from_ostream;
 pub use crate::ptr::{NullPtr, Ptr};
 pub use crate::ref_::Ref;
+pub use crate::ref_or_box::{CppFromBytes, CppFromStr, RefOrBox};
+pub use crate::smart_ptr::{
+    SharedPtr, SharedPtrFunctions, SharedPtrTarget, UniquePtr, UniquePtrFunctions, UniquePtrTarget,
+    WeakPtr,
+};
 pub use libc::wchar_t;
 
 mod casts;
 pub mod cmp;
 mod convert;
 mod cpp_box;
+mod exception;
+mod flags;
+mod hash;
 mod iterator;
 pub mod ops;
 mod ops_impls;
+mod ostream;
 mod ptr;
 mod ref_;
+mod ref_or_box;
+mod smart_ptr;
 pub mod vector_ops;
 
 // C++ doesn't guarantee these types to be exactly u16 and u32,
@@ -101,3 +101,62 @@ pub trait EndMut {
     /// may invoke arbitrary foreign code, so no safety guarantees can be made.
     unsafe fn end_mut(&self) -> Self::Output;
 }
+
+/// Represents C++'s `operator[]() const`.
+pub trait Index<T> {
+    /// Output type.
+    type Output;
+
+    /// Returns the element of `self` at `index`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `self` contains a valid pointer. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn index(&self, index: T) -> Self::Output;
+}
+
+/// Represents C++'s `operator[]()`.
+pub trait IndexMut<T> {
+    /// Output type.
+    type Output;
+
+    /// Returns the element of `self` at `index`, allowing modification of the element.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `self` contains a valid pointer. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn index_mut(&self, index: T) -> Self::Output;
+}
+
+macro_rules! define_assign_op {
+    ($trait1:ident, $func:ident, $op:expr) => {
+        #[doc = "Represents C++'s `"]
+        #[doc = $op]
+        #[doc = "` operator."]
+        ///
+        /// `std::ops` counterpart of this trait is implemented for `CppBox`, `Ptr` and `Ref`
+        /// if the pointee type implements this trait.
+        pub trait $trait1<T> {
+            /// Performs the operation on `self` and `other`, modifying `self`.
+            ///
+            /// # Safety
+            ///
+            /// The caller must make sure `self` contains a valid pointer. This function
+            /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+            unsafe fn $func(&self, other: T);
+        }
+    };
+}
+
+define_assign_op!(AddAssign, add_assign, "+=");
+define_assign_op!(SubAssign, sub_assign, "-=");
+define_assign_op!(MulAssign, mul_assign, "*=");
+define_assign_op!(DivAssign, div_assign, "/=");
+define_assign_op!(RemAssign, rem_assign, "%=");
+define_assign_op!(BitAndAssign, bitand_assign, "&=");
+define_assign_op!(BitOrAssign, bitor_assign, "|=");
+define_assign_op!(BitXorAssign, bitxor_assign, "^=");
+define_assign_op!(ShlAssign, shl_assign, "<<=");
+define_assign_op!(ShrAssign, shr_assign, ">>=");
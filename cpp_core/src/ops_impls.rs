@@ -1,9 +1,13 @@
 use crate::{
     cmp::{Ge, Gt, Le, Lt},
-    CppBox, CppDeletable, Ptr, Ref,
+    ops, CppBox, CppDeletable, Ptr, Ref,
 };
 use std::cmp::{Ordering, PartialEq, PartialOrd};
 use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub};
+use std::ops::{
+    AddAssign, BitAndAssign, BitOrAssign, BitXorAssign, DivAssign, MulAssign, RemAssign, ShlAssign,
+    ShrAssign, SubAssign,
+};
 
 macro_rules! define_op {
     ($trait1:ident, $func:ident) => {
@@ -53,6 +57,39 @@ define_op!(BitXor, bitxor);
 define_op!(Shl, shl);
 define_op!(Shr, shr);
 
+macro_rules! define_assign_op {
+    ($trait1:ident, $func:ident) => {
+        impl<T: CppDeletable + ops::$trait1<U>, U> $trait1<U> for CppBox<T> {
+            fn $func(&mut self, rhs: U) {
+                unsafe { ops::$trait1::$func(&**self, rhs) }
+            }
+        }
+
+        impl<T: ops::$trait1<U>, U> $trait1<U> for Ptr<T> {
+            fn $func(&mut self, rhs: U) {
+                unsafe { ops::$trait1::$func(&**self, rhs) }
+            }
+        }
+
+        impl<T: ops::$trait1<U>, U> $trait1<U> for Ref<T> {
+            fn $func(&mut self, rhs: U) {
+                unsafe { ops::$trait1::$func(&**self, rhs) }
+            }
+        }
+    };
+}
+
+define_assign_op!(AddAssign, add_assign);
+define_assign_op!(SubAssign, sub_assign);
+define_assign_op!(MulAssign, mul_assign);
+define_assign_op!(DivAssign, div_assign);
+define_assign_op!(RemAssign, rem_assign);
+define_assign_op!(BitAndAssign, bitand_assign);
+define_assign_op!(BitOrAssign, bitor_assign);
+define_assign_op!(BitXorAssign, bitxor_assign);
+define_assign_op!(ShlAssign, shl_assign);
+define_assign_op!(ShrAssign, shr_assign);
+
 macro_rules! define_comparison_op {
     ($container:ident) => {
         impl<T, U> PartialEq<U> for $container<T>
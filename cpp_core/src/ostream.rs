@@ -0,0 +1,57 @@
+use crate::{CppBox, CppDeletable, Ref};
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::c_char;
+
+/// Converts the output of C++'s `operator<<` produced by the C++ wrapper library
+/// to a Rust string and releases the memory occupied by the original string.
+///
+/// This function is used by `Display` implementations that `ritual`
+/// generates for classes with `operator<<(std::ostream&, const T&)`.
+///
+/// ### Safety
+///
+/// `text` must be a valid, null-terminated string allocated with `malloc`.
+/// It must not be used after calling this function.
+pub unsafe fn string_from_ostream(text: *mut c_char) -> String {
+    let string = CStr::from_ptr(text).to_string_lossy().into_owned();
+    libc::free(text as *mut libc::c_void);
+    string
+}
+
+/// Formats the stored object using its `Display` implementation.
+impl<T: CppDeletable + fmt::Display> fmt::Display for CppBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/// Formats the referenced object using its `Display` implementation.
+impl<T: fmt::Display> fmt::Display for Ref<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+#[test]
+fn display() {
+    struct Point;
+
+    impl CppDeletable for Point {
+        unsafe fn delete(&self) {}
+    }
+
+    impl fmt::Display for Point {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Point(1, 2)")
+        }
+    }
+
+    let point = Point;
+    unsafe {
+        let point_ref = Ref::from_raw(&point).unwrap();
+        assert_eq!(point_ref.to_string(), "Point(1, 2)");
+        let point_box = CppBox::from_raw(&point).unwrap();
+        assert_eq!(point_box.to_string(), "Point(1, 2)");
+    }
+}
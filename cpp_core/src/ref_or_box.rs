@@ -0,0 +1,74 @@
+use crate::{CppBox, CppDeletable, Ref};
+use std::fmt;
+
+/// Either a reference to an existing C++ object or a temporary object owned by the value.
+///
+/// `ritual` uses `impl CastInto<RefOrBox<T>>` for arguments of string types
+/// (e.g. `QString` or `std::string`) passed by value or by constant reference.
+/// Such arguments accept references to existing objects as well as Rust strings.
+/// A Rust string is converted to a temporary C++ object that is deleted
+/// after the function call.
+pub enum RefOrBox<T: CppDeletable> {
+    /// A reference to an object that is not owned by `RefOrBox`.
+    Ref(Ref<T>),
+    /// An owned object. It's deleted when `RefOrBox` is dropped.
+    Box(CppBox<T>),
+}
+
+impl<T: CppDeletable> RefOrBox<T> {
+    /// Returns constant raw pointer to the object.
+    pub fn as_raw_ptr(&self) -> *const T {
+        match self {
+            RefOrBox::Ref(value) => value.as_raw_ptr(),
+            RefOrBox::Box(value) => value.as_raw_ptr(),
+        }
+    }
+
+    /// Returns a reference to the object.
+    ///
+    /// ### Safety
+    ///
+    /// The referenced object must be alive while the returned value is used.
+    pub unsafe fn as_ref(&self) -> Ref<T> {
+        match self {
+            RefOrBox::Ref(value) => *value,
+            RefOrBox::Box(value) => value.as_ref(),
+        }
+    }
+}
+
+impl<T: CppDeletable> fmt::Debug for RefOrBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefOrBox::Ref(value) => write!(f, "RefOrBox::Ref({:?})", value),
+            RefOrBox::Box(value) => write!(f, "RefOrBox::Box({:?})", value.as_raw_ptr()),
+        }
+    }
+}
+
+/// Creates a C++ string object from a Rust string.
+///
+/// This trait is implemented for C++ types configured as string types in `ritual`.
+/// It allows to pass `&str` to functions that accept such types.
+pub trait CppFromStr: CppDeletable {
+    /// Creates a new object containing a copy of `value`.
+    ///
+    /// ### Safety
+    ///
+    /// The C++ library must be in a state that allows creating the object.
+    unsafe fn from_str(value: &str) -> CppBox<Self>;
+}
+
+/// Creates a C++ object from a slice of bytes.
+///
+/// This trait is implemented for C++ types configured as string types in `ritual`
+/// that can store arbitrary bytes. It allows to pass `&[u8]` to functions
+/// that accept such types.
+pub trait CppFromBytes: CppDeletable {
+    /// Creates a new object containing a copy of `value`.
+    ///
+    /// ### Safety
+    ///
+    /// The C++ library must be in a state that allows creating the object.
+    unsafe fn from_bytes(value: &[u8]) -> CppBox<Self>;
+}
@@ -0,0 +1,624 @@
+use crate::{CastFrom, DynamicCast, Ptr, StaticDowncast, StaticUpcast};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::{fmt, mem, ptr};
+
+/// Functions operating on `std::shared_ptr<T>` and `std::weak_ptr<T>` objects
+/// allocated with `new`.
+///
+/// The table is created by the C++ wrapper library generated by `ritual`.
+/// Its layout must match `ritual::SharedPtrFunctions` declared in the wrapper library.
+#[repr(C)]
+pub struct SharedPtrFunctions {
+    get: unsafe extern "C" fn(*const c_void) -> *mut c_void,
+    clone: unsafe extern "C" fn(*const c_void) -> *mut c_void,
+    destroy: unsafe extern "C" fn(*mut c_void),
+    to_void: unsafe extern "C" fn(*const c_void, *mut c_void) -> *mut c_void,
+    from_void: unsafe extern "C" fn(*mut c_void) -> *mut c_void,
+    downgrade: unsafe extern "C" fn(*const c_void) -> *mut c_void,
+    upgrade: unsafe extern "C" fn(*const c_void) -> *mut c_void,
+    clone_weak: unsafe extern "C" fn(*const c_void) -> *mut c_void,
+    destroy_weak: unsafe extern "C" fn(*mut c_void),
+}
+
+/// Functions operating on `std::unique_ptr<T>` objects allocated with `new`.
+///
+/// The table is created by the C++ wrapper library generated by `ritual`.
+/// Its layout must match `ritual::UniquePtrFunctions` declared in the wrapper library.
+#[repr(C)]
+pub struct UniquePtrFunctions {
+    get: unsafe extern "C" fn(*const c_void) -> *mut c_void,
+    release: unsafe extern "C" fn(*mut c_void) -> *mut c_void,
+    destroy: unsafe extern "C" fn(*mut c_void),
+}
+
+/// Class types that can be used in `SharedPtr` and `WeakPtr`.
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// used in `std::shared_ptr<T>` or `std::weak_ptr<T>` in the C++ library's API.
+pub trait SharedPtrTarget: Sized {
+    /// Returns functions operating on `std::shared_ptr<Self>`
+    /// and `std::weak_ptr<Self>` objects.
+    fn shared_ptr_functions() -> &'static SharedPtrFunctions;
+}
+
+/// Class types that can be used in `UniquePtr`.
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// used in `std::unique_ptr<T>` in the C++ library's API.
+pub trait UniquePtrTarget: Sized {
+    /// Returns functions operating on `std::unique_ptr<Self>` objects.
+    fn unique_ptr_functions() -> &'static UniquePtrFunctions;
+}
+
+/// An owned C++ `std::shared_ptr<T>` object.
+///
+/// `ritual` uses `SharedPtr` in place of `std::shared_ptr<T>` values returned from
+/// and passed to C++ functions. Cloning a `SharedPtr` creates another
+/// `std::shared_ptr<T>` sharing the ownership of the object,
+/// and dropping it releases the ownership.
+///
+/// `SharedPtr` implements `Deref`, allowing to call the object's methods
+/// directly. `Deref` will panic if the pointer is null.
+///
+/// Use `static_upcast`, `static_downcast` and `dynamic_cast` to convert the pointer to
+/// a base or derived class while keeping the shared ownership, like C++'s
+/// `std::static_pointer_cast` and `std::dynamic_pointer_cast` do.
+///
+/// ### Safety
+///
+/// The object is managed by C++ code. The user must ensure that
+/// raw pointers to the object are not used after the last owner is dropped.
+pub struct SharedPtr<T: SharedPtrTarget> {
+    ptr: ptr::NonNull<c_void>,
+    _marker: PhantomData<*mut T>,
+}
+
+impl<T: SharedPtrTarget> SharedPtr<T> {
+    /// Takes ownership of a `std::shared_ptr<T>` object allocated with `new`.
+    /// Returns `None` if `ptr` is null.
+    ///
+    /// ### Safety
+    ///
+    /// `ptr` must point to a `std::shared_ptr<T>` object that can be
+    /// safely deleted using C++'s `delete` operator.
+    /// The object must not be used by other means after this call.
+    pub unsafe fn from_raw(ptr: *mut c_void) -> Option<Self> {
+        ptr::NonNull::new(ptr).map(|ptr| SharedPtr {
+            ptr,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns a constant raw pointer to the `std::shared_ptr<T>` object.
+    pub fn as_raw_ptr(&self) -> *const c_void {
+        self.ptr.as_ptr() as *const c_void
+    }
+
+    /// Returns a mutable raw pointer to the `std::shared_ptr<T>` object.
+    pub fn as_mut_raw_ptr(&self) -> *mut c_void {
+        self.ptr.as_ptr()
+    }
+
+    /// Destroys `SharedPtr` without deleting the `std::shared_ptr<T>` object
+    /// and returns a raw pointer to it. The caller becomes the owner of
+    /// the `std::shared_ptr<T>` object.
+    pub fn into_raw_ptr(self) -> *mut c_void {
+        let ptr = self.ptr.as_ptr();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Returns the stored pointer (`std::shared_ptr<T>::get()`).
+    ///
+    /// ### Safety
+    ///
+    /// The returned pointer must not be used after the last owner of the object is dropped.
+    pub unsafe fn as_ptr(&self) -> Ptr<T> {
+        Ptr::from_raw((T::shared_ptr_functions().get)(self.as_raw_ptr()) as *const T)
+    }
+
+    /// Returns true if the stored pointer is null.
+    pub fn is_null(&self) -> bool {
+        unsafe { self.as_ptr().is_null() }
+    }
+
+    /// Creates a `WeakPtr` that refers to the same object without owning it.
+    pub fn downgrade(&self) -> WeakPtr<T> {
+        unsafe {
+            WeakPtr::from_raw((T::shared_ptr_functions().downgrade)(self.as_raw_ptr()))
+                .expect("downgrade returned null")
+        }
+    }
+
+    /// Returns a `SharedPtr` that shares the ownership with `self`
+    /// and stores `target` (like C++'s aliasing constructor).
+    unsafe fn alias<U: SharedPtrTarget>(&self, target: Ptr<U>) -> SharedPtr<U> {
+        let void_ptr = (T::shared_ptr_functions().to_void)(
+            self.as_raw_ptr(),
+            target.as_mut_raw_ptr() as *mut c_void,
+        );
+        SharedPtr::from_raw((U::shared_ptr_functions().from_void)(void_ptr))
+            .expect("from_void returned null")
+    }
+
+    /// Converts the pointer to the base class type `U`, sharing the ownership with `self`.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as the stored pointer is valid or null.
+    pub unsafe fn static_upcast<U: SharedPtrTarget>(&self) -> SharedPtr<U>
+    where
+        T: StaticUpcast<U>,
+    {
+        self.alias(StaticUpcast::static_upcast(self.as_ptr()))
+    }
+
+    /// Converts the pointer to the derived class type `U`, sharing the ownership with `self`.
+    ///
+    /// It's recommended to use `dynamic_cast` instead because it performs a checked conversion.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as the stored pointer is null or its type
+    /// is `U` or inherits from `U`.
+    pub unsafe fn static_downcast<U: SharedPtrTarget>(&self) -> SharedPtr<U>
+    where
+        T: StaticDowncast<U>,
+    {
+        self.alias(StaticDowncast::static_downcast(self.as_ptr()))
+    }
+
+    /// Converts the pointer to the derived class type `U`, sharing the ownership with `self`.
+    /// Returns `None` if the pointer is null or the object's type is not `U`
+    /// and doesn't inherit `U`.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as the stored pointer is valid or null.
+    pub unsafe fn dynamic_cast<U: SharedPtrTarget>(&self) -> Option<SharedPtr<U>>
+    where
+        T: DynamicCast<U>,
+    {
+        let target = DynamicCast::dynamic_cast(self.as_ptr());
+        if target.is_null() {
+            None
+        } else {
+            Some(self.alias(target))
+        }
+    }
+}
+
+/// Allows to call member functions of `T` and its base classes directly on the pointer.
+impl<T: SharedPtrTarget> Deref for SharedPtr<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe {
+            self.as_ptr()
+                .as_raw_ref()
+                .expect("attempted to deref a null SharedPtr<T>")
+        }
+    }
+}
+
+/// Creates another `std::shared_ptr<T>` sharing the ownership of the object.
+impl<T: SharedPtrTarget> Clone for SharedPtr<T> {
+    fn clone(&self) -> Self {
+        unsafe {
+            SharedPtr::from_raw((T::shared_ptr_functions().clone)(self.as_raw_ptr()))
+                .expect("clone returned null")
+        }
+    }
+}
+
+/// Deletes the `std::shared_ptr<T>` object, releasing its ownership of the object.
+impl<T: SharedPtrTarget> Drop for SharedPtr<T> {
+    fn drop(&mut self) {
+        unsafe {
+            (T::shared_ptr_functions().destroy)(self.ptr.as_ptr());
+        }
+    }
+}
+
+impl<T: SharedPtrTarget> fmt::Debug for SharedPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SharedPtr({:?})", unsafe { self.as_ptr() })
+    }
+}
+
+/// Converts the pointer to the base class type, sharing the ownership with `value`.
+impl<'a, T: SharedPtrTarget, U: SharedPtrTarget> CastFrom<&'a SharedPtr<U>> for SharedPtr<T>
+where
+    U: StaticUpcast<T>,
+{
+    unsafe fn cast_from(value: &'a SharedPtr<U>) -> Self {
+        value.static_upcast()
+    }
+}
+
+/// Converts the pointer to the base class type, taking over the ownership from `value`.
+impl<T: SharedPtrTarget, U: SharedPtrTarget> CastFrom<SharedPtr<U>> for SharedPtr<T>
+where
+    U: StaticUpcast<T>,
+{
+    unsafe fn cast_from(value: SharedPtr<U>) -> Self {
+        value.static_upcast()
+    }
+}
+
+/// An owned C++ `std::weak_ptr<T>` object.
+///
+/// `ritual` uses `WeakPtr` in place of `std::weak_ptr<T>` values returned from
+/// and passed to C++ functions. Use `upgrade` to obtain a `SharedPtr`
+/// if the object is still alive.
+pub struct WeakPtr<T: SharedPtrTarget> {
+    ptr: ptr::NonNull<c_void>,
+    _marker: PhantomData<*mut T>,
+}
+
+impl<T: SharedPtrTarget> WeakPtr<T> {
+    /// Takes ownership of a `std::weak_ptr<T>` object allocated with `new`.
+    /// Returns `None` if `ptr` is null.
+    ///
+    /// ### Safety
+    ///
+    /// `ptr` must point to a `std::weak_ptr<T>` object that can be
+    /// safely deleted using C++'s `delete` operator.
+    /// The object must not be used by other means after this call.
+    pub unsafe fn from_raw(ptr: *mut c_void) -> Option<Self> {
+        ptr::NonNull::new(ptr).map(|ptr| WeakPtr {
+            ptr,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns a constant raw pointer to the `std::weak_ptr<T>` object.
+    pub fn as_raw_ptr(&self) -> *const c_void {
+        self.ptr.as_ptr() as *const c_void
+    }
+
+    /// Returns a mutable raw pointer to the `std::weak_ptr<T>` object.
+    pub fn as_mut_raw_ptr(&self) -> *mut c_void {
+        self.ptr.as_ptr()
+    }
+
+    /// Destroys `WeakPtr` without deleting the `std::weak_ptr<T>` object
+    /// and returns a raw pointer to it. The caller becomes the owner of
+    /// the `std::weak_ptr<T>` object.
+    pub fn into_raw_ptr(self) -> *mut c_void {
+        let ptr = self.ptr.as_ptr();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Returns a `SharedPtr` sharing the ownership of the object
+    /// (`std::weak_ptr<T>::lock()`). Returns `None` if the object was already deleted.
+    pub fn upgrade(&self) -> Option<SharedPtr<T>> {
+        let shared = unsafe {
+            SharedPtr::<T>::from_raw((T::shared_ptr_functions().upgrade)(self.as_raw_ptr()))
+                .expect("upgrade returned null")
+        };
+        if shared.is_null() {
+            None
+        } else {
+            Some(shared)
+        }
+    }
+}
+
+/// Creates another `std::weak_ptr<T>` referring to the same object.
+impl<T: SharedPtrTarget> Clone for WeakPtr<T> {
+    fn clone(&self) -> Self {
+        unsafe {
+            WeakPtr::from_raw((T::shared_ptr_functions().clone_weak)(self.as_raw_ptr()))
+                .expect("clone returned null")
+        }
+    }
+}
+
+/// Deletes the `std::weak_ptr<T>` object.
+impl<T: SharedPtrTarget> Drop for WeakPtr<T> {
+    fn drop(&mut self) {
+        unsafe {
+            (T::shared_ptr_functions().destroy_weak)(self.ptr.as_ptr());
+        }
+    }
+}
+
+impl<T: SharedPtrTarget> fmt::Debug for WeakPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the stored pointer is only accessible through a `std::shared_ptr<T>`,
+        // and it's null if the object was already deleted
+        let ptr = unsafe {
+            self.upgrade()
+                .map_or_else(|| Ptr::null(), |shared| shared.as_ptr())
+        };
+        write!(f, "WeakPtr({:?})", ptr)
+    }
+}
+
+/// An owned C++ `std::unique_ptr<T>` object.
+///
+/// `ritual` uses `UniquePtr` in place of `std::unique_ptr<T>` values returned from
+/// and passed to C++ functions. Passing a `UniquePtr` by value to a C++ function
+/// moves the ownership of the object into the function.
+/// Dropping `UniquePtr` deletes the object.
+///
+/// `UniquePtr` implements `Deref`, allowing to call the object's methods
+/// directly. `Deref` will panic if the pointer is null.
+pub struct UniquePtr<T: UniquePtrTarget> {
+    ptr: ptr::NonNull<c_void>,
+    _marker: PhantomData<*mut T>,
+}
+
+impl<T: UniquePtrTarget> UniquePtr<T> {
+    /// Takes ownership of a `std::unique_ptr<T>` object allocated with `new`.
+    /// Returns `None` if `ptr` is null.
+    ///
+    /// ### Safety
+    ///
+    /// `ptr` must point to a `std::unique_ptr<T>` object that can be
+    /// safely deleted using C++'s `delete` operator.
+    /// The object must not be used by other means after this call.
+    pub unsafe fn from_raw(ptr: *mut c_void) -> Option<Self> {
+        ptr::NonNull::new(ptr).map(|ptr| UniquePtr {
+            ptr,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns a constant raw pointer to the `std::unique_ptr<T>` object.
+    pub fn as_raw_ptr(&self) -> *const c_void {
+        self.ptr.as_ptr() as *const c_void
+    }
+
+    /// Returns a mutable raw pointer to the `std::unique_ptr<T>` object.
+    pub fn as_mut_raw_ptr(&self) -> *mut c_void {
+        self.ptr.as_ptr()
+    }
+
+    /// Destroys `UniquePtr` without deleting the `std::unique_ptr<T>` object
+    /// and returns a raw pointer to it. The caller becomes the owner of
+    /// the `std::unique_ptr<T>` object.
+    pub fn into_raw_ptr(self) -> *mut c_void {
+        let ptr = self.ptr.as_ptr();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Returns the stored pointer (`std::unique_ptr<T>::get()`).
+    ///
+    /// ### Safety
+    ///
+    /// The returned pointer must not be used after `UniquePtr` is dropped.
+    pub unsafe fn as_ptr(&self) -> Ptr<T> {
+        Ptr::from_raw((T::unique_ptr_functions().get)(self.as_raw_ptr()) as *const T)
+    }
+
+    /// Returns true if the stored pointer is null.
+    pub fn is_null(&self) -> bool {
+        unsafe { self.as_ptr().is_null() }
+    }
+
+    /// Releases the ownership of the object (`std::unique_ptr<T>::release()`)
+    /// and returns a pointer to it. The caller becomes the owner of the object
+    /// and should ensure that it will be deleted at some point,
+    /// e.g. by converting the pointer to `CppBox`.
+    ///
+    /// ### Safety
+    ///
+    /// The returned pointer must be valid or null.
+    pub unsafe fn into_ptr(self) -> Ptr<T> {
+        let ptr = (T::unique_ptr_functions().release)(self.into_raw_ptr());
+        Ptr::from_raw(ptr as *const T)
+    }
+}
+
+/// Allows to call member functions of `T` and its base classes directly on the pointer.
+impl<T: UniquePtrTarget> Deref for UniquePtr<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe {
+            self.as_ptr()
+                .as_raw_ref()
+                .expect("attempted to deref a null UniquePtr<T>")
+        }
+    }
+}
+
+/// Deletes the `std::unique_ptr<T>` object along with the object it owns.
+impl<T: UniquePtrTarget> Drop for UniquePtr<T> {
+    fn drop(&mut self) {
+        unsafe {
+            (T::unique_ptr_functions().destroy)(self.ptr.as_ptr());
+        }
+    }
+}
+
+impl<T: UniquePtrTarget> fmt::Debug for UniquePtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UniquePtr({:?})", unsafe { self.as_ptr() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SharedPtr, SharedPtrFunctions, SharedPtrTarget};
+    use crate::{Ptr, StaticUpcast};
+    use std::any::Any;
+    use std::ffi::c_void;
+    use std::rc::{Rc, Weak};
+
+    struct Base {
+        value: i32,
+    }
+
+    struct Derived {
+        base: Base,
+    }
+
+    impl StaticUpcast<Base> for Derived {
+        unsafe fn static_upcast(ptr: Ptr<Derived>) -> Ptr<Base> {
+            Ptr::from_raw(&(*ptr.as_raw_ptr()).base)
+        }
+    }
+
+    // `Rc` and `Weak` play the roles of `std::shared_ptr` and `std::weak_ptr`.
+    // The stored pointer is kept separately from the owner, like in
+    // `std::shared_ptr`, so the same functions serve all types.
+    #[derive(Clone)]
+    struct MockShared {
+        owner: Option<Rc<dyn Any>>,
+        ptr: *mut c_void,
+    }
+
+    #[derive(Clone)]
+    struct MockWeak {
+        owner: Option<Weak<dyn Any>>,
+        ptr: *mut c_void,
+    }
+
+    fn into_raw<T>(value: T) -> *mut c_void {
+        Box::into_raw(Box::new(value)) as *mut c_void
+    }
+
+    unsafe extern "C" fn get(ptr: *const c_void) -> *mut c_void {
+        (*(ptr as *const MockShared)).ptr
+    }
+
+    unsafe extern "C" fn clone(ptr: *const c_void) -> *mut c_void {
+        into_raw((*(ptr as *const MockShared)).clone())
+    }
+
+    unsafe extern "C" fn destroy(ptr: *mut c_void) {
+        drop(Box::from_raw(ptr as *mut MockShared));
+    }
+
+    unsafe extern "C" fn to_void(ptr: *const c_void, target: *mut c_void) -> *mut c_void {
+        let shared = &*(ptr as *const MockShared);
+        into_raw(MockShared {
+            owner: shared.owner.clone(),
+            ptr: target,
+        })
+    }
+
+    unsafe extern "C" fn from_void(void_ptr: *mut c_void) -> *mut c_void {
+        // all types share the same representation
+        void_ptr
+    }
+
+    unsafe extern "C" fn downgrade(ptr: *const c_void) -> *mut c_void {
+        let shared = &*(ptr as *const MockShared);
+        into_raw(MockWeak {
+            owner: shared.owner.as_ref().map(Rc::downgrade),
+            ptr: shared.ptr,
+        })
+    }
+
+    unsafe extern "C" fn upgrade(ptr: *const c_void) -> *mut c_void {
+        let weak = &*(ptr as *const MockWeak);
+        let owner = weak.owner.as_ref().and_then(Weak::upgrade);
+        let ptr = if owner.is_some() {
+            weak.ptr
+        } else {
+            std::ptr::null_mut()
+        };
+        into_raw(MockShared { owner, ptr })
+    }
+
+    unsafe extern "C" fn clone_weak(ptr: *const c_void) -> *mut c_void {
+        into_raw((*(ptr as *const MockWeak)).clone())
+    }
+
+    unsafe extern "C" fn destroy_weak(ptr: *mut c_void) {
+        drop(Box::from_raw(ptr as *mut MockWeak));
+    }
+
+    static FUNCTIONS: SharedPtrFunctions = SharedPtrFunctions {
+        get,
+        clone,
+        destroy,
+        to_void,
+        from_void,
+        downgrade,
+        upgrade,
+        clone_weak,
+        destroy_weak,
+    };
+
+    impl SharedPtrTarget for Base {
+        fn shared_ptr_functions() -> &'static SharedPtrFunctions {
+            &FUNCTIONS
+        }
+    }
+
+    impl SharedPtrTarget for Derived {
+        fn shared_ptr_functions() -> &'static SharedPtrFunctions {
+            &FUNCTIONS
+        }
+    }
+
+    fn new_shared(rc: &Rc<Derived>) -> SharedPtr<Derived> {
+        let owner: Rc<dyn Any> = rc.clone();
+        let ptr = into_raw(MockShared {
+            owner: Some(owner),
+            ptr: &**rc as *const Derived as *mut c_void,
+        });
+        unsafe { SharedPtr::from_raw(ptr) }.unwrap()
+    }
+
+    #[test]
+    fn shared_and_weak_ptr() {
+        let rc = Rc::new(Derived {
+            base: Base { value: 3 },
+        });
+        let shared = new_shared(&rc);
+        assert_eq!(shared.base.value, 3);
+        assert!(!shared.is_null());
+
+        let shared2 = shared.clone();
+        assert_eq!(Rc::strong_count(&rc), 3);
+        let weak = shared2.downgrade();
+        drop(shared);
+        drop(shared2);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let shared3 = weak.clone().upgrade().unwrap();
+        assert_eq!(shared3.base.value, 3);
+        drop(shared3);
+        drop(rc);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn cast() {
+        let rc = Rc::new(Derived {
+            base: Base { value: 3 },
+        });
+        let shared = new_shared(&rc);
+        let base: SharedPtr<Base> = unsafe { shared.static_upcast() };
+        assert_eq!(base.value, 3);
+        assert_eq!(
+            unsafe { base.as_ptr().as_raw_ptr() },
+            &rc.base as *const Base
+        );
+        assert_eq!(Rc::strong_count(&rc), 3);
+
+        // the ownership is shared with the original pointer
+        drop(shared);
+        assert_eq!(Rc::strong_count(&rc), 2);
+        let weak = base.downgrade();
+        assert_eq!(
+            format!("{:?}", weak),
+            format!("WeakPtr({:?})", unsafe { base.as_ptr() })
+        );
+        drop(base);
+        drop(rc);
+        assert!(weak.upgrade().is_none());
+        assert_eq!(
+            format!("{:?}", weak),
+            format!("WeakPtr({:?})", unsafe { Ptr::<Base>::null() })
+        );
+    }
+}
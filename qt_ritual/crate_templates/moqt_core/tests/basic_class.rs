@@ -1,5 +1,9 @@
-use moqt_core::basic_class::{inner_struct::InnerEnum, UpdateType};
+use cpp_core::{Flags, Ref};
+use moqt_core::basic_class::{
+    inner_struct::InnerEnum, Direction, FieldPtr, TextStyle, UpdateType, UpdateTypes,
+};
 use moqt_core::{BasicClass, BasicClassField};
+use std::convert::TryFrom;
 
 #[test]
 fn basic_class() {
@@ -68,6 +72,108 @@ fn nested_enum() {
     assert_eq!(x.to_int(), 42);
 }
 
+#[test]
+fn type_aliases() {
+    unsafe {
+        let v = BasicClass::new(1);
+        v.set_foo(1);
+        let update_types: UpdateTypes = UpdateType::Mul3.into();
+        v.update_foo(update_types);
+        assert_eq!(v.foo(), 3);
+
+        let field: FieldPtr = v.class_field_ptr().as_ptr();
+        assert_eq!(field.get(), 42);
+    }
+}
+
+#[test]
+fn native_enum() {
+    assert_eq!(Direction::Up.to_int(), 1);
+    assert_eq!(u8::from(Direction::Down), 2);
+    assert_eq!(Direction::try_from(2), Ok(Direction::Down));
+    assert_eq!(Direction::try_from(3), Err(3));
+
+    unsafe {
+        let v = BasicClass::new(1);
+        assert_eq!(v.direction(), Direction::Up);
+        v.set_direction(Direction::Down);
+        let text = match v.direction() {
+            Direction::Up => "up",
+            Direction::Down => "down",
+        };
+        assert_eq!(text, "down");
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid")]
+fn native_enum_invalid_value() {
+    unsafe {
+        BasicClass::invalid_direction();
+    }
+}
+
+#[test]
+fn flags() {
+    unsafe {
+        let v = BasicClass::new(1);
+        assert!(v.text_style().is_empty());
+        v.set_text_style(TextStyle::Bold | TextStyle::Underline);
+        let style: Flags<TextStyle, _> = v.text_style();
+        assert_eq!(style.to_int(), 5);
+        assert!(style.test_flag(TextStyle::Bold));
+        assert!(!style.test_flag(TextStyle::Italic));
+        v.set_text_style(TextStyle::Italic.into());
+        assert_eq!(v.text_style().to_int(), 2);
+    }
+}
+
+#[test]
+fn out_params() {
+    unsafe {
+        let v = BasicClass::new(10);
+        let (min, max) = v.foo_range();
+        assert_eq!(min, 9);
+        assert_eq!(max, 11);
+
+        assert_eq!(v.divided_foo(2), Some(5));
+        assert_eq!(v.divided_foo(0), None);
+    }
+}
+
+#[test]
+fn nullability() {
+    unsafe {
+        let v = BasicClass::new(1);
+        let field: Ref<BasicClassField> = v.class_field_ptr();
+        assert_eq!(field.get(), 42);
+        assert!(v.find_field(42).is_some());
+        assert!(v.find_field(1).is_none());
+
+        assert_eq!(v.field_value(Some(field)), 42);
+        assert_eq!(v.field_value(None), -1);
+    }
+}
+
+#[test]
+fn rvalue_references() {
+    unsafe {
+        let v = BasicClass::new(1);
+        let field = BasicClassField::new();
+        field.set(5);
+        v.set_class_field(&field);
+        assert_eq!(v.class_field_ptr().get(), 5);
+        assert_eq!(v.moved_fields_count(), 0);
+
+        let field2 = BasicClassField::new();
+        field2.set(7);
+        v.set_class_field_move(field2);
+        assert_eq!(v.class_field_ptr().get(), 7);
+        assert_eq!(v.moved_fields_count(), 1);
+        assert_eq!(field.get(), 5);
+    }
+}
+
 #[test]
 fn vector_getters() {
     unsafe {
@@ -0,0 +1,44 @@
+use cpp_core::CppBox;
+use moqt_core::{moqt_apply_twice, moqt_visit_points, CallbackHolder};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn std_function_argument() {
+    unsafe {
+        assert_eq!(moqt_apply_twice(|x| x * 3, 2), 18);
+
+        let points = Rc::new(RefCell::new(Vec::new()));
+        let points2 = Rc::clone(&points);
+        moqt_visit_points(move |point| {
+            points2.borrow_mut().push((point.x(), point.y()));
+        });
+        assert_eq!(*points.borrow(), vec![(1, 2), (3, 4)]);
+        // the closure is dropped after the call
+        assert_eq!(Rc::strong_count(&points), 1);
+    }
+}
+
+#[test]
+fn stored_std_function() {
+    unsafe {
+        let holder: CppBox<CallbackHolder> = CppBox::default();
+        assert!(!holder.has_function());
+
+        let counter = Rc::new(RefCell::new(0));
+        let counter2 = Rc::clone(&counter);
+        holder.set_function(move |x| {
+            *counter2.borrow_mut() += 1;
+            x + 1
+        });
+        assert!(holder.has_function());
+        assert_eq!(Rc::strong_count(&counter), 2);
+        assert_eq!(holder.call(1), 2);
+        assert_eq!(holder.call(5), 6);
+        assert_eq!(*counter.borrow(), 2);
+
+        holder.clear_function();
+        assert!(!holder.has_function());
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
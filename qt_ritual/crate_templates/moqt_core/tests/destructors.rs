@@ -39,3 +39,20 @@ fn virtual_destructors() {
         assert_eq!(factory.counter(), 0);
     }
 }
+
+#[test]
+fn ownership_transfer() {
+    unsafe {
+        let factory = HandleFactory::new();
+        let h1 = CppBox::new(factory.create()).unwrap();
+        assert_eq!(factory.counter(), 1);
+        factory.destroy(h1);
+        assert_eq!(factory.counter(), 0);
+
+        // boxes of derived classes are accepted as well
+        let h2 = CppBox::new(factory.create_derived()).unwrap();
+        assert_eq!(factory.counter(), 2);
+        factory.destroy_base(h2);
+        assert_eq!(factory.counter(), 0);
+    }
+}
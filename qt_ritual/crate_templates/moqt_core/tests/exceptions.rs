@@ -0,0 +1,25 @@
+use moqt_core::{moqt_checked_divide, moqt_throw_int};
+
+#[test]
+fn no_exception() {
+    unsafe {
+        assert_eq!(moqt_checked_divide(7, 2), Ok(3));
+    }
+}
+
+#[test]
+fn std_exception() {
+    unsafe {
+        let err = moqt_checked_divide(1, 0).unwrap_err();
+        assert_eq!(err.what(), "division by zero");
+        assert_eq!(err.to_string(), "C++ exception: division by zero");
+    }
+}
+
+#[test]
+fn unknown_exception() {
+    unsafe {
+        let err = moqt_throw_int().unwrap_err();
+        assert_eq!(err.what(), "unknown C++ exception");
+    }
+}
@@ -1,5 +1,6 @@
-use cpp_core::CppBox;
+use cpp_core::{CppBox, Ref};
 use moqt_core::{QPoint, QVectorOfInt};
+use std::collections::HashSet;
 
 #[test]
 fn create() {
@@ -7,6 +8,10 @@ fn create() {
         let point: CppBox<QPoint> = QPoint::new_0a();
         assert_eq!(point.x(), 0);
         assert_eq!(point.y(), 0);
+
+        let point2: CppBox<QPoint> = CppBox::default();
+        assert_eq!(point2.x(), 0);
+        assert_eq!(point2.y(), 0);
     }
 }
 
@@ -24,6 +29,42 @@ fn modify() {
     }
 }
 
+#[test]
+fn clone() {
+    unsafe {
+        let point: CppBox<QPoint> = QPoint::new_2a(2, 3);
+        let point2 = point.clone();
+        point2.set_x(4);
+        assert_eq!(point.x(), 2);
+        assert_eq!(point2.x(), 4);
+        assert_eq!(point2.y(), 3);
+    }
+}
+
+#[test]
+fn display() {
+    unsafe {
+        let point: CppBox<QPoint> = QPoint::new_2a(2, -3);
+        assert_eq!(point.to_string(), "QPoint(2, -3)");
+        assert_eq!(point.as_ref().to_string(), "QPoint(2, -3)");
+    }
+}
+
+#[test]
+fn hash() {
+    unsafe {
+        let points: Vec<CppBox<QPoint>> = vec![
+            QPoint::new_2a(1, 2),
+            QPoint::new_2a(3, 4),
+            QPoint::new_2a(1, 2),
+        ];
+        let set: HashSet<Ref<QPoint>> = points.iter().map(|point| point.as_ref()).collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&QPoint::new_2a(3, 4).as_ref()));
+        assert!(!set.contains(&QPoint::new_2a(4, 3).as_ref()));
+    }
+}
+
 #[test]
 fn vec() {
     unsafe {
@@ -54,6 +95,16 @@ fn operators() {
         assert_eq!(c.x(), 5);
         assert_eq!(c.y(), 8);
 
+        let mut d: CppBox<QPoint> = QPoint::new_2a(1, 1);
+        d += a.as_ref();
+        assert_eq!(d.x(), 2);
+        assert_eq!(d.y(), 3);
+
+        let mut d_ref = d.as_ref();
+        d_ref += b.as_ref();
+        assert_eq!(d.x(), 5);
+        assert_eq!(d.y(), 7);
+
         assert_eq!(c, QPoint::new_2a(5, 8).as_ref());
         assert!(c != QPoint::new_2a(5, 9).as_ref());
 
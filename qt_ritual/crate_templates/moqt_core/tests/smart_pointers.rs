@@ -0,0 +1,61 @@
+use cpp_core::{SharedPtr, UniquePtr, WeakPtr};
+use moqt_core::{
+    moqt_consume_square, moqt_make_shared_square, moqt_make_unique_square, moqt_shared_area,
+    moqt_unique_area, moqt_use_count, moqt_weak_shape, Shape, Square,
+};
+
+#[test]
+fn shared_ptr() {
+    unsafe {
+        let square: SharedPtr<Square> = moqt_make_shared_square(3);
+        assert!(!square.is_null());
+        assert_eq!(square.area(), 9);
+        square.set_side(4);
+        assert_eq!(square.side(), 4);
+
+        // derived class pointers are converted to base class pointers implicitly
+        assert_eq!(moqt_shared_area(&square), 16);
+        // the argument holds a temporary reference
+        assert_eq!(moqt_use_count(&square), 2);
+        let square2 = square.clone();
+        assert_eq!(moqt_use_count(&square), 3);
+        drop(square2);
+        assert_eq!(moqt_use_count(&square), 2);
+
+        let shape: SharedPtr<Shape> = square.static_upcast();
+        assert_eq!(shape.area(), 16);
+        assert_eq!(moqt_shared_area(shape.clone()), 16);
+        let square3: SharedPtr<Square> = shape.dynamic_cast().unwrap();
+        assert_eq!(square3.side(), 4);
+        let square4: SharedPtr<Square> = shape.static_downcast();
+        assert_eq!(square4.side(), 4);
+        assert_eq!(moqt_use_count(&shape), 5);
+    }
+}
+
+#[test]
+fn weak_ptr() {
+    unsafe {
+        let square = moqt_make_shared_square(2);
+        let weak: WeakPtr<Shape> = moqt_weak_shape(&square);
+        let shape = weak.upgrade().unwrap();
+        assert_eq!(shape.area(), 4);
+        drop(shape);
+        drop(square);
+        assert!(weak.upgrade().is_none());
+    }
+}
+
+#[test]
+fn unique_ptr() {
+    unsafe {
+        let square: UniquePtr<Square> = moqt_make_unique_square(3);
+        assert!(!square.is_null());
+        assert_eq!(moqt_unique_area(&square), 9);
+        square.set_side(5);
+        assert_eq!(square.area(), 25);
+
+        // the object is moved into the function
+        assert_eq!(moqt_consume_square(square), 5);
+    }
+}
@@ -0,0 +1,30 @@
+use moqt_core::{Overloaded, QByteArray, QString};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+#[test]
+fn c_string_argument() {
+    unsafe {
+        let text = CStr::from_bytes_with_nul(b"text\0").unwrap();
+        assert_eq!(QString::from_utf8_char_int(text, -1).size(), 4);
+        assert_eq!(QString::from_utf8_char_int(text, 2).size(), 2);
+    }
+}
+
+#[test]
+fn string_type_argument() {
+    unsafe {
+        assert_eq!(QString::from_utf8_q_byte_array("text").size(), 4);
+        assert_eq!(QString::from_utf8_q_byte_array(&b"bytes"[..]).size(), 5);
+
+        let s = String::from("string");
+        assert_eq!(QString::from_utf8_q_byte_array(&s).size(), 6);
+
+        let array = QByteArray::from_int_char(3, b'a' as c_char);
+        assert_eq!(QString::from_utf8_q_byte_array(&array).size(), 3);
+        assert_eq!(QString::from_utf8_q_byte_array(array.as_ref()).size(), 3);
+        assert_eq!(QString::from_utf8_q_byte_array(array).size(), 3);
+
+        Overloaded::from_q_string("text");
+    }
+}
@@ -0,0 +1,47 @@
+use cpp_core::Ptr;
+use moqt_core::{AbstractCounterOverrides, AbstractCounterSubclass};
+
+struct Doubler;
+
+impl AbstractCounterOverrides for Doubler {
+    unsafe fn next(&self, _this: Ptr<AbstractCounterSubclass>, value: i32) -> i32 {
+        value * 2
+    }
+}
+
+struct Negator;
+
+impl AbstractCounterOverrides for Negator {
+    unsafe fn step(&self, this: Ptr<AbstractCounterSubclass>) -> i32 {
+        -this.base_step()
+    }
+
+    unsafe fn next(&self, _this: Ptr<AbstractCounterSubclass>, value: i32) -> i32 {
+        -value
+    }
+}
+
+#[test]
+fn pure_virtual_override() {
+    unsafe {
+        let counter = AbstractCounterSubclass::new(3);
+        counter.set_overrides(Doubler);
+        assert_eq!(counter.step(), 3);
+        assert_eq!(counter.next(5), 10);
+        assert_eq!(counter.advance(5), 13);
+    }
+}
+
+#[test]
+fn virtual_override() {
+    unsafe {
+        let counter = AbstractCounterSubclass::new(3);
+        counter.set_overrides(Negator);
+        assert_eq!(counter.step(), -3);
+        assert_eq!(counter.base_step(), 3);
+        assert_eq!(counter.advance(5), -8);
+
+        counter.set_overrides(Doubler);
+        assert_eq!(counter.advance(5), 13);
+    }
+}
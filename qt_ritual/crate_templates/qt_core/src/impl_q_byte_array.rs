@@ -1,5 +1,5 @@
 use crate::QByteArray;
-use cpp_core::CppBox;
+use cpp_core::{CppBox, CppFromBytes, CppFromStr};
 use std::os::raw::{c_char, c_int};
 
 impl QByteArray {
@@ -10,3 +10,17 @@ impl QByteArray {
         QByteArray::from_char_int(slice.as_ptr() as *const c_char, slice.len() as c_int)
     }
 }
+
+/// Allows to pass Rust strings to functions accepting `QByteArray`
+impl CppFromStr for QByteArray {
+    unsafe fn from_str(value: &str) -> CppBox<QByteArray> {
+        QByteArray::from_slice(value.as_bytes())
+    }
+}
+
+/// Allows to pass byte slices to functions accepting `QByteArray`
+impl CppFromBytes for QByteArray {
+    unsafe fn from_bytes(value: &[u8]) -> CppBox<QByteArray> {
+        QByteArray::from_slice(value)
+    }
+}
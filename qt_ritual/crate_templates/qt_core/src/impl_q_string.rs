@@ -1,5 +1,5 @@
 use crate::QString;
-use cpp_core::CppBox;
+use cpp_core::{CppBox, CppFromStr};
 use std::os::raw::{c_char, c_int};
 
 /// Allows to convert Qt strings to `std` strings
@@ -29,6 +29,13 @@ impl QString {
     }
 }
 
+/// Allows to pass Rust strings to functions accepting `QString`
+impl CppFromStr for QString {
+    unsafe fn from_str(value: &str) -> CppBox<QString> {
+        QString::from_std_str(value)
+    }
+}
+
 /// Creates a `QString` from a Rust string.
 ///
 /// This is the same as `QString::from_std_str(str)`.
@@ -1,3 +1,5 @@
+use cpp_core::ops::Index;
+
 #[test]
 fn qrect() {
     unsafe {
@@ -15,5 +17,6 @@ fn qrect() {
 
         assert_eq!(array.index_int(0), 42);
         assert_eq!(array.index_int(1), 47);
+        assert_eq!(array.index(1_i32), 47);
     }
 }
@@ -5,7 +5,7 @@ use crate::doc_parser::{parse_docs, set_crate_root_doc};
 use crate::slot_wrappers::add_signal_slot_wrappers;
 use log::info;
 use qt_ritual_common::{all_crate_names, get_full_build_config, lib_dependencies, lib_folder_name};
-use ritual::config::{Config, CrateDependencyKind, GlobalConfig};
+use ritual::config::{Config, CppNullabilityAnnotation, CrateDependencyKind, GlobalConfig};
 use ritual::config::{CrateDependencySource, CrateProperties};
 use ritual_common::cpp_build_config::CppLibraryType;
 use ritual_common::cpp_build_config::{CppBuildConfigData, CppBuildPaths};
@@ -30,6 +30,7 @@ use self::{charts::charts_config, core::core_config, gui::gui_config, widgets::w
 use crate::lib_configs::qml::qml_config;
 use crate::lib_configs::ui_tools::ui_tools_config;
 use ritual::cpp_data::{CppItem, CppPath};
+use ritual::cpp_function::CppNullability;
 use ritual::cpp_type::CppType;
 use std::env;
 
@@ -158,6 +159,51 @@ pub fn create_config(
                 format_err!("{} env var is missing", MOQT_TEMPLATE_DIR_ENV_VAR_NAME)
             })?);
         config.set_crate_template_path(template_path.join(&crate_name));
+        config.set_native_enums(true);
+        let text_style_path = CppPath::from_good_str("BasicClass::TextStyle");
+        let text_style_path_clone = text_style_path.clone();
+        config.set_cpp_flag_enums_hook(move |path| Ok(path == &text_style_path_clone));
+        config.set_cpp_flags_hook(move |function, index| {
+            let path = function.path.to_cpp_pseudo_code();
+            let is_flags = (path == "BasicClass::textStyle" && index.is_none())
+                || (path == "BasicClass::setTextStyle" && index == Some(0));
+            Ok(if is_flags {
+                Some(text_style_path.clone())
+            } else {
+                None
+            })
+        });
+
+        config.set_cpp_exceptions_hook(|function| {
+            let path = function.path.to_cpp_pseudo_code();
+            Ok(path == "moqt_checked_divide" || path == "moqt_throw_int")
+        });
+        config.set_cpp_subclassing_hook(|path| {
+            Ok(path == &CppPath::from_good_str("AbstractCounter"))
+        });
+
+        let annotations_path = repo_dir_path("qt_ritual/test_assets/moqt")?
+            .join(&crate_name)
+            .join("annotations.toml");
+        if annotations_path.exists() {
+            config.load_cpp_annotations(&annotations_path)?;
+        }
+
+        for &(function, argument, nullability) in &[
+            ("BasicClass::classFieldPtr", None, CppNullability::NonNull),
+            ("BasicClass::findField", None, CppNullability::Nullable),
+            (
+                "BasicClass::fieldValue",
+                Some("field"),
+                CppNullability::Nullable,
+            ),
+        ] {
+            config.add_cpp_nullability(CppNullabilityAnnotation {
+                function: function.to_string(),
+                argument: argument.map(str::to_string),
+                nullability,
+            });
+        }
 
         let steps = config.processing_steps_mut();
         let crate_name_clone = crate_name.to_string();
@@ -215,6 +261,11 @@ pub fn create_config(
 
     config.add_after_cpp_parser_hook(detect_signals_and_slots);
 
+    // `impl CppFromStr` for these types is in the `qt_core` crate template
+    for name in &["QString", "QByteArray"] {
+        config.add_cpp_string_type(CppPath::from_good_str(name));
+    }
+
     let steps = config.processing_steps_mut();
     for cpp_parser_stage in &["cpp_parser", "cpp_parser_stage2"] {
         steps.add_after(
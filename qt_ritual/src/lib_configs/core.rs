@@ -403,6 +403,7 @@ fn add_qpointer(data: &mut ProcessorData<'_>) -> Result<()> {
                         ))]),
                     }),
                     kind: CppTypeDeclarationKind::Class,
+                    deprecation: None,
                 }),
             )?;
         }
@@ -421,14 +422,17 @@ fn connection_to_bool_function() -> CppFunction {
         arguments: vec![CppFunctionArgument {
             name: "connection".into(),
             has_default_value: false,
+            nullability: None,
             argument_type: CppType::new_reference(
                 true,
                 CppType::Class(CppPath::from_good_str("QMetaObject::Connection")),
             ),
         }],
         cast: None,
+        return_nullability: None,
         operator: None,
         declaration_code: None,
+        deprecation: None,
         return_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Bool),
     }
 }
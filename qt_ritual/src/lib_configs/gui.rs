@@ -1,4 +1,4 @@
-use ritual::config::Config;
+use ritual::config::{Config, CppOwnershipTransfer};
 use ritual_common::errors::Result;
 
 /// QtGui specific configuration.
@@ -33,5 +33,26 @@ pub fn gui_config(config: &mut Config) -> Result<()> {
         }
         Ok(true)
     });
+
+    // arguments of these functions are owned by the receiving object after the call
+    let ownership_transfers = &[
+        ("QStandardItemModel::appendRow", 0),
+        ("QStandardItemModel::insertRow", 1),
+        ("QStandardItemModel::setItem", 1),
+        ("QStandardItemModel::setItem", 2),
+        ("QStandardItemModel::setHorizontalHeaderItem", 1),
+        ("QStandardItemModel::setVerticalHeaderItem", 1),
+        ("QStandardItemModel::setItemPrototype", 0),
+        ("QStandardItem::appendRow", 0),
+        ("QStandardItem::insertRow", 1),
+        ("QStandardItem::setChild", 1),
+        ("QStandardItem::setChild", 2),
+    ];
+    for &(function, argument) in ownership_transfers {
+        config.add_cpp_ownership_transfer(CppOwnershipTransfer {
+            function: function.to_string(),
+            argument,
+        });
+    }
     Ok(())
 }
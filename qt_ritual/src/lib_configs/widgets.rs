@@ -1,4 +1,4 @@
-use ritual::config::Config;
+use ritual::config::{Config, CppOwnershipTransfer};
 use ritual_common::errors::Result;
 
 /// QtWidgets specific configuration.
@@ -17,5 +17,62 @@ pub fn widgets_config(config: &mut Config) -> Result<()> {
         Ok(true)
     });
 
+    // arguments of these functions are owned by the receiving object after the call
+    let ownership_transfers = &[
+        ("QWidget::setLayout", 0),
+        ("QLayout::addItem", 0),
+        ("QLayout::addWidget", 0),
+        ("QBoxLayout::addItem", 0),
+        ("QBoxLayout::addWidget", 0),
+        ("QBoxLayout::addLayout", 0),
+        ("QBoxLayout::insertItem", 1),
+        ("QBoxLayout::insertWidget", 1),
+        ("QBoxLayout::insertLayout", 1),
+        ("QGridLayout::addItem", 0),
+        ("QGridLayout::addWidget", 0),
+        ("QGridLayout::addLayout", 0),
+        ("QFormLayout::addItem", 0),
+        ("QFormLayout::addRow", 0),
+        ("QFormLayout::addRow", 1),
+        ("QFormLayout::insertRow", 1),
+        ("QFormLayout::insertRow", 2),
+        ("QStackedLayout::addWidget", 0),
+        ("QStackedLayout::insertWidget", 1),
+        ("QMainWindow::setCentralWidget", 0),
+        ("QMainWindow::setMenuBar", 0),
+        ("QMainWindow::setMenuWidget", 0),
+        ("QMainWindow::setStatusBar", 0),
+        ("QMainWindow::addToolBar", 0),
+        ("QMainWindow::addToolBar", 1),
+        ("QMainWindow::addDockWidget", 1),
+        ("QScrollArea::setWidget", 0),
+        ("QDockWidget::setWidget", 0),
+        ("QStackedWidget::addWidget", 0),
+        ("QStackedWidget::insertWidget", 1),
+        ("QTabWidget::addTab", 0),
+        ("QTabWidget::insertTab", 1),
+        ("QToolBox::addItem", 0),
+        ("QToolBox::insertItem", 1),
+        ("QSplitter::addWidget", 0),
+        ("QSplitter::insertWidget", 1),
+        ("QListWidget::addItem", 0),
+        ("QListWidget::insertItem", 1),
+        ("QTreeWidget::addTopLevelItem", 0),
+        ("QTreeWidget::insertTopLevelItem", 1),
+        ("QTreeWidget::setHeaderItem", 0),
+        ("QTreeWidgetItem::addChild", 0),
+        ("QTreeWidgetItem::insertChild", 1),
+        ("QTableWidget::setItem", 2),
+        ("QTableWidget::setHorizontalHeaderItem", 1),
+        ("QTableWidget::setVerticalHeaderItem", 1),
+        ("QGraphicsScene::addItem", 0),
+    ];
+    for &(function, argument) in ownership_transfers {
+        config.add_cpp_ownership_transfer(CppOwnershipTransfer {
+            function: function.to_string(),
+            argument,
+        });
+    }
+
     Ok(())
 }
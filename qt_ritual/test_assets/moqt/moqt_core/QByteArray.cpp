@@ -13,7 +13,7 @@ const char* QByteArray::constData() const {
 }
 
 int QByteArray::size() const {
-    return 0;
+    return m_size;
 }
 
 const char* QByteArray::begin() const {
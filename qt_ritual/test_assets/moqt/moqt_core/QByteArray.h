@@ -5,15 +5,17 @@
 
 class MOQT_CORE_EXPORT QByteArray {
 public:
-    QByteArray(int, char) {}
-    QByteArray(const char*, int) {}
-    QByteArray() {}
+    QByteArray(int size, char) : m_size(size) {}
+    QByteArray(const char*, int size) : m_size(size) {}
+    QByteArray() : m_size(0) {}
     char* data();
     const char* data() const;
     const char* constData() const;
     int size() const;
     const char* begin() const;
     const char* end() const;
+private:
+    int m_size;
 };
 
 #endif //QBYTEARRAY_H
@@ -65,3 +65,7 @@ bool operator==(const QPoint& one, int64_t other) {
 int operator==(const QPoint& one, float other) {
     return 2;
 }
+
+std::ostream& operator<<(std::ostream& stream, const QPoint& point) {
+    return stream << "QPoint(" << point.x() << ", " << point.y() << ")";
+}
@@ -3,6 +3,8 @@
 
 #include "moqt_core_exports.h"
 #include <cstdint>
+#include <functional>
+#include <ostream>
 #include "QVector.h"
 
 class MOQT_CORE_EXPORT QPoint {
@@ -46,6 +48,17 @@ MOQT_CORE_EXPORT bool operator==(const QPoint& one, int64_t other);
 
 MOQT_CORE_EXPORT int operator==(const QPoint& one, float other);
 
+MOQT_CORE_EXPORT std::ostream& operator<<(std::ostream& stream, const QPoint& point);
+
+namespace std {
+    template<>
+    struct hash<QPoint> {
+        size_t operator()(const QPoint& point) const {
+            return std::hash<int>()(point.x()) * 31 + std::hash<int>()(point.y());
+        }
+    };
+}
+
 template <typename T>
 inline QPoint operator<<(QPoint point, const QVector<T> &vec) {
     return QPoint();
@@ -1,11 +1,16 @@
 #include "QString.h"
+#include <cstring>
 
 QString QString::fromUtf8(const char *str, int size) {
-    return QString();
+    QString result;
+    result.m_size = size < 0 ? static_cast<int>(strlen(str)) : size;
+    return result;
 }
 
 QString QString::fromUtf8(const QByteArray& str) {
-    return QString();
+    QString result;
+    result.m_size = str.size();
+    return result;
 }
 
 QByteArray QString::toUtf8() const {
@@ -15,3 +20,7 @@ QByteArray QString::toUtf8() const {
 QByteArray QString::toLocal8Bit() const {
     return QByteArray();
 }
+
+int QString::size() const {
+    return m_size;
+}
@@ -16,10 +16,14 @@ public:
 
 class MOQT_CORE_EXPORT QString {
 public:
+    QString() : m_size(0) {}
     static QString fromUtf8(const char *str, int size = -1);
     static QString fromUtf8(const QByteArray& str);
     QByteArray toUtf8() const;
     QByteArray toLocal8Bit() const;
+    int size() const;
+private:
+    int m_size;
 };
 
 
@@ -1,7 +1,11 @@
 #include "basic_class.h"
+#include <utility>
 
 BasicClass::BasicClass(int x) : int_field(1), intReference_field(int_field) {
     m_foo = x;
+    m_direction = Direction::Up;
+    m_text_style = 0;
+    m_moved_fields_count = 0;
     intPointerField = nullptr;
 }
 
@@ -26,6 +30,64 @@ void BasicClass::updateFoo(UpdateTypes updateTypes) {
     }
 }
 
+BasicClass::Direction BasicClass::direction() const {
+    return m_direction;
+}
+
+void BasicClass::setDirection(Direction direction) {
+    m_direction = direction;
+}
+
+BasicClass::Direction BasicClass::invalidDirection() {
+    return static_cast<Direction>(3);
+}
+
+int BasicClass::textStyle() const {
+    return m_text_style;
+}
+
+void BasicClass::setTextStyle(int style) {
+    m_text_style = style;
+}
+
+void BasicClass::fooRange(int* min, int* max) const {
+    *min = m_foo - 1;
+    *max = m_foo + 1;
+}
+
+int BasicClass::dividedFoo(int divisor, bool* ok) const {
+    bool is_ok = divisor != 0;
+    if (ok) {
+        *ok = is_ok;
+    }
+    return is_ok ? m_foo / divisor : 0;
+}
+
+BasicClassField* BasicClass::classFieldPtr() {
+    return &class_field;
+}
+
+BasicClassField* BasicClass::findField(int value) {
+    return class_field.get() == value ? &class_field : nullptr;
+}
+
+int BasicClass::fieldValue(const BasicClassField* field) const {
+    return field ? field->get() : -1;
+}
+
+void BasicClass::setClassField(const BasicClassField& field) {
+    class_field = field;
+}
+
+void BasicClass::setClassField(BasicClassField&& field) {
+    class_field = std::move(field);
+    m_moved_fields_count++;
+}
+
+int BasicClass::movedFieldsCount() const {
+    return m_moved_fields_count;
+}
+
 QVector<int> BasicClass::get_vector_int() const {
     auto r = QVector<int>();
     r.append(1);
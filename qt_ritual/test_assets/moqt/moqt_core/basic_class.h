@@ -42,9 +42,44 @@ public:
     };
 
     typedef QFlags<UpdateType> UpdateTypes;
+    typedef BasicClassField* FieldPtr;
+
+    enum class Direction : uint8_t {
+        Up = 1,
+        Down = 2,
+    };
+
+    Direction direction() const;
+    void setDirection(Direction direction);
+    // returns a value that doesn't match any of the enumerators
+    static Direction invalidDirection();
+
+    // bitmask enum passed as int (without QFlags)
+    enum TextStyle {
+        Bold = 1,
+        Italic = 2,
+        Underline = 4,
+    };
+
+    int textStyle() const;
+    void setTextStyle(int style);
 
     void updateFoo(UpdateTypes updateTypes);
 
+    // out-parameters
+    void fooRange(int* min, int* max) const;
+    int dividedFoo(int divisor, bool* ok = nullptr) const;
+
+    // nullability
+    BasicClassField* classFieldPtr();
+    BasicClassField* findField(int value);
+    int fieldValue(const BasicClassField* field) const;
+
+    // rvalue references
+    void setClassField(const BasicClassField& field);
+    void setClassField(BasicClassField&& field);
+    int movedFieldsCount() const;
+
     QVector<int> get_vector_int() const;
     QVector<BasicClassField> get_vector_class() const;
 
@@ -90,6 +125,9 @@ public:
 
 private:
     int m_foo;
+    Direction m_direction;
+    int m_text_style;
+    int m_moved_fields_count;
 };
 
 QFlags<BasicClass::UpdateType> operator|(BasicClass::UpdateType f1, BasicClass::UpdateType f2);
@@ -0,0 +1,26 @@
+#include "callbacks.h"
+
+void CallbackHolder::setFunction(const std::function<int(int)>& function) {
+    m_function = function;
+}
+
+void CallbackHolder::clearFunction() {
+    m_function = nullptr;
+}
+
+bool CallbackHolder::hasFunction() const {
+    return static_cast<bool>(m_function);
+}
+
+int CallbackHolder::call(int value) const {
+    return m_function(value);
+}
+
+int moqt_apply_twice(std::function<int(int)> function, int value) {
+    return function(function(value));
+}
+
+void moqt_visit_points(const std::function<void(const QPoint&)>& function) {
+    function(QPoint(1, 2));
+    function(QPoint(3, 4));
+}
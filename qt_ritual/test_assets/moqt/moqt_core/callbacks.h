@@ -0,0 +1,22 @@
+#ifndef CALLBACKS_H
+#define CALLBACKS_H
+
+#include "moqt_core_exports.h"
+#include "QPoint.h"
+#include <functional>
+
+class MOQT_CORE_EXPORT CallbackHolder {
+public:
+    void setFunction(const std::function<int(int)>& function);
+    void clearFunction();
+    bool hasFunction() const;
+    int call(int value) const;
+
+private:
+    std::function<int(int)> m_function;
+};
+
+MOQT_CORE_EXPORT int moqt_apply_twice(std::function<int(int)> function, int value);
+MOQT_CORE_EXPORT void moqt_visit_points(const std::function<void(const QPoint&)>& function);
+
+#endif // CALLBACKS_H
@@ -12,6 +12,12 @@ DerivedHandle* HandleFactory::createDerived() {
 DerivedHandle2* HandleFactory::createDerived2() {
     return new DerivedHandle2(this);
 }
+void HandleFactory::destroy(Handle* handle) {
+    delete handle;
+}
+void HandleFactory::destroyBase(BaseHandle* handle) {
+    delete handle;
+}
 
 DestructorLess::DestructorLess() {
 
@@ -11,6 +11,8 @@ public:
     BaseHandle* createBase();
     DerivedHandle* createDerived();
     DerivedHandle2* createDerived2();
+    void destroy(Handle* handle);
+    void destroyBase(BaseHandle* handle);
     int counter() { return m_counter; }
 
 private:
@@ -0,0 +1,13 @@
+#include "exceptions.h"
+#include <stdexcept>
+
+int moqt_checked_divide(int a, int b) {
+    if (b == 0) {
+        throw std::invalid_argument("division by zero");
+    }
+    return a / b;
+}
+
+void moqt_throw_int() {
+    throw 42;
+}
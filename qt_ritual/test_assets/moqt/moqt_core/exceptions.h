@@ -0,0 +1,9 @@
+#ifndef EXCEPTIONS_H
+#define EXCEPTIONS_H
+
+#include "moqt_core_exports.h"
+
+MOQT_CORE_EXPORT int moqt_checked_divide(int a, int b);
+MOQT_CORE_EXPORT void moqt_throw_int();
+
+#endif // EXCEPTIONS_H
@@ -12,3 +12,7 @@
 #include "Overloaded.h"
 #include "QByteArray.h"
 #include "QVector.h"
+#include "callbacks.h"
+#include "smart_pointers.h"
+#include "subclasses.h"
+#include "exceptions.h"
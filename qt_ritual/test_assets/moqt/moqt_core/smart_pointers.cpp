@@ -0,0 +1,49 @@
+#include "smart_pointers.h"
+
+Shape::~Shape() {}
+
+int Shape::area() const {
+    return 0;
+}
+
+Square::Square(int side) : m_side(side) {}
+
+int Square::area() const {
+    return m_side * m_side;
+}
+
+int Square::side() const {
+    return m_side;
+}
+
+void Square::setSide(int side) {
+    m_side = side;
+}
+
+std::shared_ptr<Square> moqt_make_shared_square(int side) {
+    return std::make_shared<Square>(side);
+}
+
+int moqt_shared_area(std::shared_ptr<Shape> shape) {
+    return shape ? shape->area() : -1;
+}
+
+int moqt_use_count(const std::shared_ptr<Shape>& shape) {
+    return static_cast<int>(shape.use_count());
+}
+
+std::weak_ptr<Shape> moqt_weak_shape(const std::shared_ptr<Shape>& shape) {
+    return shape;
+}
+
+std::unique_ptr<Square> moqt_make_unique_square(int side) {
+    return std::unique_ptr<Square>(new Square(side));
+}
+
+int moqt_unique_area(const std::unique_ptr<Square>& square) {
+    return square ? square->area() : -1;
+}
+
+int moqt_consume_square(std::unique_ptr<Square> square) {
+    return square->side();
+}
@@ -0,0 +1,33 @@
+#ifndef SMART_POINTERS_H
+#define SMART_POINTERS_H
+
+#include "moqt_core_exports.h"
+#include <memory>
+
+class MOQT_CORE_EXPORT Shape {
+public:
+    virtual ~Shape();
+    virtual int area() const;
+};
+
+class MOQT_CORE_EXPORT Square : public Shape {
+public:
+    explicit Square(int side);
+    int area() const override;
+    int side() const;
+    void setSide(int side);
+
+private:
+    int m_side;
+};
+
+MOQT_CORE_EXPORT std::shared_ptr<Square> moqt_make_shared_square(int side);
+MOQT_CORE_EXPORT int moqt_shared_area(std::shared_ptr<Shape> shape);
+MOQT_CORE_EXPORT int moqt_use_count(const std::shared_ptr<Shape>& shape);
+MOQT_CORE_EXPORT std::weak_ptr<Shape> moqt_weak_shape(const std::shared_ptr<Shape>& shape);
+
+MOQT_CORE_EXPORT std::unique_ptr<Square> moqt_make_unique_square(int side);
+MOQT_CORE_EXPORT int moqt_unique_area(const std::unique_ptr<Square>& square);
+MOQT_CORE_EXPORT int moqt_consume_square(std::unique_ptr<Square> square);
+
+#endif // SMART_POINTERS_H
@@ -0,0 +1,13 @@
+#include "subclasses.h"
+
+AbstractCounter::AbstractCounter(int step) : m_step(step) {}
+
+AbstractCounter::~AbstractCounter() {}
+
+int AbstractCounter::step() const {
+    return m_step;
+}
+
+int AbstractCounter::advance(int value) {
+    return next(value) + step();
+}
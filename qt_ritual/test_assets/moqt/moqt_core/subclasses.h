@@ -0,0 +1,20 @@
+#ifndef SUBCLASSES_H
+#define SUBCLASSES_H
+
+#include "moqt_core_exports.h"
+
+class MOQT_CORE_EXPORT AbstractCounter {
+public:
+    AbstractCounter(int step);
+    virtual ~AbstractCounter();
+
+    virtual int step() const;
+    virtual int next(int value) = 0;
+
+    int advance(int value);
+
+private:
+    int m_step;
+};
+
+#endif // SUBCLASSES_H
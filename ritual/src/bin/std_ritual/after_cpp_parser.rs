@@ -54,21 +54,26 @@ fn add_vector_from_pointers_functions(data: &mut ProcessorData<'_>) -> Result<()
                     name: "first".into(),
                     argument_type: CppType::new_pointer(true, t.clone()),
                     has_default_value: false,
+                    nullability: None,
                 },
                 CppFunctionArgument {
                     name: "last".into(),
                     argument_type: CppType::new_pointer(true, t.clone()),
                     has_default_value: false,
+                    nullability: None,
                 },
                 CppFunctionArgument {
                     name: "alloc".into(),
                     argument_type: allocator_type,
                     has_default_value: true,
+                    nullability: None,
                 },
             ],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: None,
+            deprecation: None,
         }),
     )?;
     data.add_cpp_item(
@@ -104,16 +109,20 @@ fn add_vector_from_pointers_functions(data: &mut ProcessorData<'_>) -> Result<()
                     name: "first".into(),
                     argument_type: CppType::new_pointer(true, t.clone()),
                     has_default_value: false,
+                    nullability: None,
                 },
                 CppFunctionArgument {
                     name: "last".into(),
                     argument_type: CppType::new_pointer(true, t),
                     has_default_value: false,
+                    nullability: None,
                 },
             ],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: None,
+            deprecation: None,
         }),
     )?;
     Ok(())
@@ -198,6 +207,7 @@ fn add_vector_instantiations(data: &mut ProcessorData<'_>) -> Result<()> {
                     name: "vector".into(),
                     template_arguments: Some(vec![arg.clone(), allocator_type]),
                 }),
+                deprecation: None,
             }),
         )?;
     }
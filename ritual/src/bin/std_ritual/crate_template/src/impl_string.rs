@@ -0,0 +1,17 @@
+use crate::String;
+use cpp_core::{CppBox, CppFromBytes, CppFromStr};
+use std::os::raw::c_char;
+
+/// Allows to pass Rust strings to functions accepting `std::string`
+impl CppFromStr for String {
+    unsafe fn from_str(value: &str) -> CppBox<String> {
+        String::from_bytes(value.as_bytes())
+    }
+}
+
+/// Allows to pass byte slices to functions accepting `std::string`
+impl CppFromBytes for String {
+    unsafe fn from_bytes(value: &[u8]) -> CppBox<String> {
+        String::from_char_usize(value.as_ptr() as *const c_char, value.len())
+    }
+}
@@ -1 +1 @@
-
+mod impl_string;
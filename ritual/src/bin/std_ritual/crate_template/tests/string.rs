@@ -27,3 +27,21 @@ fn string_from_slice() {
         assert_eq!(CStr::from_ptr(s.c_str()).to_str().unwrap(), "string");
     }
 }
+
+#[test]
+fn string_arguments() {
+    unsafe {
+        let data = CStr::from_bytes_with_nul(b"text\0").unwrap();
+        let s = String::from_char_usize(data, 2);
+        assert_eq!(CStr::from_ptr(s.c_str()).to_str().unwrap(), "te");
+
+        let s2 = String::new_copy("string");
+        assert_eq!(CStr::from_ptr(s2.c_str()).to_str().unwrap(), "string");
+
+        let s3 = String::new_copy(&b"bytes"[..]);
+        assert_eq!(s3.length(), 5);
+
+        let s4 = String::new_copy(&s2);
+        assert_eq!(s4.length(), 6);
+    }
+}
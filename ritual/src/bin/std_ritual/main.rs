@@ -1,6 +1,6 @@
 use ritual::cli;
 use ritual::config::{Config, CrateProperties, GlobalConfig};
-use ritual::cpp_data::CppPath;
+use ritual::cpp_data::{CppPath, CppPathItem};
 use ritual::cpp_type::{CppBuiltInNumericType, CppType};
 use ritual::rust_info::{NameType, RustPathScope};
 use ritual::rust_type::RustPath;
@@ -108,6 +108,29 @@ fn create_config(mut crate_properties: CrateProperties) -> Result<Config> {
         after_cpp_parser::hook,
     )?;
 
+    // `impl CppFromStr` for `std::string` is in the crate template
+    let char_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Char);
+    let std_type = |name: &str| {
+        CppType::Class(CppPath::from_items(vec![
+            CppPathItem::from_good_str("std"),
+            CppPathItem {
+                name: name.into(),
+                template_arguments: Some(vec![char_type.clone()]),
+            },
+        ]))
+    };
+    config.add_cpp_string_type(CppPath::from_items(vec![
+        CppPathItem::from_good_str("std"),
+        CppPathItem {
+            name: "basic_string".into(),
+            template_arguments: Some(vec![
+                char_type.clone(),
+                std_type("char_traits"),
+                std_type("allocator"),
+            ]),
+        },
+    ]));
+
     let namespace = CppPath::from_good_str("std");
     config.set_rust_path_scope_hook(move |path| {
         if path == &namespace {
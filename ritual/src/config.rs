@@ -2,16 +2,18 @@
 
 use crate::cpp_checker::PreliminaryTest;
 use crate::cpp_data::{CppItem, CppPath};
+use crate::cpp_function::{CppFunction, CppNullability};
 use crate::cpp_parser::CppParserOutput;
 use crate::processor::{ProcessingSteps, ProcessorData};
 use crate::rust_info::{NameType, RustItem, RustPathScope};
 use crate::rust_type::RustPath;
 use ritual_common::cpp_build_config::{CppBuildConfig, CppBuildPaths};
-use ritual_common::errors::{bail, Result};
+use ritual_common::errors::{bail, Result, ResultExt};
+use ritual_common::file_utils::{file_to_string, load_json};
 use ritual_common::target::Target;
 use ritual_common::toml;
 use serde_derive::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CrateDependencyKind {
@@ -177,6 +179,63 @@ pub type RustItemHook = dyn Fn(&mut RustItem, &ProcessorData<'_>) -> Result<()>
 pub type AfterCppParserHook =
     dyn Fn(&mut ProcessorData<'_>, &CppParserOutput) -> Result<()> + 'static;
 pub type CppItemFilterHook = dyn Fn(&CppItem) -> Result<bool> + 'static;
+pub type CppExceptionsHook = dyn Fn(&CppFunction) -> Result<bool> + 'static;
+pub type CppSubclassingHook = dyn Fn(&CppPath) -> Result<bool> + 'static;
+pub type CppFlagEnumsHook = dyn Fn(&CppPath) -> Result<bool> + 'static;
+pub type CppFlagsHook = dyn Fn(&CppFunction, Option<usize>) -> Result<Option<CppPath>> + 'static;
+
+/// Kind of a C++ out-parameter (see `Config::add_cpp_out_param`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CppOutParamKind {
+    /// The value written by the C++ function is returned from the Rust function.
+    Value,
+    /// A `bool` that the C++ function sets to `false` on failure (e.g. `bool* ok`).
+    /// The Rust function returns `None` in this case.
+    OkFlag,
+}
+
+/// Annotation that marks an argument of a C++ function as an out-parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CppOutParam {
+    /// Path of the function (e.g. `QString::toInt`). All overloads are affected.
+    pub function: String,
+    /// Name of the argument.
+    pub argument: String,
+    pub kind: CppOutParamKind,
+}
+
+/// Annotation that specifies nullability of a pointer argument or return value
+/// of a C++ function (see `Config::add_cpp_nullability`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CppNullabilityAnnotation {
+    /// Path of the function (e.g. `QObject::setParent`). All overloads are affected.
+    pub function: String,
+    /// Name of the argument, or `None` for the return value.
+    pub argument: Option<String>,
+    pub nullability: CppNullability,
+}
+
+/// Annotation that marks a pointer argument of a C++ function
+/// that takes ownership of the object (see `Config::add_cpp_ownership_transfer`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CppOwnershipTransfer {
+    /// Path of the function (e.g. `QWidget::setLayout`). All overloads are affected.
+    pub function: String,
+    /// Index of the argument (starting from 0).
+    pub argument: usize,
+}
+
+/// Contents of a file loaded with `Config::load_cpp_annotations`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CppAnnotationsFile {
+    #[serde(default)]
+    out_params: Vec<CppOutParam>,
+    #[serde(default)]
+    nullability: Vec<CppNullabilityAnnotation>,
+    #[serde(default)]
+    ownership_transfers: Vec<CppOwnershipTransfer>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkerLibraryConfig {
@@ -211,6 +270,9 @@ pub struct Config {
     cpp_build_config: CppBuildConfig,
     cpp_build_paths: CppBuildPaths,
     cpp_parser_arguments: Vec<String>,
+    parse_macro_constants: bool,
+    parse_doc_comments: bool,
+    native_enums: bool,
     processing_steps: ProcessingSteps,
     movable_types_hook: Option<Box<dyn Fn(&CppPath) -> Result<MovableTypesHookOutput>>>,
     cpp_parser_path_hook: Option<Box<dyn Fn(&CppPath) -> Result<bool>>>,
@@ -219,6 +281,14 @@ pub struct Config {
     rust_item_hook: Option<Box<RustItemHook>>,
     after_cpp_parser_hooks: Vec<Box<AfterCppParserHook>>,
     cpp_item_filter_hook: Option<Box<CppItemFilterHook>>,
+    cpp_exceptions_hook: Option<Box<CppExceptionsHook>>,
+    cpp_subclassing_hook: Option<Box<CppSubclassingHook>>,
+    cpp_flag_enums_hook: Option<Box<CppFlagEnumsHook>>,
+    cpp_flags_hook: Option<Box<CppFlagsHook>>,
+    cpp_out_params: Vec<CppOutParam>,
+    cpp_nullability: Vec<CppNullabilityAnnotation>,
+    cpp_ownership_transfers: Vec<CppOwnershipTransfer>,
+    cpp_string_types: Vec<CppPath>,
     cluster_config: Option<ClusterConfig>,
     cpp_checker_tests: Vec<PreliminaryTest>,
     write_dependencies_local_paths: bool,
@@ -242,6 +312,9 @@ impl Config {
             target_include_paths: Default::default(),
             include_directives: Default::default(),
             cpp_parser_arguments: Default::default(),
+            parse_macro_constants: false,
+            parse_doc_comments: false,
+            native_enums: false,
             cpp_build_config: Default::default(),
             movable_types_hook: Default::default(),
             processing_steps: Default::default(),
@@ -252,6 +325,14 @@ impl Config {
             rust_item_hook: Default::default(),
             after_cpp_parser_hooks: Default::default(),
             cpp_item_filter_hook: Default::default(),
+            cpp_exceptions_hook: Default::default(),
+            cpp_subclassing_hook: Default::default(),
+            cpp_flag_enums_hook: Default::default(),
+            cpp_flags_hook: Default::default(),
+            cpp_out_params: Default::default(),
+            cpp_nullability: Default::default(),
+            cpp_ownership_transfers: Default::default(),
+            cpp_string_types: Default::default(),
             cluster_config: None,
             cpp_checker_tests: Default::default(),
             write_dependencies_local_paths: true,
@@ -302,6 +383,58 @@ impl Config {
         }
     }
 
+    /// Enables or disables parsing of preprocessor macros.
+    ///
+    /// If enabled, object-like macros that expand to an integer,
+    /// floating point or string literal are converted to Rust constants.
+    /// The constants are placed in a module corresponding to the header
+    /// that defines the macro. Disabled by default.
+    pub fn set_parse_macro_constants(&mut self, value: bool) {
+        self.parse_macro_constants = value;
+    }
+
+    /// Returns value set by `Config::set_parse_macro_constants`.
+    pub fn parse_macro_constants(&self) -> bool {
+        self.parse_macro_constants
+    }
+
+    /// Enables or disables extraction of documentation from C++ comments.
+    ///
+    /// If enabled, Doxygen-style comments attached to C++ declarations
+    /// are converted to documentation of the generated Rust items.
+    /// `\brief`, `\param` and `\return` commands are recognized.
+    /// Disabled by default. This option should not be used if documentation
+    /// is provided by a custom processing step (e.g. Qt's documentation parser).
+    pub fn set_parse_doc_comments(&mut self, value: bool) {
+        self.parse_doc_comments = value;
+    }
+
+    /// Returns value set by `Config::set_parse_doc_comments`.
+    pub fn parse_doc_comments(&self) -> bool {
+        self.parse_doc_comments
+    }
+
+    /// Enables or disables generation of Rust `enum`s for C++ scoped enums.
+    ///
+    /// If enabled, an `enum class` is converted to a `#[repr]` Rust `enum`
+    /// if its underlying type has a fixed size, all its values are distinct,
+    /// and it's not a flag enum (see `Config::set_cpp_flag_enums_hook`). Such enums implement
+    /// `TryFrom` for the underlying type and can be matched exhaustively.
+    /// Other enums are still converted to a newtype struct with associated constants.
+    /// Disabled by default.
+    ///
+    /// Values of native enums are passed through FFI as integers. If the C++ library
+    /// returns a value that doesn't correspond to any of the declared enumerators,
+    /// the Rust function panics.
+    pub fn set_native_enums(&mut self, value: bool) {
+        self.native_enums = value;
+    }
+
+    /// Returns value set by `Config::set_native_enums`.
+    pub fn native_enums(&self) -> bool {
+        self.native_enums
+    }
+
     /// Sets `CppBuildPaths` value for this config. These paths
     /// are used for testing C++ methods while processing the library,
     /// but they are not used when building the generated crate.
@@ -499,6 +632,199 @@ impl Config {
         self.cpp_item_filter_hook.as_ref().map(|b| &**b)
     }
 
+    /// Sets a hook that determines whether C++ exceptions thrown by a function
+    /// should be caught by its FFI wrapper. If the hook returns `true`, the wrapper
+    /// catches the exception, and the Rust function returns
+    /// `Result<_, cpp_core::CppException>` carrying the exception's message.
+    /// Use `|_| Ok(true)` to catch exceptions in all functions.
+    ///
+    /// If the hook is not set, exceptions are not caught. Note that an exception
+    /// propagating through an FFI function results in undefined behavior.
+    pub fn set_cpp_exceptions_hook(
+        &mut self,
+        hook: impl Fn(&CppFunction) -> Result<bool> + 'static,
+    ) {
+        assert!(
+            self.cpp_exceptions_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.cpp_exceptions_hook = Some(Box::new(hook));
+    }
+
+    pub fn cpp_exceptions_hook(&self) -> Option<&CppExceptionsHook> {
+        self.cpp_exceptions_hook.as_ref().map(|b| &**b)
+    }
+
+    /// Sets a hook that determines whether C++ classes can be subclassed from Rust.
+    /// If the hook returns `true` for a class, a C++ subclass named `{Class}Subclass`
+    /// is generated. The subclass overrides all virtual methods of the class
+    /// and forwards them to Rust closures (if they are set). The corresponding
+    /// Rust struct provides `set_overrides` method that accepts an implementation
+    /// of the `{Class}Overrides` trait.
+    ///
+    /// The subclass is not generated if a callback of any pure virtual method
+    /// of the class can't be set from Rust.
+    ///
+    /// If the hook is not set, subclasses are not generated.
+    pub fn set_cpp_subclassing_hook(&mut self, hook: impl Fn(&CppPath) -> Result<bool> + 'static) {
+        assert!(
+            self.cpp_subclassing_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.cpp_subclassing_hook = Some(Box::new(hook));
+    }
+
+    pub fn cpp_subclassing_hook(&self) -> Option<&CppSubclassingHook> {
+        self.cpp_subclassing_hook.as_ref().map(|b| &**b)
+    }
+
+    /// Sets a hook that determines whether values of a C++ enum are bit flags
+    /// that can be combined with `|`. If the hook returns `true` for an enum,
+    /// the enum's Rust type can be converted to `cpp_core::Flags` and implements `BitOr`.
+    /// Such enums are never converted to native Rust enums.
+    ///
+    /// Qt's `QFlags` doesn't need this hook because flag enums are detected automatically
+    /// from `QFlags` instantiations.
+    pub fn set_cpp_flag_enums_hook(&mut self, hook: impl Fn(&CppPath) -> Result<bool> + 'static) {
+        assert!(
+            self.cpp_flag_enums_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.cpp_flag_enums_hook = Some(Box::new(hook));
+    }
+
+    pub fn cpp_flag_enums_hook(&self) -> Option<&CppFlagEnumsHook> {
+        self.cpp_flag_enums_hook.as_ref().map(|b| &**b)
+    }
+
+    /// Sets a hook that determines whether an integer argument or return value
+    /// of a C++ function is a combination of flags of an enum. The hook receives
+    /// the function and the index of the argument (or `None` for the return value).
+    /// If the hook returns the path of an enum, the value is represented as
+    /// `cpp_core::Flags<E, T>` in the Rust API, where `T` corresponds to the underlying
+    /// type of the enum. The integer type of the value must have the same size.
+    /// The enum should be also marked with `Config::set_cpp_flag_enums_hook`
+    /// to allow converting its values to `Flags`.
+    ///
+    /// If the hook is not set, integer values are passed as is.
+    pub fn set_cpp_flags_hook(
+        &mut self,
+        hook: impl Fn(&CppFunction, Option<usize>) -> Result<Option<CppPath>> + 'static,
+    ) {
+        assert!(self.cpp_flags_hook.is_none(), "only one hook can be set");
+        self.cpp_flags_hook = Some(Box::new(hook));
+    }
+
+    pub fn cpp_flags_hook(&self) -> Option<&CppFlagsHook> {
+        self.cpp_flags_hook.as_ref().map(|b| &**b)
+    }
+
+    /// Marks an argument of a C++ function as an out-parameter.
+    /// The argument must be a non-const pointer or reference to a primitive type.
+    /// It's removed from the arguments of the Rust function. Instead,
+    /// the Rust function allocates the value, passes it to the C++ function
+    /// and returns it along with the original return value as a tuple
+    /// (e.g. `(ret, out1, out2)`). If an argument has `CppOutParamKind::OkFlag` kind,
+    /// the Rust function returns `Option` that is `None` if the flag was set to `false`.
+    ///
+    /// If an out-parameter has a default value, the variant of the function without
+    /// this argument is not generated.
+    pub fn add_cpp_out_param(&mut self, param: CppOutParam) {
+        self.cpp_out_params.push(param);
+    }
+
+    /// Returns out-parameter annotations added by `Config::add_cpp_out_param`
+    /// and `Config::load_cpp_annotations`.
+    pub fn cpp_out_params(&self) -> &[CppOutParam] {
+        &self.cpp_out_params
+    }
+
+    /// Specifies nullability of a pointer argument or return value of a C++ function.
+    /// Pointers to classes are represented as `Ptr<T>` by default. A non-null pointer
+    /// is represented as `Ref<T>`, and a nullable pointer is represented
+    /// as `Option<Ref<T>>` instead. Returned pointers to `QObject`-based classes
+    /// are still represented as `QPtr<T>`.
+    ///
+    /// Annotations added with this function take priority over
+    /// `_Nonnull` and `_Nullable` attributes in the C++ headers.
+    pub fn add_cpp_nullability(&mut self, annotation: CppNullabilityAnnotation) {
+        self.cpp_nullability.push(annotation);
+    }
+
+    /// Returns nullability annotations added by `Config::add_cpp_nullability`
+    /// and `Config::load_cpp_annotations`.
+    pub fn cpp_nullability(&self) -> &[CppNullabilityAnnotation] {
+        &self.cpp_nullability
+    }
+
+    /// Marks a pointer argument of a C++ function that takes ownership of the object
+    /// (e.g. `QWidget::setLayout` or a function accepting a `std::unique_ptr`).
+    /// Such an argument is represented as `CppBox<U>` (or `QBox<U>` for `QObject`-based
+    /// classes), where `U` is `T` or its derived class, that is consumed by the Rust function,
+    /// so the object is not deleted by Rust code after the call. The annotation is ignored
+    /// for other arguments and for classes that can't be deleted from Rust.
+    pub fn add_cpp_ownership_transfer(&mut self, transfer: CppOwnershipTransfer) {
+        self.cpp_ownership_transfers.push(transfer);
+    }
+
+    /// Returns annotations added by `Config::add_cpp_ownership_transfer`
+    /// and `Config::load_cpp_annotations`.
+    pub fn cpp_ownership_transfers(&self) -> &[CppOwnershipTransfer] {
+        &self.cpp_ownership_transfers
+    }
+
+    /// Marks a C++ class as a string type (e.g. `QString` or `std::string`).
+    /// Arguments of string types passed by value or by constant reference are
+    /// represented as `impl CastInto<RefOrBox<T>>`, so that they also accept
+    /// Rust strings. The generated wrapper converts a Rust string to a temporary
+    /// C++ object that is deleted after the call.
+    ///
+    /// The crate must implement `cpp_core::CppFromStr` (and, optionally,
+    /// `cpp_core::CppFromBytes`) for the Rust type of the class.
+    pub fn add_cpp_string_type(&mut self, path: CppPath) {
+        self.cpp_string_types.push(path);
+    }
+
+    /// Returns types added by `Config::add_cpp_string_type`.
+    pub fn cpp_string_types(&self) -> &[CppPath] {
+        &self.cpp_string_types
+    }
+
+    /// Loads annotations of C++ functions from a TOML or JSON file.
+    /// The file is parsed as TOML if it has `.toml` extension.
+    /// The file may contain `out_params`, `nullability` and `ownership_transfers` arrays
+    /// of values with the same structure as `CppOutParam`, `CppNullabilityAnnotation`
+    /// and `CppOwnershipTransfer`, respectively:
+    ///
+    /// ```toml
+    /// [[out_params]]
+    /// function = "QString::toInt"
+    /// argument = "ok"
+    /// kind = "ok_flag"
+    ///
+    /// [[nullability]]
+    /// function = "QObject::parent"
+    /// nullability = "nullable"
+    ///
+    /// [[ownership_transfers]]
+    /// function = "QWidget::setLayout"
+    /// argument = 0
+    /// ```
+    pub fn load_cpp_annotations(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let file: CppAnnotationsFile = if path.extension().map_or(false, |e| e == "toml") {
+            toml::from_str(&file_to_string(path)?)
+                .with_context(|_| format!("failed to parse TOML file: {}", path.display()))?
+        } else {
+            load_json(path)?
+        };
+        self.cpp_out_params.extend(file.out_params);
+        self.cpp_nullability.extend(file.nullability);
+        self.cpp_ownership_transfers
+            .extend(file.ownership_transfers);
+        Ok(())
+    }
+
     pub fn set_cluster_config(&mut self, cluster_config: ClusterConfig) {
         self.cluster_config = Some(cluster_config);
     }
@@ -23,10 +23,13 @@ fn create_cast_method(cast: CppCast, from: &CppType, to: &CppType) -> Result<Cpp
             name: "ptr".to_string(),
             argument_type: from.clone(),
             has_default_value: false,
+            nullability: None,
         }],
         allows_variadic_arguments: false,
         declaration_code: None,
+        deprecation: None,
         cast: Some(cast),
+        return_nullability: None,
     };
     Ok(CppItem::Function(function))
 }
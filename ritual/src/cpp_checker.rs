@@ -59,6 +59,10 @@ fn snippet_for_item(item: DbItem<&CppFfiItem>, database: &DatabaseClient) -> Res
             item.item.source_item_cpp_code(database)?,
             true,
         )),
+        CppFfiItem::SubclassWrapper(_) => Ok(Snippet::new_global(
+            item.item.source_item_cpp_code(database)?,
+            false,
+        )),
     }
 }
 
@@ -665,9 +669,10 @@ fn type_paths(type1: &CppType) -> Vec<&CppPath> {
         | CppType::BuiltInNumeric(_)
         | CppType::SpecificNumeric(_)
         | CppType::PointerSizedInteger { .. }
-        | CppType::TemplateParameter { .. } => Vec::new(),
+        | CppType::TemplateParameter { .. }
+        | CppType::IntegralConstant(_) => Vec::new(),
         CppType::Enum { path } | CppType::Class(path) => vec![path],
-        CppType::FunctionPointer(function) => function
+        CppType::FunctionPointer(function) | CppType::StdFunction(function) => function
             .arguments
             .iter()
             .chain(once(&*function.return_type))
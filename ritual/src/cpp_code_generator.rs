@@ -2,7 +2,7 @@ use crate::config::Config;
 use crate::cpp_checks::Condition;
 use crate::cpp_ffi_data::{
     CppFfiArgumentMeaning, CppFfiFunctionKind, CppFfiType, CppFieldAccessorType,
-    CppToFfiTypeConversion, QtSignalWrapper, QtSlotWrapper,
+    CppSubclassWrapper, CppToFfiTypeConversion, QtSignalWrapper, QtSlotWrapper,
 };
 use crate::cpp_ffi_data::{CppFfiFunction, CppFfiItem};
 use crate::cpp_function::{CppFunction, ReturnValueAllocationPlace};
@@ -20,6 +20,20 @@ use std::io::Write;
 use std::iter::once;
 use std::path::{Path, PathBuf};
 
+/// Generates code that moves the value out of the heap-allocated object
+/// pointed to by `expression` (see `CppToFfiTypeConversion::RValueReferenceToPointer`).
+/// The object is deleted at the end of the full-expression.
+fn moved_value_code(type1: &CppFfiType, expression: &str) -> Result<String> {
+    Ok(format!(
+        "std::move(*std::unique_ptr<{}>({}))",
+        type1
+            .ffi_type()
+            .pointer_like_to_target()?
+            .to_cpp_code(None)?,
+        expression
+    ))
+}
+
 struct Generator<'a>(&'a DatabaseClient);
 
 impl Generator<'_> {
@@ -96,6 +110,129 @@ impl Generator<'_> {
         ))
     }
 
+    /// Generates a declaration of a variable or a function named `name` of type `type1`.
+    fn declaration(&self, type1: &CppType, name: &str) -> Result<String> {
+        if let CppType::FunctionPointer(..) = type1 {
+            type1.to_cpp_code(Some(name))
+        } else {
+            Ok(format!("{} {}", type1.to_cpp_code(None)?, name))
+        }
+    }
+
+    /// Generates code for a subclass wrapper
+    fn subclass_wrapper(&self, wrapper: &CppSubclassWrapper) -> Result<String> {
+        let class_name = &wrapper.class_path.last().name;
+        let base_class = wrapper.base_class_path.to_cpp_code()?;
+        let arguments_code = |function: &CppFunction| -> Result<(String, String)> {
+            let declarations = function
+                .arguments
+                .iter()
+                .enumerate()
+                .map_if_ok(|(num, arg)| {
+                    self.declaration(&arg.argument_type, &format!("arg{}", num))
+                })?
+                .join(", ");
+            let names = (0..function.arguments.len())
+                .map(|num| format!("arg{}", num))
+                .join(", ");
+            Ok((declarations, names))
+        };
+
+        let mut constructors = String::new();
+        for constructor in &wrapper.constructors {
+            let (declarations, names) = arguments_code(constructor)?;
+            constructors += &format!(
+                "    {}({}) : {}({}) {{}}\n",
+                class_name, declarations, base_class, names
+            );
+        }
+
+        let mut methods = String::new();
+        let mut fields = String::new();
+        for method in &wrapper.methods {
+            let function = &method.function;
+            let (declarations, names) = arguments_code(function)?;
+            let member = function
+                .member()
+                .ok_or_else(|| err_msg("virtual method must be a class member"))?;
+            let const_text = if member.is_const { " const" } else { "" };
+            let base_call = format!(
+                "{}::{}({})",
+                function.class_path()?.to_cpp_code()?,
+                function.path.last().name,
+                names
+            );
+
+            let callback_args =
+                once(format!("m_{}_callback.data()", method.name))
+                    .chain(
+                        method.arguments.iter().enumerate().map_if_ok(|(num, t)| {
+                            self.convert_type_to_ffi(t, format!("arg{}", num))
+                        })?,
+                    )
+                    .join(", ");
+            let callback_call = self.convert_type_from_ffi(
+                &method.return_type,
+                format!("callback({})", callback_args),
+            )?;
+            let fallback = if member.is_pure_virtual {
+                "abort();".to_string()
+            } else {
+                format!("return {};", base_call)
+            };
+            let base_caller = if member.is_pure_virtual {
+                String::new()
+            } else {
+                format!(
+                    "\n    {}{} {{\n        return {};\n    }}\n",
+                    self.declaration(
+                        &function.return_type,
+                        &format!("{}({})", method.base_caller_name(), declarations)
+                    )?,
+                    const_text,
+                    base_call
+                )
+            };
+
+            let func_type = CppType::FunctionPointer(method.function_type.clone());
+            methods += &format!(
+                include_str!("../templates/c_lib/subclass_wrapper_method.h"),
+                signature = self.declaration(
+                    &function.return_type,
+                    &format!("{}({})", function.path.last().name, declarations)
+                )?,
+                const_text = const_text,
+                name = method.name,
+                callback_call = callback_call,
+                fallback = fallback,
+                callback_arg = func_type.to_cpp_code(Some("callback"))?,
+                base_caller = base_caller,
+            );
+            fields += &format!(
+                "    ritual::Callback<{}> m_{}_callback;\n",
+                func_type.to_cpp_code(Some(""))?,
+                method.name
+            );
+        }
+
+        let namespace = &wrapper.class_path.items()[..wrapper.class_path.items().len() - 1];
+        let namespace_begin = namespace
+            .iter()
+            .map(|item| format!("namespace {} {{ ", item.name))
+            .join("");
+        let namespace_end = namespace.iter().map(|_| " }").join("");
+        Ok(format!(
+            include_str!("../templates/c_lib/subclass_wrapper.h"),
+            namespace_begin = namespace_begin,
+            namespace_end = namespace_end,
+            class_name = class_name,
+            base_class = base_class,
+            constructors = constructors,
+            methods = methods,
+            fields = fields,
+        ))
+    }
+
     /// Generates code that wraps `expression` of type `type1.original_type` and
     /// converts it to type `type1.ffi_type`
     fn convert_type_to_ffi(&self, type1: &CppFfiType, expression: String) -> Result<String> {
@@ -109,7 +246,38 @@ impl Generator<'_> {
                 expression
             ),
             CppToFfiTypeConversion::ReferenceToPointer => format!("&{}", expression),
+            CppToFfiTypeConversion::RValueReferenceToPointer => {
+                bail!("RValueReferenceToPointer conversion to FFI type is not supported");
+            }
             CppToFfiTypeConversion::QFlagsToInt => format!("int({})", expression),
+            CppToFfiTypeConversion::StdFunctionToCallback { .. } => {
+                bail!("StdFunctionToCallback conversion to FFI type is not supported");
+            }
+        })
+    }
+
+    /// Generates code that wraps `expression` of type `type1.ffi_type` and
+    /// converts it to type `type1.original_type`
+    fn convert_type_from_ffi(&self, type1: &CppFfiType, expression: String) -> Result<String> {
+        Ok(match type1.conversion() {
+            CppToFfiTypeConversion::NoChange | CppToFfiTypeConversion::ImplicitCast { .. } => {
+                expression
+            }
+            CppToFfiTypeConversion::ValueToPointer { .. } => {
+                bail!("ValueToPointer conversion from FFI type is not supported");
+            }
+            CppToFfiTypeConversion::ReferenceToPointer => format!("*{}", expression),
+            CppToFfiTypeConversion::RValueReferenceToPointer => {
+                moved_value_code(type1, &expression)?
+            }
+            CppToFfiTypeConversion::QFlagsToInt => format!(
+                "{}({})",
+                type1.original_type().to_cpp_code(None)?,
+                expression
+            ),
+            CppToFfiTypeConversion::StdFunctionToCallback { .. } => {
+                bail!("StdFunctionToCallback conversion from FFI type is not supported");
+            }
         })
     }
 
@@ -158,9 +326,15 @@ impl Generator<'_> {
             CppToFfiTypeConversion::ReferenceToPointer => {
                 result = format!("&{}", result);
             }
+            CppToFfiTypeConversion::RValueReferenceToPointer => {
+                bail!("rvalue references are not supported in return types");
+            }
             CppToFfiTypeConversion::QFlagsToInt => {
                 result = format!("int({})", result);
             }
+            CppToFfiTypeConversion::StdFunctionToCallback { .. } => {
+                bail!("std::function is not supported in return types");
+            }
         }
 
         if method.allocation_place == ReturnValueAllocationPlace::Stack && !is_constructor {
@@ -187,6 +361,9 @@ impl Generator<'_> {
                 match argument.argument_type.conversion() {
                     CppToFfiTypeConversion::ValueToPointer { .. }
                     | CppToFfiTypeConversion::ReferenceToPointer => result = format!("*{}", result),
+                    CppToFfiTypeConversion::RValueReferenceToPointer => {
+                        result = moved_value_code(&argument.argument_type, &result)?;
+                    }
                     CppToFfiTypeConversion::NoChange
                     | CppToFfiTypeConversion::ImplicitCast { .. } => {}
                     CppToFfiTypeConversion::QFlagsToInt => {
@@ -206,12 +383,78 @@ impl Generator<'_> {
                         };
                         result = format!("{}({})", type_text, result);
                     }
+                    CppToFfiTypeConversion::StdFunctionToCallback {
+                        arguments,
+                        return_type,
+                    } => {
+                        let index = if let CppFfiArgumentMeaning::Argument(index) = argument.meaning
+                        {
+                            index
+                        } else {
+                            bail!("unexpected argument meaning");
+                        };
+                        let find_name = |meaning: CppFfiArgumentMeaning| {
+                            method
+                                .arguments
+                                .iter()
+                                .find(|arg| arg.meaning == meaning)
+                                .map(|arg| arg.name.clone())
+                                .ok_or_else(|| err_msg("callback argument not found"))
+                        };
+                        let deleter = find_name(CppFfiArgumentMeaning::CallbackDeleter(index))?;
+                        let data = find_name(CppFfiArgumentMeaning::CallbackData(index))?;
+                        result = format!(
+                            "ritual::callback_function({}, {}, {}, {})",
+                            result,
+                            deleter,
+                            data,
+                            self.callback_lambda(
+                                argument.argument_type.ffi_type(),
+                                arguments,
+                                return_type
+                            )?
+                        );
+                    }
                 }
                 Ok(result)
             })?;
         Ok(r.join(", "))
     }
 
+    /// Generates a lambda that calls a callback of type `callback_type`
+    /// with the specified arguments and return value conversions.
+    /// The lambda receives the callback and its data pointer followed by
+    /// the original arguments.
+    fn callback_lambda(
+        &self,
+        callback_type: &CppType,
+        arguments: &[CppFfiType],
+        return_type: &CppFfiType,
+    ) -> Result<String> {
+        let lambda_args = once(callback_type.to_cpp_code(Some("callback"))?)
+            .chain(once("void* data".to_string()))
+            .chain(arguments.iter().enumerate().map_if_ok(|(num, t)| {
+                self.declaration(t.original_type(), &format!("arg{}", num))
+            })?)
+            .join(", ");
+        let callback_args = once("data".to_string())
+            .chain(
+                arguments
+                    .iter()
+                    .enumerate()
+                    .map_if_ok(|(num, t)| self.convert_type_to_ffi(t, format!("arg{}", num)))?,
+            )
+            .join(", ");
+        let callback_call =
+            self.convert_type_from_ffi(return_type, format!("callback({})", callback_args))?;
+        Ok(format!(
+            "[]({}) -> {} {{ return {}; }}",
+            lambda_args,
+            return_type.original_type().to_cpp_code(None)?,
+            callback_call
+        ))
+    }
+
     /// Generates code for the value returned by the FFI method.
     #[allow(clippy::collapsible_if)]
     fn returned_expression(&self, item: DbItem<&CppFfiFunction>) -> Result<String> {
@@ -338,10 +581,20 @@ impl Generator<'_> {
 
     /// Generates implementation of the FFI method for the source file.
     fn function_implementation(&self, method: DbItem<&CppFfiFunction>) -> Result<String> {
+        let mut body = self.source_body(method.clone())?;
+        if let Some(arg) = method
+            .item
+            .arguments
+            .iter()
+            .find(|x| x.meaning == CppFfiArgumentMeaning::Exception)
+        {
+            let is_void = method.item.return_type.ffi_type().is_void();
+            body = catch_exceptions_code(&body, &arg.name, is_void);
+        }
         Ok(format!(
             "RITUAL_EXPORT {} {{\n  {}}}\n\n",
             self.function_signature(method.item)?,
-            self.source_body(method)?
+            body
         ))
     }
 
@@ -427,6 +680,15 @@ impl Generator<'_> {
                     let code = self.qt_signal_wrapper(qt_signal_wrapper)?;
                     write!(cpp_file, "{}", self.wrap_with_condition(&code, &condition))?;
                 }
+                CppFfiItem::SubclassWrapper(subclass_wrapper) => {
+                    let checks = self.0.cpp_checks(&ffi_item.id)?;
+                    if !checks.any_success() {
+                        continue;
+                    }
+                    let condition = checks.condition(self.0.environments());
+                    let code = self.subclass_wrapper(subclass_wrapper)?;
+                    write!(cpp_file, "{}", self.wrap_with_condition(&code, &condition))?;
+                }
                 _ => {}
             }
         }
@@ -494,6 +756,41 @@ impl Generator<'_> {
     }
 }
 
+/// Wraps `body` of an FFI function into a `try` block that writes the message
+/// of a caught exception to the location pointed to by `exception_arg`.
+fn catch_exceptions_code(body: &str, exception_arg: &str, is_void: bool) -> String {
+    format!(
+        "try {{\n    {body}  }} catch (const std::exception& e) {{\n    \
+         *{arg} = ritual::exception_message(e.what());\n  \
+         }} catch (...) {{\n    \
+         *{arg} = ritual::exception_message(\"unknown C++ exception\");\n  \
+         }}\n{maybe_return}",
+        body = body,
+        arg = exception_arg,
+        maybe_return = if is_void { "" } else { "  return {};\n" }
+    )
+}
+
+#[test]
+fn catch_exceptions_code_test() {
+    assert_eq!(
+        catch_exceptions_code("return f(arg1);\n", "ritual_exception", false),
+        "try {\n    return f(arg1);\n  } catch (const std::exception& e) {\n    \
+         *ritual_exception = ritual::exception_message(e.what());\n  \
+         } catch (...) {\n    \
+         *ritual_exception = ritual::exception_message(\"unknown C++ exception\");\n  \
+         }\n  return {};\n"
+    );
+    assert_eq!(
+        catch_exceptions_code("f(arg1);\n", "ritual_exception", true),
+        "try {\n    f(arg1);\n  } catch (const std::exception& e) {\n    \
+         *ritual_exception = ritual::exception_message(e.what());\n  \
+         } catch (...) {\n    \
+         *ritual_exception = ritual::exception_message(\"unknown C++ exception\");\n  \
+         }\n"
+    );
+}
+
 pub fn function_implementation(
     db: &DatabaseClient,
     method: DbItem<&CppFfiFunction>,
@@ -509,6 +806,10 @@ pub fn qt_signal_wrapper(db: &DatabaseClient, wrapper: &QtSignalWrapper) -> Resu
     Generator(db).qt_signal_wrapper(wrapper)
 }
 
+pub fn subclass_wrapper(db: &DatabaseClient, wrapper: &CppSubclassWrapper) -> Result<String> {
+    Generator(db).subclass_wrapper(wrapper)
+}
+
 pub fn generate_cpp_file(
     db: &DatabaseClient,
     file_path: &Path,
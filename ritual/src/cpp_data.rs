@@ -2,7 +2,7 @@
 
 use crate::cpp_function::CppFunction;
 pub use crate::cpp_operator::CppOperator;
-use crate::cpp_type::{CppTemplateParameter, CppType};
+use crate::cpp_type::{CppBuiltInNumericType, CppTemplateParameter, CppType};
 use crate::database::DatabaseClient;
 use itertools::Itertools;
 use ritual_common::errors::{bail, ensure, Error, Result};
@@ -83,6 +83,95 @@ impl CppClassField {
     }
 }
 
+/// A variable or a constant declared at namespace scope
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CppGlobalVariable {
+    pub path: CppPath,
+    /// Type of the variable
+    pub variable_type: CppType,
+    /// True if the variable is declared `const` and can't be assigned to
+    pub is_const: bool,
+}
+
+impl CppGlobalVariable {
+    pub fn is_same(&self, other: &CppGlobalVariable) -> bool {
+        self.path == other.path
+            && self.variable_type == other.variable_type
+            && self.is_const == other.is_const
+    }
+
+    pub fn short_text(&self) -> String {
+        format!(
+            "{}{} {}",
+            if self.is_const { "const " } else { "" },
+            self.variable_type.to_cpp_pseudo_code(),
+            self.path.to_cpp_pseudo_code()
+        )
+    }
+}
+
+/// Value of an object-like macro that expands to a literal
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub enum CppMacroValue {
+    /// Integer literal. `value_type` is deduced from the suffix and
+    /// the magnitude of the value, as the C++ compiler would do.
+    Integer {
+        value: u64,
+        is_negative: bool,
+        value_type: CppBuiltInNumericType,
+    },
+    /// Floating point literal. `value` is formatted as a valid Rust literal.
+    FloatingPoint {
+        value: String,
+        value_type: CppBuiltInNumericType,
+    },
+    /// String literal (or concatenation of multiple string literals)
+    String(String),
+}
+
+/// A preprocessor macro that can be represented as a constant
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CppMacroConstant {
+    /// Name of the macro
+    pub path: CppPath,
+    /// File name of the header that defines the macro (without full path)
+    pub include_file: String,
+    pub value: CppMacroValue,
+}
+
+impl CppMacroConstant {
+    pub fn is_same(&self, other: &CppMacroConstant) -> bool {
+        self.path == other.path && self.include_file == other.include_file
+    }
+
+    pub fn short_text(&self) -> String {
+        format!("#define {}", self.path.to_cpp_pseudo_code())
+    }
+}
+
+/// A `typedef` or a `using` alias declaration
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CppTypeAlias {
+    /// Name of the alias, including namespaces and nested classes
+    pub path: CppPath,
+    /// Type the alias refers to
+    pub target_type: CppType,
+}
+
+impl CppTypeAlias {
+    pub fn is_same(&self, other: &CppTypeAlias) -> bool {
+        self.path == other.path && self.target_type == other.target_type
+    }
+
+    pub fn short_text(&self) -> String {
+        format!(
+            "using {} = {}",
+            self.path.to_cpp_pseudo_code(),
+            self.target_type.to_cpp_pseudo_code()
+        )
+    }
+}
+
 /// Item of base class list in a class declaration
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct CppBaseSpecifier {
@@ -369,8 +458,21 @@ impl fmt::Debug for CppPathItem {
 /// Information about a C++ type declaration
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Hash)]
 pub enum CppTypeDeclarationKind {
-    Enum,
+    Enum {
+        /// Integer type used to represent values of the enum
+        underlying_type: CppType,
+        /// True if the enum is declared with `enum class` or `enum struct`
+        is_scoped: bool,
+    },
     Class,
+    Union,
+}
+
+/// Information about a C++ `[[deprecated]]` attribute (or an equivalent one)
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Hash)]
+pub struct CppDeprecation {
+    /// Message specified in the attribute, if any
+    pub message: Option<String>,
 }
 
 /// Information about a C++ type declaration
@@ -379,6 +481,8 @@ pub struct CppTypeDeclaration {
     /// Identifier, including namespaces and nested classes
     pub path: CppPath,
     pub kind: CppTypeDeclarationKind,
+    /// Deprecation attribute of the type, if any
+    pub deprecation: Option<CppDeprecation>,
 }
 
 impl CppTypeDeclaration {
@@ -389,16 +493,24 @@ impl CppTypeDeclaration {
 
 impl CppTypeDeclarationKind {
     /// Checks if the type is a class type.
+    /// Unions are also class types, as in the C++ standard.
     pub fn is_class(&self) -> bool {
         match self {
-            CppTypeDeclarationKind::Class { .. } => true,
+            CppTypeDeclarationKind::Class { .. } | CppTypeDeclarationKind::Union => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_union(&self) -> bool {
+        match self {
+            CppTypeDeclarationKind::Union => true,
             _ => false,
         }
     }
 
     pub fn is_enum(&self) -> bool {
         match self {
-            CppTypeDeclarationKind::Enum => true,
+            CppTypeDeclarationKind::Enum { .. } => true,
             _ => false,
         }
     }
@@ -418,6 +530,9 @@ pub enum CppItem {
     Function(CppFunction),
     ClassField(CppClassField),
     ClassBase(CppBaseSpecifier),
+    GlobalVariable(CppGlobalVariable),
+    MacroConstant(CppMacroConstant),
+    TypeAlias(CppTypeAlias),
 }
 
 impl CppItem {
@@ -467,6 +582,27 @@ impl CppItem {
                     false
                 }
             }
+            GlobalVariable(v) => {
+                if let GlobalVariable(v2) = &other {
+                    v.is_same(v2)
+                } else {
+                    false
+                }
+            }
+            MacroConstant(v) => {
+                if let MacroConstant(v2) = &other {
+                    v.is_same(v2)
+                } else {
+                    false
+                }
+            }
+            TypeAlias(v) => {
+                if let TypeAlias(v2) = &other {
+                    v.is_same(v2)
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -477,6 +613,9 @@ impl CppItem {
             CppItem::EnumValue(data) => &data.path,
             CppItem::Function(data) => &data.path,
             CppItem::ClassField(data) => &data.path,
+            CppItem::GlobalVariable(data) => &data.path,
+            CppItem::MacroConstant(data) => &data.path,
+            CppItem::TypeAlias(data) => &data.path,
             CppItem::ClassBase(_) => return None,
         };
         Some(path)
@@ -485,10 +624,12 @@ impl CppItem {
     pub fn all_involved_types(&self) -> Vec<CppType> {
         match self {
             CppItem::Type(t) => match t.kind {
-                CppTypeDeclarationKind::Enum => vec![CppType::Enum {
+                CppTypeDeclarationKind::Enum { .. } => vec![CppType::Enum {
                     path: t.path.clone(),
                 }],
-                CppTypeDeclarationKind::Class { .. } => vec![CppType::Class(t.path.clone())],
+                CppTypeDeclarationKind::Class { .. } | CppTypeDeclarationKind::Union => {
+                    vec![CppType::Class(t.path.clone())]
+                }
             },
             CppItem::EnumValue(enum_value) => vec![CppType::Enum {
                 path: enum_value
@@ -496,7 +637,7 @@ impl CppItem {
                     .parent()
                     .expect("enum value must have parent path"),
             }],
-            CppItem::Namespace(_) => Vec::new(),
+            CppItem::Namespace(_) | CppItem::MacroConstant(_) => Vec::new(),
             CppItem::Function(function) => function.all_involved_types(),
             CppItem::ClassField(field) => {
                 let class_type =
@@ -507,6 +648,8 @@ impl CppItem {
                 CppType::Class(base.base_class_type.clone()),
                 CppType::Class(base.derived_class_type.clone()),
             ],
+            CppItem::GlobalVariable(variable) => vec![variable.variable_type.clone()],
+            CppItem::TypeAlias(alias) => vec![alias.target_type.clone()],
         }
     }
 
@@ -531,6 +674,27 @@ impl CppItem {
             None
         }
     }
+    pub fn as_global_variable_ref(&self) -> Option<&CppGlobalVariable> {
+        if let CppItem::GlobalVariable(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+    pub fn as_macro_constant_ref(&self) -> Option<&CppMacroConstant> {
+        if let CppItem::MacroConstant(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+    pub fn as_type_alias_ref(&self) -> Option<&CppTypeAlias> {
+        if let CppItem::TypeAlias(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
     pub fn as_enum_value_ref(&self) -> Option<&CppEnumValue> {
         if let CppItem::EnumValue(data) = self {
             Some(data)
@@ -567,6 +731,9 @@ impl CppItem {
             CppItem::EnumValue(value) => format!("enum value {}", value.path.to_cpp_pseudo_code()),
             CppItem::Function(value) => value.short_text(),
             CppItem::ClassField(value) => value.short_text(),
+            CppItem::GlobalVariable(value) => value.short_text(),
+            CppItem::MacroConstant(value) => value.short_text(),
+            CppItem::TypeAlias(value) => value.short_text(),
             CppItem::ClassBase(_) => format!("{:?}", self),
         }
     }
@@ -579,10 +746,15 @@ impl fmt::Display for CppItem {
                 format!("namespace {}", namespace.path.to_cpp_pseudo_code())
             }
             CppItem::Type(type1) => match type1.kind {
-                CppTypeDeclarationKind::Enum => format!("enum {}", type1.path.to_cpp_pseudo_code()),
+                CppTypeDeclarationKind::Enum { .. } => {
+                    format!("enum {}", type1.path.to_cpp_pseudo_code())
+                }
                 CppTypeDeclarationKind::Class { .. } => {
                     format!("class {}", type1.path.to_cpp_pseudo_code())
                 }
+                CppTypeDeclarationKind::Union => {
+                    format!("union {}", type1.path.to_cpp_pseudo_code())
+                }
             },
             CppItem::Function(method) => method.short_text(),
             CppItem::EnumValue(value) => format!(
@@ -591,6 +763,9 @@ impl fmt::Display for CppItem {
                 value.value
             ),
             CppItem::ClassField(field) => field.short_text(),
+            CppItem::GlobalVariable(variable) => variable.short_text(),
+            CppItem::MacroConstant(constant) => constant.short_text(),
+            CppItem::TypeAlias(alias) => alias.short_text(),
             CppItem::ClassBase(class_base) => {
                 let virtual_text = if class_base.is_virtual {
                     "virtual "
@@ -1,11 +1,12 @@
 use crate::cpp_code_generator;
 use crate::cpp_data::CppPath;
-use crate::cpp_function::ReturnValueAllocationPlace;
+use crate::cpp_function::{CppFunction, ReturnValueAllocationPlace};
 use crate::cpp_type::{CppBuiltInNumericType, CppFunctionPointerType, CppType};
 use crate::database::DatabaseClient;
 use itertools::Itertools;
 use ritual_common::errors::{bail, Result};
 use serde_derive::{Deserialize, Serialize};
+use std::iter::once;
 
 /// Variation of a field accessor method
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
@@ -83,11 +84,27 @@ pub enum CppToFfiTypeConversion {
     /// C++ argument is a reference (like QPoint&)
     /// and FFI argument is a pointer (like QPoint*)
     ReferenceToPointer,
+    /// C++ argument is an rvalue reference to a class (like QPoint&&)
+    /// or a value of a move-only class (like `std::unique_ptr<QPoint>`)
+    /// and FFI argument is a pointer to a heap-allocated object (like QPoint*).
+    /// The object is moved into the argument and deleted after the call.
+    RValueReferenceToPointer,
     /// C++ argument is QFlags<T>
     /// and FFI argument is uint
     QFlagsToInt,
     /// Implicit conversion is used.
     ImplicitCast { ffi_type: CppType },
+    /// C++ argument is `std::function` (or a constant reference to it)
+    /// and FFI argument is a callback function pointer. The callback receives
+    /// the data pointer as the first argument. The deleter and the data pointer
+    /// are passed in separate FFI arguments (see `CppFfiArgumentMeaning::CallbackDeleter`
+    /// and `CppFfiArgumentMeaning::CallbackData`).
+    StdFunctionToCallback {
+        /// Conversions of the function's arguments passed to the callback
+        arguments: Vec<CppFfiType>,
+        /// Conversion of the value returned by the callback
+        return_type: Box<CppFfiType>,
+    },
 }
 
 /// Information that indicates how an FFI function argument
@@ -104,6 +121,15 @@ pub enum CppFfiArgumentMeaning {
     /// This argument receives pointer to the buffer where
     /// the return value should be transferred to using placement new.
     ReturnValue,
+    /// This argument receives pointer to the message of a C++ exception
+    /// thrown by the original C++ method (or null if no exception was thrown).
+    Exception,
+    /// This argument receives the deleter of the callback data for
+    /// the C++ method's `std::function` argument with the specified index.
+    CallbackDeleter(usize),
+    /// This argument receives the callback data for the C++ method's
+    /// `std::function` argument with the specified index.
+    CallbackData(usize),
 }
 
 impl CppFfiArgumentMeaning {
@@ -115,6 +141,16 @@ impl CppFfiArgumentMeaning {
             _ => false,
         }
     }
+
+    /// Checks if this argument is passed to the FFI function
+    /// along with a callback for a `std::function` argument
+    pub fn is_callback_deleter_or_data(&self) -> bool {
+        match *self {
+            CppFfiArgumentMeaning::CallbackDeleter(..)
+            | CppFfiArgumentMeaning::CallbackData(..) => true,
+            _ => false,
+        }
+    }
 }
 
 /// Representation of an argument of a FFI function
@@ -236,6 +272,18 @@ impl CppFfiType {
                     conversion,
                 })
             }
+            CppToFfiTypeConversion::RValueReferenceToPointer => {
+                let target = if original_type.is_class() {
+                    &original_type
+                } else {
+                    original_type.pointer_like_to_target()?
+                };
+                Ok(CppFfiType {
+                    ffi_type: CppType::new_pointer(false, target.clone()),
+                    original_type,
+                    conversion,
+                })
+            }
             CppToFfiTypeConversion::QFlagsToInt => Ok(CppFfiType {
                 ffi_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 original_type,
@@ -246,6 +294,24 @@ impl CppFfiType {
                 original_type,
                 conversion,
             }),
+            CppToFfiTypeConversion::StdFunctionToCallback {
+                arguments,
+                return_type,
+            } => {
+                let void_ptr = CppType::new_pointer(false, CppType::Void);
+                let function_type = CppFunctionPointerType {
+                    return_type: Box::new(return_type.ffi_type().clone()),
+                    arguments: once(void_ptr)
+                        .chain(arguments.iter().map(|t| t.ffi_type().clone()))
+                        .collect(),
+                    allows_variadic_arguments: false,
+                };
+                Ok(CppFfiType {
+                    ffi_type: CppType::FunctionPointer(function_type),
+                    original_type,
+                    conversion,
+                })
+            }
         }
     }
 
@@ -293,12 +359,53 @@ pub struct QtSlotWrapper {
     pub function_type: CppFunctionPointerType,
 }
 
+/// Information about a virtual method overridden by a subclass wrapper
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CppSubclassMethod {
+    /// The virtual method of the base class (or one of its bases)
+    pub function: CppFunction,
+    /// Unique name used in the names of the generated methods
+    /// (`set_{name}_callback` and `base_{name}`)
+    pub name: String,
+    /// Arguments passed to the callback
+    pub arguments: Vec<CppFfiType>,
+    /// Return type of the callback
+    pub return_type: CppFfiType,
+    /// The function pointer type accepted by the callback setter
+    pub function_type: CppFunctionPointerType,
+}
+
+impl CppSubclassMethod {
+    pub fn setter_name(&self) -> String {
+        format!("set_{}_callback", self.name)
+    }
+
+    pub fn base_caller_name(&self) -> String {
+        format!("base_{}", self.name)
+    }
+}
+
+/// Information about a generated C++ subclass that forwards
+/// calls of virtual methods to callbacks set from Rust
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CppSubclassWrapper {
+    /// The class being subclassed
+    pub base_class_path: CppPath,
+    /// Generated name of the subclass
+    pub class_path: CppPath,
+    /// Constructors of the base class mirrored by the subclass
+    pub constructors: Vec<CppFunction>,
+    /// Overridden virtual methods
+    pub methods: Vec<CppSubclassMethod>,
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CppFfiItem {
     Function(CppFfiFunction),
     QtSlotWrapper(QtSlotWrapper),
     QtSignalWrapper(QtSignalWrapper),
+    SubclassWrapper(CppSubclassWrapper),
 }
 
 impl CppFfiItem {
@@ -334,6 +441,14 @@ impl CppFfiItem {
         }
     }
 
+    pub fn as_subclass_wrapper_ref(&self) -> Option<&CppSubclassWrapper> {
+        if let CppFfiItem::SubclassWrapper(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+
     pub fn is_slot_wrapper(&self) -> bool {
         if let CppFfiItem::QtSlotWrapper(_) = self {
             true
@@ -369,6 +484,10 @@ impl CppFfiItem {
                     .map(CppType::to_cpp_pseudo_code)
                     .join(", ")
             ),
+            CppFfiItem::SubclassWrapper(subclass_wrapper) => format!(
+                "subclass wrapper for {}",
+                subclass_wrapper.base_class_path.to_cpp_pseudo_code()
+            ),
         }
     }
 
@@ -395,6 +514,13 @@ impl CppFfiItem {
                     false
                 }
             }
+            CppFfiItem::SubclassWrapper(wrapper) => {
+                if let CppFfiItem::SubclassWrapper(other_wrapper) = other {
+                    wrapper.base_class_path == other_wrapper.base_class_path
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -403,13 +529,16 @@ impl CppFfiItem {
             CppFfiItem::Function(f) => &f.path,
             CppFfiItem::QtSlotWrapper(s) => &s.class_path,
             CppFfiItem::QtSignalWrapper(s) => &s.class_path,
+            CppFfiItem::SubclassWrapper(s) => &s.class_path,
         }
     }
 
     pub fn is_source_item(&self) -> bool {
         match self {
             CppFfiItem::Function(_) => false,
-            CppFfiItem::QtSlotWrapper(_) | CppFfiItem::QtSignalWrapper(_) => true,
+            CppFfiItem::QtSlotWrapper(_)
+            | CppFfiItem::QtSignalWrapper(_)
+            | CppFfiItem::SubclassWrapper(_) => true,
         }
     }
 
@@ -422,6 +551,9 @@ impl CppFfiItem {
             CppFfiItem::QtSignalWrapper(signal_wrapper) => {
                 cpp_code_generator::qt_signal_wrapper(db, signal_wrapper)
             }
+            CppFfiItem::SubclassWrapper(subclass_wrapper) => {
+                cpp_code_generator::subclass_wrapper(db, subclass_wrapper)
+            }
         }
     }
 }
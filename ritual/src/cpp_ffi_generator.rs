@@ -1,4 +1,6 @@
+use crate::config::Config;
 use crate::cpp_data::CppClassField;
+use crate::cpp_data::CppGlobalVariable;
 use crate::cpp_data::CppItem;
 use crate::cpp_data::CppPath;
 use crate::cpp_data::CppPathItem;
@@ -9,6 +11,8 @@ use crate::cpp_ffi_data::{CppFfiFunction, CppFfiFunctionKind, CppFieldAccessorTy
 use crate::cpp_ffi_data::{CppFfiFunctionArgument, CppFfiItem};
 use crate::cpp_function::ReturnValueAllocationPlace;
 use crate::cpp_function::{CppFunction, CppFunctionArgument, CppFunctionKind};
+use crate::cpp_smart_pointers::{smart_pointer_target, SmartPointerKind};
+use crate::cpp_type::CppBuiltInNumericType;
 use crate::cpp_type::CppPointerLikeTypeKind;
 use crate::cpp_type::CppType;
 use crate::cpp_type::CppTypeRole;
@@ -18,7 +22,39 @@ use crate::processor::ProcessorData;
 use itertools::Itertools;
 use log::{debug, trace};
 use ritual_common::errors::{bail, Result, ResultExt};
+use ritual_common::utils::MapIfOk;
 use std::collections::HashSet;
+use std::iter::once;
+
+/// Returns conversion of a `std::function` argument with the specified
+/// function type to a callback.
+fn std_function_conversion(function: &CppFunctionPointerType) -> Result<CppToFfiTypeConversion> {
+    if function.allows_variadic_arguments {
+        bail!("function types with variadic arguments are not supported");
+    }
+    let arguments = function
+        .arguments
+        .iter()
+        .map_if_ok(|arg| ffi_type(arg, CppTypeRole::NotReturnType))?;
+    let return_type = ffi_type(&function.return_type, CppTypeRole::ReturnType)?;
+    for t in arguments.iter().chain(once(&return_type)) {
+        if t.ffi_type().is_function_pointer() {
+            bail!("nested callbacks are not supported");
+        }
+        match t.conversion() {
+            CppToFfiTypeConversion::ValueToPointer { .. }
+            | CppToFfiTypeConversion::RValueReferenceToPointer => bail!(
+                "passing class values through callbacks is not supported: {}",
+                t.original_type().to_cpp_pseudo_code()
+            ),
+            _ => {}
+        }
+    }
+    Ok(CppToFfiTypeConversion::StdFunctionToCallback {
+        arguments,
+        return_type: Box::new(return_type),
+    })
+}
 
 /// Converts this C++ type to its adaptation for FFI interface,
 /// removing all features not supported by C ABI
@@ -42,7 +78,7 @@ pub fn ffi_type(original_type: &CppType, role: CppTypeRole) -> Result<CppFfiType
                 all_types.push(return_type.as_ref());
                 for arg in all_types {
                     match *arg {
-                        CppType::FunctionPointer(..) => {
+                        CppType::FunctionPointer(..) | CppType::StdFunction(..) => {
                             bail!(
                                 "function pointers containing nested function pointers are \
                                  not supported"
@@ -62,9 +98,25 @@ pub fn ffi_type(original_type: &CppType, role: CppTypeRole) -> Result<CppFfiType
                 }
                 CppToFfiTypeConversion::NoChange
             }
+            CppType::IntegralConstant(_) => {
+                bail!("integral constants are not types");
+            }
+            CppType::StdFunction(function) => {
+                if role == CppTypeRole::ReturnType {
+                    bail!("returning std::function is not supported");
+                }
+                std_function_conversion(function)?
+            }
             CppType::Class(path) => {
                 if is_qflags(&path) {
                     CppToFfiTypeConversion::QFlagsToInt
+                } else if role == CppTypeRole::NotReturnType
+                    && smart_pointer_target(path).map(|(kind, _)| kind)
+                        == Some(SmartPointerKind::Unique)
+                {
+                    // `std::unique_ptr` can't be copied, so the argument is moved
+                    // out of the object owned by `cpp_core::UniquePtr`
+                    CppToFfiTypeConversion::RValueReferenceToPointer
                 } else {
                     CppToFfiTypeConversion::ValueToPointer {
                         is_ffi_const: role != CppTypeRole::ReturnType,
@@ -84,12 +136,30 @@ pub fn ffi_type(original_type: &CppType, role: CppTypeRole) -> Result<CppFfiType
                                 // TODO: use a separate conversion type (QFlagsConstRefToUInt)?
                                 CppToFfiTypeConversion::QFlagsToInt
                             }
+                            CppType::StdFunction(function) => {
+                                if !*is_const || role == CppTypeRole::ReturnType {
+                                    bail!(
+                                        "std::function is only supported in arguments \
+                                         passed by value or const reference"
+                                    );
+                                }
+                                std_function_conversion(function)?
+                            }
                             _ => CppToFfiTypeConversion::ReferenceToPointer,
                         }
                     }
-                    CppPointerLikeTypeKind::RValueReference => {
-                        bail!("rvalue references are not supported");
-                    }
+                    CppPointerLikeTypeKind::RValueReference => match &**target {
+                        CppType::Class(path)
+                            if role == CppTypeRole::NotReturnType
+                                && !*is_const
+                                && !is_qflags(path) =>
+                        {
+                            CppToFfiTypeConversion::RValueReferenceToPointer
+                        }
+                        _ => bail!(
+                            "rvalue references are only supported for non-const class arguments"
+                        ),
+                    },
                 }
             }
             _ => CppToFfiTypeConversion::NoChange,
@@ -162,18 +232,30 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
             continue;
         }
         let result = match &item.item {
-            CppItem::Function(method) => {
-                generate_ffi_methods_for_method(method, &movable_types, &mut name_provider)
-                    .map(|v| v.into_iter().collect_vec())
-            }
+            CppItem::Function(method) => should_catch_exceptions(data.config, method)
+                .and_then(|catch_exceptions| {
+                    generate_ffi_methods_for_method(
+                        method,
+                        catch_exceptions,
+                        &movable_types,
+                        &mut name_provider,
+                    )
+                })
+                .map(|v| v.into_iter().collect_vec()),
             CppItem::ClassField(field) => {
                 generate_field_accessors(field, &movable_types, &mut name_provider)
                     .map(|v| v.into_iter().collect_vec())
             }
+            CppItem::GlobalVariable(variable) => {
+                generate_variable_accessors(variable, &movable_types, &mut name_provider)
+                    .map(|v| v.into_iter().collect_vec())
+            }
             CppItem::ClassBase(_)
             | CppItem::Type(_)
             | CppItem::EnumValue(_)
-            | CppItem::Namespace(_) => {
+            | CppItem::Namespace(_)
+            | CppItem::MacroConstant(_)
+            | CppItem::TypeAlias(_) => {
                 // no FFI methods for these items
                 continue;
             }
@@ -197,8 +279,21 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
     Ok(())
 }
 
+/// Checks whether C++ exceptions thrown by `function` should be caught by the FFI wrapper.
+fn should_catch_exceptions(config: &Config, function: &CppFunction) -> Result<bool> {
+    // destructors are implicitly `noexcept`, and casts never throw
+    if function.is_destructor() || function.cast.is_some() {
+        return Ok(false);
+    }
+    match config.cpp_exceptions_hook() {
+        Some(hook) => hook(function),
+        None => Ok(false),
+    }
+}
+
 fn generate_ffi_methods_for_method(
     method: &CppFunction,
+    catch_exceptions: bool,
     movable_types: &[CppPath],
     name_provider: &mut FfiNameProvider,
 ) -> Result<Vec<CppFfiItem>> {
@@ -206,6 +301,7 @@ fn generate_ffi_methods_for_method(
     methods.push(CppFfiItem::Function(to_ffi_method(
         NewFfiFunctionKind::Function {
             cpp_function: method.clone(),
+            catch_exceptions,
         },
         movable_types,
         name_provider,
@@ -217,18 +313,41 @@ fn generate_ffi_methods_for_method(
 pub enum NewFfiFunctionKind {
     Function {
         cpp_function: CppFunction,
+        /// Whether C++ exceptions should be caught and reported
+        /// through an additional argument
+        catch_exceptions: bool,
     },
     FieldAccessor {
         accessor_type: CppFieldAccessorType,
         field: CppClassField,
     },
+    VariableAccessor {
+        accessor_type: CppFieldAccessorType,
+        variable: CppGlobalVariable,
+    },
+}
+
+impl NewFfiFunctionKind {
+    /// Returns path and type of the accessed field or variable.
+    fn accessed_item(&self) -> Option<(&CppPath, &CppType)> {
+        match self {
+            NewFfiFunctionKind::Function { .. } => None,
+            NewFfiFunctionKind::FieldAccessor { field, .. } => {
+                Some((&field.path, &field.field_type))
+            }
+            NewFfiFunctionKind::VariableAccessor { variable, .. } => {
+                Some((&variable.path, &variable.variable_type))
+            }
+        }
+    }
 }
 
 /// Creates FFI function signature for this function:
 /// - converts all types to FFI types;
 /// - adds "this" argument explicitly if present;
 /// - adds "output" argument for return value if
-///   the return value is stack-allocated.
+///   the return value is stack-allocated;
+/// - adds "ritual_exception" argument if C++ exceptions should be caught.
 pub fn to_ffi_method(
     kind: NewFfiFunctionKind,
     movable_types: &[CppPath],
@@ -236,11 +355,12 @@ pub fn to_ffi_method(
 ) -> Result<CppFfiFunction> {
     let ascii_caption = match &kind {
         NewFfiFunctionKind::Function { cpp_function, .. } => cpp_function.path.ascii_caption(),
-        NewFfiFunctionKind::FieldAccessor {
-            field,
-            accessor_type,
-        } => {
-            let field_caption = field.path.ascii_caption();
+        NewFfiFunctionKind::FieldAccessor { accessor_type, .. }
+        | NewFfiFunctionKind::VariableAccessor { accessor_type, .. } => {
+            let (path, _) = kind
+                .accessed_item()
+                .expect("accessor must have accessed item");
+            let field_caption = path.ascii_caption();
             match *accessor_type {
                 CppFieldAccessorType::CopyGetter | CppFieldAccessorType::ConstRefGetter => {
                     field_caption
@@ -258,7 +378,8 @@ pub fn to_ffi_method(
         allocation_place: ReturnValueAllocationPlace::NotApplicable,
         kind: match kind {
             NewFfiFunctionKind::Function { .. } => CppFfiFunctionKind::Function,
-            NewFfiFunctionKind::FieldAccessor { accessor_type, .. } => {
+            NewFfiFunctionKind::FieldAccessor { accessor_type, .. }
+            | NewFfiFunctionKind::VariableAccessor { accessor_type, .. } => {
                 CppFfiFunctionKind::FieldAccessor { accessor_type }
             }
         },
@@ -287,6 +408,7 @@ pub fn to_ffi_method(
                 Some(CppType::new_pointer(is_const, class_type))
             }
         }
+        NewFfiFunctionKind::VariableAccessor { .. } => None,
     };
 
     if let Some(this_arg_type) = this_arg_type {
@@ -315,15 +437,17 @@ pub fn to_ffi_method(
             }
             cpp_function.arguments.clone()
         }
-        NewFfiFunctionKind::FieldAccessor {
-            field,
-            accessor_type,
-        } => {
+        NewFfiFunctionKind::FieldAccessor { accessor_type, .. }
+        | NewFfiFunctionKind::VariableAccessor { accessor_type, .. } => {
             if accessor_type == &CppFieldAccessorType::Setter {
+                let (_, value_type) = kind
+                    .accessed_item()
+                    .expect("accessor must have accessed item");
                 let arg = CppFunctionArgument {
                     name: "value".to_string(),
-                    argument_type: field.field_type.clone(),
+                    argument_type: value_type.clone(),
                     has_default_value: false,
+                    nullability: None,
                 };
                 vec![arg]
             } else {
@@ -334,11 +458,33 @@ pub fn to_ffi_method(
 
     for (index, arg) in normal_args.iter().enumerate() {
         let c_type = ffi_type(&arg.argument_type, CppTypeRole::NotReturnType)?;
+        let is_callback = match c_type.conversion() {
+            CppToFfiTypeConversion::StdFunctionToCallback { .. } => true,
+            _ => false,
+        };
         r.arguments.push(CppFfiFunctionArgument {
             name: arg.name.clone(),
             argument_type: c_type,
             meaning: CppFfiArgumentMeaning::Argument(index),
         });
+        if is_callback {
+            let void_ptr = CppType::new_pointer(false, CppType::Void);
+            let deleter_type = CppType::FunctionPointer(CppFunctionPointerType {
+                return_type: Box::new(CppType::Void),
+                arguments: vec![void_ptr.clone()],
+                allows_variadic_arguments: false,
+            });
+            r.arguments.push(CppFfiFunctionArgument {
+                name: format!("{}_deleter", arg.name),
+                argument_type: CppFfiType::new(deleter_type, CppToFfiTypeConversion::NoChange)?,
+                meaning: CppFfiArgumentMeaning::CallbackDeleter(index),
+            });
+            r.arguments.push(CppFfiFunctionArgument {
+                name: format!("{}_data", arg.name),
+                argument_type: CppFfiType::new(void_ptr, CppToFfiTypeConversion::NoChange)?,
+                meaning: CppFfiArgumentMeaning::CallbackData(index),
+            });
+        }
     }
 
     let real_return_type = match &kind {
@@ -348,19 +494,22 @@ pub fn to_ffi_method(
             }
             _ => cpp_function.return_type.clone(),
         },
-        NewFfiFunctionKind::FieldAccessor {
-            field,
-            accessor_type,
-        } => match *accessor_type {
-            CppFieldAccessorType::CopyGetter => field.field_type.clone(),
-            CppFieldAccessorType::ConstRefGetter => {
-                CppType::new_reference(true, field.field_type.clone())
-            }
-            CppFieldAccessorType::MutRefGetter => {
-                CppType::new_reference(false, field.field_type.clone())
+        NewFfiFunctionKind::FieldAccessor { accessor_type, .. }
+        | NewFfiFunctionKind::VariableAccessor { accessor_type, .. } => {
+            let (_, value_type) = kind
+                .accessed_item()
+                .expect("accessor must have accessed item");
+            match *accessor_type {
+                CppFieldAccessorType::CopyGetter => value_type.clone(),
+                CppFieldAccessorType::ConstRefGetter => {
+                    CppType::new_reference(true, value_type.clone())
+                }
+                CppFieldAccessorType::MutRefGetter => {
+                    CppType::new_reference(false, value_type.clone())
+                }
+                CppFieldAccessorType::Setter => CppType::Void,
             }
-            CppFieldAccessorType::Setter => CppType::Void,
-        },
+        }
     };
     let real_return_type_ffi = ffi_type(&real_return_type, CppTypeRole::ReturnType)?;
     match &real_return_type {
@@ -383,6 +532,22 @@ pub fn to_ffi_method(
         }
     }
 
+    if let NewFfiFunctionKind::Function {
+        catch_exceptions: true,
+        ..
+    } = &kind
+    {
+        let message_type = CppType::new_pointer(
+            false,
+            CppType::new_pointer(false, CppType::BuiltInNumeric(CppBuiltInNumericType::Char)),
+        );
+        r.arguments.push(CppFfiFunctionArgument {
+            name: "ritual_exception".to_string(),
+            argument_type: ffi_type(&message_type, CppTypeRole::NotReturnType)?,
+            meaning: CppFfiArgumentMeaning::Exception,
+        });
+    }
+
     Ok(r)
 }
 
@@ -417,6 +582,38 @@ fn generate_field_accessors(
     Ok(new_methods)
 }
 
+/// Adds fictional getter and setter methods for a namespace-scope variable.
+/// Only getters are generated for constants.
+fn generate_variable_accessors(
+    variable: &CppGlobalVariable,
+    movable_types: &[CppPath],
+    name_provider: &mut FfiNameProvider,
+) -> Result<Vec<CppFfiItem>> {
+    let mut new_methods = Vec::new();
+    let mut create_method = |accessor_type| -> Result<CppFfiItem> {
+        let kind = NewFfiFunctionKind::VariableAccessor {
+            variable: variable.clone(),
+            accessor_type,
+        };
+        let ffi_function = to_ffi_method(kind, movable_types, name_provider)?;
+        Ok(CppFfiItem::Function(ffi_function))
+    };
+
+    if variable.variable_type.is_class() {
+        new_methods.push(create_method(CppFieldAccessorType::ConstRefGetter)?);
+        if !variable.is_const {
+            new_methods.push(create_method(CppFieldAccessorType::MutRefGetter)?);
+        }
+    } else {
+        new_methods.push(create_method(CppFieldAccessorType::CopyGetter)?);
+    }
+    if !variable.is_const {
+        new_methods.push(create_method(CppFieldAccessorType::Setter)?);
+    }
+
+    Ok(new_methods)
+}
+
 fn check_preconditions(item: &CppItem, source_ffi_item: Option<DbItem<&CppFfiItem>>) -> Result<()> {
     match item {
         CppItem::Function(function) => {
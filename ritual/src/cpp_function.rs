@@ -1,6 +1,6 @@
 //! Types for handling information about C++ methods.
 
-use crate::cpp_data::{CppPath, CppPathItem, CppVisibility};
+use crate::cpp_data::{CppDeprecation, CppPath, CppPathItem, CppVisibility};
 use crate::cpp_ffi_data::CppCast;
 pub use crate::cpp_operator::{CppOperator, CppOperatorInfo};
 use crate::cpp_type::{CppPointerLikeTypeKind, CppType};
@@ -11,6 +11,16 @@ use ritual_common::utils::MapIfOk;
 use serde_derive::{Deserialize, Serialize};
 use std::fmt::Write;
 
+/// Nullability of a pointer argument or return value
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CppNullability {
+    /// The pointer is never null (`_Nonnull`)
+    NonNull,
+    /// The pointer can be null (`_Nullable`)
+    Nullable,
+}
+
 /// Information about an argument of a C++ method
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct CppFunctionArgument {
@@ -23,6 +33,9 @@ pub struct CppFunctionArgument {
     /// Flag indicating that the argument has default value and
     /// therefore can be omitted when calling the method
     pub has_default_value: bool,
+    /// Nullability of the argument if it's a pointer with
+    /// a `_Nonnull` or `_Nullable` attribute
+    pub nullability: Option<CppNullability>,
 }
 
 impl CppFunctionArgument {
@@ -113,6 +126,9 @@ pub struct CppFunction {
     /// Return type of the method.
     /// Return type is reported as void for constructors and destructors.
     pub return_type: CppType,
+    /// Nullability of the return type if it's a pointer with
+    /// a `_Nonnull` or `_Nullable` attribute
+    pub return_nullability: Option<CppNullability>,
     /// List of the method's arguments
     pub arguments: Vec<CppFunctionArgument>,
     /// Whether the argument list is terminated with "..."
@@ -121,6 +137,8 @@ pub struct CppFunction {
     /// C++ code of the method's declaration.
     /// None if the method was not explicitly declared.
     pub declaration_code: Option<String>,
+    /// Deprecation attribute of the function, if any
+    pub deprecation: Option<CppDeprecation>,
 }
 
 /// Chosen type allocation place for the method
@@ -180,6 +198,43 @@ impl CppFunction {
         true
     }
 
+    /// Returns true if this function is an overload of `other` that takes
+    /// rvalue references (`T&&`) in place of some of const references (`const T&`)
+    /// taken by `other`, with all other arguments being the same.
+    pub fn is_rvalue_reference_overload_of(&self, other: &CppFunction) -> bool {
+        let member_data = |f: &CppFunction| f.member().map(|m| (m.is_const, m.is_static));
+        if self.path != other.path
+            || self.operator != other.operator
+            || member_data(self) != member_data(other)
+            || self.arguments.len() != other.arguments.len()
+            || self.allows_variadic_arguments != other.allows_variadic_arguments
+        {
+            return false;
+        }
+        let mut has_rvalue_reference = false;
+        for (arg, other_arg) in self.arguments.iter().zip(&other.arguments) {
+            if arg.argument_type == other_arg.argument_type {
+                continue;
+            }
+            match (&arg.argument_type, &other_arg.argument_type) {
+                (
+                    CppType::PointerLike {
+                        kind: CppPointerLikeTypeKind::RValueReference,
+                        is_const: false,
+                        target,
+                    },
+                    CppType::PointerLike {
+                        kind: CppPointerLikeTypeKind::Reference,
+                        is_const: true,
+                        target: other_target,
+                    },
+                ) if target == other_target => has_rvalue_reference = true,
+                _ => return false,
+            }
+        }
+        has_rvalue_reference
+    }
+
     pub fn is_same(&self, other: &CppFunction) -> bool {
         let member_is_same = match (&self.member, &other.member) {
             (Some(m1), Some(m2)) => m1.is_same(m2),
@@ -341,6 +396,19 @@ impl CppFunction {
         arg == self.arguments[0].argument_type
     }
 
+    /// Returns true if this method is a move constructor.
+    pub fn is_move_constructor(&self) -> bool {
+        if !self.is_constructor() || self.arguments.len() != 1 {
+            return false;
+        }
+        let arg = CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::RValueReference,
+            is_const: false,
+            target: Box::new(CppType::Class(self.class_path().unwrap())),
+        };
+        arg == self.arguments[0].argument_type
+    }
+
     /// Returns true if this method is a destructor.
     pub fn is_destructor(&self) -> bool {
         match &self.member {
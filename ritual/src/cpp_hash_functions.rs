@@ -0,0 +1,204 @@
+use crate::cpp_data::{CppItem, CppPath, CppPathItem};
+use crate::cpp_function::{CppFunction, CppFunctionArgument};
+use crate::cpp_type::{CppPointerLikeTypeKind, CppType};
+use crate::database::ItemWithSource;
+use crate::processor::ProcessorData;
+use log::trace;
+use ritual_common::errors::Result;
+use std::collections::HashSet;
+
+/// Name of the helper function template that calls `std::hash<T>`.
+/// The template is declared in `global.h` of the C++ wrapper library.
+const STD_HASH_NAME: &str = "std_hash";
+/// Name of the helper function template that calls `qHash`.
+/// The template is declared in `global.h` of the C++ wrapper library.
+const Q_HASH_NAME: &str = "q_hash";
+
+/// Creates `ritual::{name}<T>` function that returns the hash of a `class_path` value.
+fn create_hash_function(name: &str, class_path: &CppPath) -> CppFunction {
+    let class_type = CppType::Class(class_path.clone());
+    CppFunction {
+        path: CppPath::from_items(vec![
+            CppPathItem::from_good_str("ritual"),
+            CppPathItem {
+                name: name.into(),
+                template_arguments: Some(vec![class_type.clone()]),
+            },
+        ]),
+        member: None,
+        operator: None,
+        return_type: CppType::PointerSizedInteger {
+            path: CppPath::from_good_str("size_t"),
+            is_signed: false,
+        },
+        arguments: vec![CppFunctionArgument {
+            name: "value".to_string(),
+            argument_type: CppType::new_reference(true, class_type),
+            has_default_value: false,
+            nullability: None,
+        }],
+        allows_variadic_arguments: false,
+        declaration_code: None,
+        deprecation: None,
+        cast: None,
+        return_nullability: None,
+    }
+}
+
+/// Creates `ritual::std_hash<T>` function for a class that has
+/// a `std::hash<T>` specialization.
+pub fn create_std_hash_function(class_path: &CppPath) -> CppFunction {
+    create_hash_function(STD_HASH_NAME, class_path)
+}
+
+/// Returns true if `function` is one of the hashing functions
+/// added by `create_std_hash_function` or by this processing step.
+pub fn is_hash_function(function: &CppFunction) -> bool {
+    let items = function.path.items();
+    items.len() == 2
+        && items[0].name == "ritual"
+        && (items[1].name == STD_HASH_NAME || items[1].name == Q_HASH_NAME)
+}
+
+/// Returns the class hashed by `function` if it was created by `create_std_hash_function`.
+fn std_hash_function_target(function: &CppFunction) -> Option<&CppPath> {
+    let items = function.path.items();
+    if items.len() != 2 || items[0].name != "ritual" || items[1].name != STD_HASH_NAME {
+        return None;
+    }
+    match items[1].template_arguments.as_ref().map(|args| &args[..]) {
+        Some([CppType::Class(path)]) => Some(path),
+        _ => None,
+    }
+}
+
+/// Returns the class hashed by `function` if it's a free `qHash(const T&)` function.
+/// Other arguments (e.g. the seed) must have default values.
+fn q_hash_target(function: &CppFunction) -> Option<&CppPath> {
+    if function.path.last().name != "qHash"
+        || function.path.last().template_arguments.is_some()
+        || function.member.is_some()
+        || function.arguments.is_empty()
+        || function.arguments[1..]
+            .iter()
+            .any(|arg| !arg.has_default_value)
+    {
+        return None;
+    }
+    let value_type = match &function.arguments[0].argument_type {
+        CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::Reference,
+            is_const: true,
+            target,
+        } => target,
+        other => other,
+    };
+    if let CppType::Class(path) = value_type {
+        if !value_type.is_or_contains_template_parameter() {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Adds `ritual::q_hash<T>` functions for all classes of the current crate
+/// that have a `qHash(const T&)` overload but no `std::hash<T>` specialization.
+/// Hashing functions are used to implement `Hash` for the corresponding Rust types.
+pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
+    let classes: HashSet<&CppPath> = data
+        .db
+        .cpp_items()
+        .filter_map(|item| item.item.as_type_ref())
+        .filter(|t| t.kind.is_class())
+        .map(|t| &t.path)
+        .collect();
+    let classes_with_std_hash: HashSet<&CppPath> = data
+        .db
+        .cpp_items()
+        .filter_map(|item| item.item.as_function_ref())
+        .filter_map(std_hash_function_target)
+        .collect();
+    let mut results = Vec::new();
+    for item in data.db.cpp_items() {
+        let function = if let Some(f) = item.item.as_function_ref() {
+            f
+        } else {
+            continue;
+        };
+        let class_path = if let Some(path) = q_hash_target(function) {
+            path
+        } else {
+            continue;
+        };
+        if !classes.contains(class_path) || classes_with_std_hash.contains(class_path) {
+            continue;
+        }
+        let new_function = create_hash_function(Q_HASH_NAME, class_path);
+        results.push(ItemWithSource::new(
+            &item.id,
+            CppItem::Function(new_function),
+        ));
+    }
+    for item in results {
+        // The helper is a part of the wrapper library, so
+        // the hooks that filter items of the C++ library are not applied.
+        if let Some(id) = data
+            .db
+            .add_cpp_item_without_hook(Some(item.source_id), item.item)?
+        {
+            trace!("added q_hash function: {}", id);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn q_hash_target_works() {
+    use crate::cpp_type::CppBuiltInNumericType;
+
+    let uint = CppType::BuiltInNumeric(CppBuiltInNumericType::UInt);
+    let mut function = CppFunction {
+        path: CppPath::from_good_str("qHash"),
+        member: None,
+        operator: None,
+        return_type: uint.clone(),
+        arguments: vec![
+            CppFunctionArgument {
+                name: "key".to_string(),
+                argument_type: CppType::new_reference(
+                    true,
+                    CppType::Class(CppPath::from_good_str("ns::Point")),
+                ),
+                has_default_value: false,
+                nullability: None,
+            },
+            CppFunctionArgument {
+                name: "seed".to_string(),
+                argument_type: uint,
+                has_default_value: true,
+                nullability: None,
+            },
+        ],
+        allows_variadic_arguments: false,
+        declaration_code: None,
+        deprecation: None,
+        cast: None,
+        return_nullability: None,
+    };
+    assert_eq!(
+        q_hash_target(&function),
+        Some(&CppPath::from_good_str("ns::Point"))
+    );
+    assert!(!is_hash_function(&function));
+    let std_hash = create_std_hash_function(&CppPath::from_good_str("ns::Point"));
+    assert!(is_hash_function(&std_hash));
+    assert_eq!(
+        std_hash_function_target(&std_hash),
+        Some(&CppPath::from_good_str("ns::Point"))
+    );
+    assert_eq!(std_hash_function_target(&function), None);
+
+    // the seed must be optional
+    function.arguments[1].has_default_value = false;
+    assert_eq!(q_hash_target(&function), None);
+}
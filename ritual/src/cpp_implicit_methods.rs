@@ -44,7 +44,9 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 arguments: vec![],
                 allows_variadic_arguments: false,
                 cast: None,
+                return_nullability: None,
                 declaration_code: None,
+                deprecation: None,
             };
             methods.push(ItemWithSource::new(&type1.id, destructor));
 
@@ -68,7 +70,9 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 arguments: vec![],
                 allows_variadic_arguments: false,
                 declaration_code: None,
+                deprecation: None,
                 cast: None,
+                return_nullability: None,
             };
             methods.push(ItemWithSource::new(&type1.id, default_constructor));
 
@@ -76,6 +80,7 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 argument_type: CppType::new_reference(true, CppType::Class(class_path.clone())),
                 name: "other".to_string(),
                 has_default_value: false,
+                nullability: None,
             };
 
             let copy_constructor = CppFunction {
@@ -98,7 +103,9 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 arguments: vec![copy_arg.clone()],
                 allows_variadic_arguments: false,
                 cast: None,
+                return_nullability: None,
                 declaration_code: None,
+                deprecation: None,
             };
             methods.push(ItemWithSource::new(&type1.id, copy_constructor));
 
@@ -122,7 +129,9 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 arguments: vec![copy_arg],
                 allows_variadic_arguments: false,
                 cast: None,
+                return_nullability: None,
                 declaration_code: None,
+                deprecation: None,
             };
             methods.push(ItemWithSource::new(&type1.id, assignment_operator));
         }
@@ -0,0 +1,200 @@
+use crate::cpp_data::{CppItem, CppPath, CppPathItem};
+use crate::cpp_function::{CppFunction, CppFunctionArgument};
+use crate::cpp_operator::CppOperator;
+use crate::cpp_type::{CppBuiltInNumericType, CppPointerLikeTypeKind, CppType};
+use crate::database::ItemWithSource;
+use crate::processor::ProcessorData;
+use log::trace;
+use ritual_common::errors::Result;
+use std::collections::HashSet;
+
+/// Name of the helper function template declared in `global.h`
+/// of the C++ wrapper library.
+const OSTREAM_TO_STRING_NAME: &str = "ostream_to_string";
+
+/// Returns true if `type1` is `std::ostream`.
+fn is_ostream(type1: &CppType) -> bool {
+    if let CppType::Class(path) = type1 {
+        if path == &CppPath::from_good_str("std::ostream") {
+            return true;
+        }
+        let items = path.items();
+        items.len() == 2
+            && items[0].name == "std"
+            && items[0].template_arguments.is_none()
+            && items[1].name == "basic_ostream"
+            && items[1].template_arguments.as_ref().map_or(false, |args| {
+                args.get(0) == Some(&CppType::BuiltInNumeric(CppBuiltInNumericType::Char))
+            })
+    } else {
+        false
+    }
+}
+
+/// Returns the class printed by `function` if it's
+/// a free `operator<<(std::ostream&, const T&)` function.
+fn ostream_operator_target(function: &CppFunction) -> Option<&CppPath> {
+    if function.operator != Some(CppOperator::BitwiseLeftShift)
+        || function.member.is_some()
+        || function.arguments.len() != 2
+    {
+        return None;
+    }
+    match &function.arguments[0].argument_type {
+        CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::Reference,
+            is_const: false,
+            target,
+        } if is_ostream(target) => {}
+        _ => return None,
+    }
+    let value_type = match &function.arguments[1].argument_type {
+        CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::Reference,
+            is_const: true,
+            target,
+        } => target,
+        other => other,
+    };
+    if let CppType::Class(path) = value_type {
+        if !value_type.is_or_contains_template_parameter() {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Returns true if `function` is an instantiation of `ritual::ostream_to_string`
+/// added by this processing step.
+pub fn is_ostream_to_string(function: &CppFunction) -> bool {
+    let items = function.path.items();
+    items.len() == 2 && items[0].name == "ritual" && items[1].name == OSTREAM_TO_STRING_NAME
+}
+
+/// Creates `ritual::ostream_to_string<T>` function that returns the output
+/// of `operator<<` for `class_path` as a string allocated with `malloc`.
+fn create_ostream_to_string_function(class_path: &CppPath) -> CppFunction {
+    let class_type = CppType::Class(class_path.clone());
+    CppFunction {
+        path: CppPath::from_items(vec![
+            CppPathItem::from_good_str("ritual"),
+            CppPathItem {
+                name: OSTREAM_TO_STRING_NAME.into(),
+                template_arguments: Some(vec![class_type.clone()]),
+            },
+        ]),
+        member: None,
+        operator: None,
+        return_type: CppType::new_pointer(
+            false,
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Char),
+        ),
+        arguments: vec![CppFunctionArgument {
+            name: "value".to_string(),
+            argument_type: CppType::new_reference(true, class_type),
+            has_default_value: false,
+            nullability: None,
+        }],
+        allows_variadic_arguments: false,
+        declaration_code: None,
+        deprecation: None,
+        cast: None,
+        return_nullability: None,
+    }
+}
+
+/// Adds `ritual::ostream_to_string<T>` functions for all classes
+/// of the current crate that have `operator<<(std::ostream&, const T&)`.
+/// These functions are used to implement `Display`
+/// for the corresponding Rust types.
+pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
+    let classes: HashSet<&CppPath> = data
+        .db
+        .cpp_items()
+        .filter_map(|item| item.item.as_type_ref())
+        .filter(|t| t.kind.is_class())
+        .map(|t| &t.path)
+        .collect();
+    let mut results = Vec::new();
+    for item in data.db.cpp_items() {
+        let function = if let Some(f) = item.item.as_function_ref() {
+            f
+        } else {
+            continue;
+        };
+        let class_path = if let Some(path) = ostream_operator_target(function) {
+            path
+        } else {
+            continue;
+        };
+        if !classes.contains(class_path) {
+            continue;
+        }
+        let new_function = create_ostream_to_string_function(class_path);
+        results.push(ItemWithSource::new(
+            &item.id,
+            CppItem::Function(new_function),
+        ));
+    }
+    for item in results {
+        // The helper is a part of the wrapper library, so
+        // the hooks that filter items of the C++ library are not applied.
+        if let Some(id) = data
+            .db
+            .add_cpp_item_without_hook(Some(item.source_id), item.item)?
+        {
+            trace!("added ostream_to_string function: {}", id);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn ostream_operator_target_works() {
+    let ostream = CppType::Class(CppPath::from_good_str("std::ostream"));
+    let point = CppType::Class(CppPath::from_good_str("ns::Point"));
+    let mut function = CppFunction {
+        path: CppPath::from_items(vec![
+            CppPathItem::from_good_str("ns"),
+            CppPathItem {
+                name: "operator<<".into(),
+                template_arguments: None,
+            },
+        ]),
+        member: None,
+        operator: Some(CppOperator::BitwiseLeftShift),
+        return_type: CppType::new_reference(false, ostream.clone()),
+        arguments: vec![
+            CppFunctionArgument {
+                name: "stream".to_string(),
+                argument_type: CppType::new_reference(false, ostream),
+                has_default_value: false,
+                nullability: None,
+            },
+            CppFunctionArgument {
+                name: "point".to_string(),
+                argument_type: CppType::new_reference(true, point),
+                has_default_value: false,
+                nullability: None,
+            },
+        ],
+        allows_variadic_arguments: false,
+        declaration_code: None,
+        deprecation: None,
+        cast: None,
+        return_nullability: None,
+    };
+    assert_eq!(
+        ostream_operator_target(&function),
+        Some(&CppPath::from_good_str("ns::Point"))
+    );
+
+    let helper = create_ostream_to_string_function(&CppPath::from_good_str("ns::Point"));
+    assert!(is_ostream_to_string(&helper));
+    assert!(!is_ostream_to_string(&function));
+
+    // `QDataStream& operator<<(QDataStream&, const Point&)` is not an ostream operator
+    function.arguments[0].argument_type =
+        CppType::new_reference(false, CppType::Class(CppPath::from_good_str("QDataStream")));
+    assert_eq!(ostream_operator_target(&function), None);
+}
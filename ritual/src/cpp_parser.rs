@@ -1,18 +1,21 @@
 use crate::config::Config;
 use crate::cpp_code_generator::{all_include_directives, write_include_directives};
 use crate::cpp_data::{
-    CppBaseSpecifier, CppClassField, CppEnumValue, CppItem, CppNamespace, CppOriginLocation,
-    CppPath, CppPathItem, CppTypeDeclaration, CppTypeDeclarationKind, CppVisibility,
+    CppBaseSpecifier, CppClassField, CppDeprecation, CppEnumValue, CppGlobalVariable, CppItem,
+    CppMacroConstant, CppMacroValue, CppNamespace, CppOriginLocation, CppPath, CppPathItem,
+    CppTypeAlias, CppTypeDeclaration, CppTypeDeclarationKind, CppVisibility,
 };
 use crate::cpp_function::{
-    CppFunction, CppFunctionArgument, CppFunctionKind, CppFunctionMemberData,
+    CppFunction, CppFunctionArgument, CppFunctionKind, CppFunctionMemberData, CppNullability,
 };
+use crate::cpp_hash_functions;
 use crate::cpp_operator::CppOperator;
 use crate::cpp_type::{
     CppBuiltInNumericType, CppFunctionPointerType, CppPointerLikeTypeKind, CppSpecificNumericType,
     CppSpecificNumericTypeKind, CppTemplateParameter, CppType,
 };
 use crate::database::ItemId;
+use crate::doc_comment_parser::parse_doc_comment;
 use crate::processor::ProcessorData;
 use clang::diagnostic::{Diagnostic, Severity};
 use clang::*;
@@ -25,7 +28,9 @@ use ritual_common::file_utils::{
     canonicalize, copy_recursively, create_file, open_file, os_str_to_str, path_to_str,
     remove_dir_all, remove_file,
 };
-use ritual_common::target::{current_env, current_target, Env, LibraryTarget};
+use ritual_common::target::{
+    current_env, current_target, Env, LibraryTarget, PointerWidth, Target, OS,
+};
 use ritual_common::utils::MapIfOk;
 use std::io::Write;
 use std::mem;
@@ -109,6 +114,74 @@ fn get_origin_location(entity: Entity<'_>) -> Result<CppOriginLocation> {
     }
 }
 
+/// Extracts information about a deprecation attribute of `entity`
+/// (`[[deprecated]]`, `__attribute__((deprecated))`, `Q_DECL_DEPRECATED`, etc.).
+fn get_deprecation(entity: Entity<'_>) -> Option<CppDeprecation> {
+    if entity.get_availability() != Availability::Deprecated {
+        return None;
+    }
+    let message = entity
+        .get_children()
+        .into_iter()
+        .filter(|child| child.get_kind() == EntityKind::UnexposedAttr)
+        .filter_map(|child| child.get_range())
+        .filter_map(|range| {
+            let tokens = range
+                .tokenize()
+                .into_iter()
+                .map(|token| token.get_spelling())
+                .collect_vec();
+            parse_deprecation_message(&tokens)
+        })
+        .next();
+    Some(CppDeprecation { message })
+}
+
+/// Returns true if enum `entity` is declared with `enum class` or `enum struct`.
+fn is_scoped_enum(entity: Entity<'_>) -> bool {
+    entity.get_range().map_or(false, |range| {
+        let tokens = range
+            .tokenize()
+            .into_iter()
+            .take(2)
+            .map(|token| token.get_spelling())
+            .collect_vec();
+        tokens.len() == 2 && tokens[0] == "enum" && (tokens[1] == "class" || tokens[1] == "struct")
+    })
+}
+
+/// Returns nullability specified by a `_Nonnull` or `_Nullable` keyword in `tokens`.
+fn parse_nullability(tokens: &[String]) -> Option<CppNullability> {
+    tokens.iter().find_map(|token| match token.as_str() {
+        "_Nonnull" => Some(CppNullability::NonNull),
+        "_Nullable" => Some(CppNullability::Nullable),
+        _ => None,
+    })
+}
+
+/// Extracts the message from the tokens of a deprecation attribute.
+/// Returns `None` if the attribute is not a deprecation attribute or has no message.
+fn parse_deprecation_message(tokens: &[String]) -> Option<String> {
+    let start = tokens
+        .iter()
+        .position(|token| token.to_lowercase().contains("deprecated"))?;
+    let mut message = String::new();
+    let mut found = false;
+    for token in tokens[start + 1..]
+        .iter()
+        .skip_while(|token| !token.starts_with('"'))
+        .take_while(|token| token.starts_with('"'))
+    {
+        message.push_str(&parse_string_literal(token).ok()?);
+        found = true;
+    }
+    if found {
+        Some(message)
+    } else {
+        None
+    }
+}
+
 /// Extract template argument declarations from a class or method definition `entity`.
 fn get_template_arguments(entity: Entity<'_>) -> Option<Vec<CppType>> {
     let mut nested_level = 0;
@@ -129,7 +202,10 @@ fn get_template_arguments(entity: Entity<'_>) -> Option<Vec<CppType>> {
     let args = entity
         .get_children()
         .into_iter()
-        .filter(|c| c.get_kind() == EntityKind::TemplateTypeParameter)
+        .filter(|c| {
+            c.get_kind() == EntityKind::TemplateTypeParameter
+                || c.get_kind() == EntityKind::NonTypeTemplateParameter
+        })
         .enumerate()
         .map(|(i, c)| {
             CppType::TemplateParameter(CppTemplateParameter {
@@ -146,6 +222,37 @@ fn get_template_arguments(entity: Entity<'_>) -> Option<Vec<CppType>> {
     }
 }
 
+/// Checks that all non-type template parameters of `entity` have integral types.
+fn check_non_type_template_parameters(entity: Entity<'_>) -> Result<()> {
+    for child in entity.get_children() {
+        if child.get_kind() != EntityKind::NonTypeTemplateParameter {
+            continue;
+        }
+        let kind = child
+            .get_type()
+            .ok_or_else(|| err_msg("failed to get non-type template parameter type"))?
+            .get_canonical_type()
+            .get_kind();
+        match kind {
+            TypeKind::Bool
+            | TypeKind::CharS
+            | TypeKind::CharU
+            | TypeKind::SChar
+            | TypeKind::UChar
+            | TypeKind::Short
+            | TypeKind::UShort
+            | TypeKind::Int
+            | TypeKind::UInt
+            | TypeKind::Long
+            | TypeKind::ULong
+            | TypeKind::LongLong
+            | TypeKind::ULongLong => {}
+            _ => bail!("Non-type template parameter of non-integral type is not supported"),
+        }
+    }
+    Ok(())
+}
+
 fn get_context_template_args(entity: Entity<'_>) -> Vec<CppType> {
     let mut current_entity = entity;
     let mut args = Vec::new();
@@ -183,6 +290,7 @@ fn get_path(entity: Entity<'_>) -> Result<CppPath> {
             EntityKind::ClassDecl
             | EntityKind::ClassTemplate
             | EntityKind::StructDecl
+            | EntityKind::UnionDecl
             | EntityKind::Namespace
             | EntityKind::EnumDecl
             | EntityKind::ClassTemplatePartialSpecialization => {
@@ -400,13 +508,21 @@ pub fn parse_generated_items(data: &mut ProcessorData<'_>) -> Result<()> {
 }
 
 impl CppParser<'_, '_> {
+    /// Adds `item` parsed from `entity` to the database.
+    /// If enabled, documentation comment of `entity` is also added.
     fn add_output(
         &mut self,
         include_file: String,
-        origin_location: CppOriginLocation,
+        entity: Entity<'_>,
         item: CppItem,
     ) -> Result<()> {
+        let origin_location = get_origin_location(entity)?;
         if let Some(id) = self.data.add_cpp_item(self.source_id.clone(), item)? {
+            if self.data.config.parse_doc_comments() {
+                if let Some(doc) = entity.get_comment().and_then(|c| parse_doc_comment(&c)) {
+                    self.data.db.add_doc_item(id.clone(), doc);
+                }
+            }
             self.output.0.push(CppParserOutputItem {
                 include_file,
                 origin_location,
@@ -459,6 +575,7 @@ impl CppParser<'_, '_> {
                 if declaration.get_kind() == EntityKind::ClassDecl
                     || declaration.get_kind() == EntityKind::ClassTemplate
                     || declaration.get_kind() == EntityKind::StructDecl
+                    || declaration.get_kind() == EntityKind::UnionDecl
                 {
                     if declaration
                         .get_accessibility()
@@ -490,7 +607,7 @@ impl CppParser<'_, '_> {
                         }
                         let mut name = get_path(declaration)?;
                         name.last_mut().template_arguments = Some(arg_types);
-                        return Ok(CppType::Class(name));
+                        return Ok(class_type(name));
                     } else {
                         bail!("Can't parse declaration of an unexposed type: {}", name);
                     }
@@ -529,6 +646,10 @@ impl CppParser<'_, '_> {
             return Ok(arg.clone());
         }
 
+        if let Some(value) = parse_integral_template_argument(&name) {
+            return Ok(CppType::IntegralConstant(value));
+        }
+
         if name.ends_with(" *") {
             let remaining_name = name[0..name.len() - " *".len()].trim();
             let subtype = self.parse_unexposed_type(
@@ -586,7 +707,7 @@ impl CppParser<'_, '_> {
                 CppTypeDeclarationKind::Enum { .. } => {
                     return Ok(CppType::Enum { path });
                 }
-                CppTypeDeclarationKind::Class { .. } => {
+                CppTypeDeclarationKind::Class { .. } | CppTypeDeclarationKind::Union => {
                     return Ok(CppType::Class(path));
                 }
             }
@@ -739,9 +860,25 @@ impl CppParser<'_, '_> {
                             if arg_types.is_empty() {
                                 bail!("arg_types is empty");
                             }
-                            for arg_type in arg_types {
+                            // values of non-type arguments are only available in the display name
+                            let arg_texts = parse_template_args(&type1.get_display_name())
+                                .map(|(_name, args)| args);
+                            for (index, arg_type) in arg_types.into_iter().enumerate() {
                                 match arg_type {
-                                    None => bail!("Template argument is None"),
+                                    None => {
+                                        let arg_text = arg_texts
+                                            .as_ref()
+                                            .and_then(|args| args.get(index))
+                                            .ok_or_else(|| err_msg("Template argument is None"))?;
+                                        let value = parse_integral_template_argument(arg_text)
+                                            .ok_or_else(|| {
+                                                format_err!(
+                                                    "Unsupported non-type template argument: {}",
+                                                    arg_text
+                                                )
+                                            })?;
+                                        r.push(CppType::IntegralConstant(value));
+                                    }
                                     Some(arg_type) => {
                                         match self.parse_type(arg_type, context_template_args) {
                                             Ok(parsed_type) => r.push(parsed_type),
@@ -761,7 +898,7 @@ impl CppParser<'_, '_> {
                     };
                     declaration_name.last_mut().template_arguments = template_arguments;
 
-                    Ok(CppType::Class(declaration_name))
+                    Ok(class_type(declaration_name))
                 } else {
                     bail!("failed to get class declaration: {:?}", type1);
                 }
@@ -952,6 +1089,10 @@ impl CppParser<'_, '_> {
                 path: CppPath::from_good_str(name),
                 is_signed: false,
             }),
+            // `operator<<` overloads for `std::ostream` are detected by
+            // `cpp_ostream_operators`, so the typedef is preserved
+            // instead of expanding it to `std::basic_ostream<char, ...>`.
+            "std::ostream" => Some(CppType::Class(CppPath::from_good_str("std::ostream"))),
             "std::initializer_list::size_type"
             | "std::__cxx11::basic_string::size_type"
             | "std::vector::size_type" => Some(CppType::PointerSizedInteger {
@@ -967,17 +1108,18 @@ impl CppParser<'_, '_> {
     fn parse_function(&mut self, entity: Entity<'_>) -> Result<()> {
         let class_name = match entity.get_semantic_parent() {
             Some(p) => match p.get_kind() {
-                EntityKind::ClassDecl | EntityKind::ClassTemplate | EntityKind::StructDecl => {
-                    match get_path(p) {
-                        Ok(class_name) => Some(class_name),
-                        Err(msg) => {
-                            bail!(
-                                "function parent is a class but it doesn't have a name: {}",
-                                msg
-                            );
-                        }
+                EntityKind::ClassDecl
+                | EntityKind::ClassTemplate
+                | EntityKind::StructDecl
+                | EntityKind::UnionDecl => match get_path(p) {
+                    Ok(class_name) => Some(class_name),
+                    Err(msg) => {
+                        bail!(
+                            "function parent is a class but it doesn't have a name: {}",
+                            msg
+                        );
                     }
-                }
+                },
                 EntityKind::ClassTemplatePartialSpecialization => {
                     bail!("this function is part of a template partial specialization");
                 }
@@ -1045,6 +1187,7 @@ impl CppParser<'_, '_> {
                     )
                 })?;
             let mut has_default_value = false;
+            let mut type_tokens = Vec::new();
             for token in argument_entity
                 .get_range()
                 .ok_or_else(|| {
@@ -1064,11 +1207,18 @@ impl CppParser<'_, '_> {
                     // clang sometimes reports incorrect range for arguments
                     break;
                 }
+                type_tokens.push(spelling);
             }
+            let nullability = if argument_type.is_pointer() {
+                parse_nullability(&type_tokens)
+            } else {
+                None
+            };
             arguments.push(CppFunctionArgument {
                 name,
                 argument_type,
                 has_default_value,
+                nullability,
             });
         }
 
@@ -1091,13 +1241,7 @@ impl CppParser<'_, '_> {
 
         let template_arguments = match entity.get_kind() {
             EntityKind::FunctionTemplate => {
-                if entity
-                    .get_children()
-                    .into_iter()
-                    .any(|c| c.get_kind() == EntityKind::NonTypeTemplateParameter)
-                {
-                    bail!("Non-type template parameter is not supported");
-                }
+                check_non_type_template_parameters(entity)?;
                 get_template_arguments(entity)
             }
             _ => None,
@@ -1219,6 +1363,23 @@ impl CppParser<'_, '_> {
             Some(token_strings.join(" "))
         };
 
+        let return_nullability = if return_type_parsed.is_pointer() {
+            entity.get_range().and_then(|range| {
+                // the return type is declared before the function's name
+                let tokens = range
+                    .tokenize()
+                    .into_iter()
+                    .map(|token| token.get_spelling())
+                    .take_while(|token| {
+                        token != &name_with_namespace.last().name && token != "operator"
+                    })
+                    .collect_vec();
+                parse_nullability(&tokens)
+            })
+        } else {
+            None
+        };
+
         let function = CppFunction {
             path: name_with_namespace,
             operator: method_operator,
@@ -1249,12 +1410,14 @@ impl CppParser<'_, '_> {
             allows_variadic_arguments,
             return_type: return_type_parsed,
             cast: None,
+            return_nullability,
             declaration_code,
+            deprecation: get_deprecation(entity),
         };
 
         self.add_output(
             self.entity_include_file(entity)?,
-            get_origin_location(entity)?,
+            entity,
             CppItem::Function(function),
         )?;
 
@@ -1271,12 +1434,31 @@ impl CppParser<'_, '_> {
             )
         })?;
         let enum_name = get_path(entity)?;
+        // The underlying type of an unscoped enum without a fixed type is
+        // implementation-defined, so the type chosen by the compiler is used.
+        let clang_underlying_type = entity
+            .get_enum_underlying_type()
+            .ok_or_else(|| err_msg("failed to get enum underlying type"))?;
+        let underlying_type = self
+            .parse_type(clang_underlying_type, &[])
+            .with_context(|_| err_msg("failed to parse enum underlying type"))?;
+        let is_unsigned = match &underlying_type {
+            CppType::BuiltInNumeric(numeric) => numeric.is_unsigned_integer(),
+            CppType::SpecificNumeric(CppSpecificNumericType { kind, .. }) => {
+                kind == &CppSpecificNumericTypeKind::Integer { is_signed: false }
+            }
+            _ => false,
+        };
         self.add_output(
             include_file.clone(),
-            get_origin_location(entity)?,
+            entity,
             CppItem::Type(CppTypeDeclaration {
-                kind: CppTypeDeclarationKind::Enum,
+                kind: CppTypeDeclarationKind::Enum {
+                    underlying_type,
+                    is_scoped: is_scoped_enum(entity),
+                },
                 path: enum_name.clone(),
+                deprecation: get_deprecation(entity),
             }),
         )?;
         for child in entity.get_children() {
@@ -1285,15 +1467,19 @@ impl CppParser<'_, '_> {
                     .get_enum_constant_value()
                     .ok_or_else(|| err_msg("failed to get value of enum variant"))?;
 
+                // Values of unsigned enums that exceed `i64::MAX`
+                // are stored in the bit pattern of `i64`.
+                let value = if is_unsigned { val.1 as i64 } else { val.0 };
+
                 let value_name = child
                     .get_name()
                     .ok_or_else(|| err_msg("failed to get name of enum variant"))?;
                 self.add_output(
                     include_file.clone(),
-                    get_origin_location(child)?,
+                    child,
                     CppItem::EnumValue(CppEnumValue {
                         path: enum_name.join(CppPathItem::from_good_str(&value_name)),
-                        value: val.0,
+                        value,
                     }),
                 )?;
             }
@@ -1317,7 +1503,7 @@ impl CppParser<'_, '_> {
             .with_context(|_| err_msg("failed to parse field type"))?;
         self.add_output(
             include_file,
-            get_origin_location(entity)?,
+            entity,
             CppItem::ClassField(CppClassField {
                 path: class_type.join(CppPathItem::from_good_str(&field_name)),
                 field_type,
@@ -1333,6 +1519,53 @@ impl CppParser<'_, '_> {
         Ok(())
     }
 
+    /// Parses a variable `entity` declared at namespace scope.
+    fn parse_global_variable(&mut self, entity: Entity<'_>) -> Result<()> {
+        let include_file = self
+            .entity_include_file(entity)
+            .with_context(|_| err_msg("Origin of variable is unknown"))?;
+        let path = get_path(entity)?;
+        let clang_type = entity
+            .get_type()
+            .ok_or_else(|| err_msg("failed to get variable type"))?;
+        let variable_type = self
+            .parse_type(clang_type, &[])
+            .with_context(|_| err_msg("failed to parse variable type"))?;
+        self.add_output(
+            include_file,
+            entity,
+            CppItem::GlobalVariable(CppGlobalVariable {
+                path,
+                variable_type,
+                is_const: clang_type.is_const_qualified(),
+            }),
+        )?;
+        Ok(())
+    }
+
+    /// Parses a `typedef` or a `using` alias declaration `entity`.
+    fn parse_type_alias(&mut self, entity: Entity<'_>) -> Result<()> {
+        let include_file = self
+            .entity_include_file(entity)
+            .with_context(|_| err_msg("Origin of type alias is unknown"))?;
+        let path = get_path(entity)?;
+        let underlying_type = entity
+            .get_typedef_underlying_type()
+            .ok_or_else(|| err_msg("failed to get underlying type"))?;
+        let target_type = self
+            .parse_type(underlying_type, &get_context_template_args(entity))
+            .with_context(|_| err_msg("failed to parse underlying type"))?;
+        if target_type == CppType::Class(path.clone()) {
+            bail!("type alias has the same name as its target");
+        }
+        self.add_output(
+            include_file,
+            entity,
+            CppItem::TypeAlias(CppTypeAlias { path, target_type }),
+        )?;
+        Ok(())
+    }
+
     // we pass parent manually because both lexical and semantic parent are missing for these
     // entities for some reason
     fn parse_class_base(
@@ -1350,7 +1583,7 @@ impl CppParser<'_, '_> {
         if let CppType::Class(base_type) = &base_type {
             self.add_output(
                 self.entity_include_file(entity)?,
-                get_origin_location(entity).unwrap(),
+                entity,
                 CppItem::ClassBase(CppBaseSpecifier {
                     base_class_type: base_type.clone(),
                     is_virtual: entity.is_virtual_base(),
@@ -1369,7 +1602,7 @@ impl CppParser<'_, '_> {
         Ok(())
     }
 
-    /// Parses a class or a struct `entity`.
+    /// Parses a class, a struct or a union `entity`.
     fn parse_class(&mut self, entity: Entity<'_>) -> Result<()> {
         let include_file = self.entity_include_file(entity).with_context(|_| {
             format!(
@@ -1379,15 +1612,14 @@ impl CppParser<'_, '_> {
             )
         })?;
         let full_name = get_path(entity)?;
+        let kind = if entity.get_kind() == EntityKind::UnionDecl {
+            CppTypeDeclarationKind::Union
+        } else {
+            CppTypeDeclarationKind::Class
+        };
         let template_arguments = get_template_arguments(entity);
         if entity.get_kind() == EntityKind::ClassTemplate {
-            if entity
-                .get_children()
-                .into_iter()
-                .any(|c| c.get_kind() == EntityKind::NonTypeTemplateParameter)
-            {
-                bail!("Non-type template parameter is not supported");
-            }
+            check_non_type_template_parameters(entity)?;
 
             if template_arguments.is_none() {
                 dump_entity(entity, 0);
@@ -1419,16 +1651,14 @@ impl CppParser<'_, '_> {
                 }
                 current_base_index += 1;
             }
-            if child.get_kind() == EntityKind::NonTypeTemplateParameter {
-                bail!("Non-type template parameter is not supported");
-            }
         }
         self.add_output(
             include_file,
-            get_origin_location(entity).unwrap(),
+            entity,
             CppItem::Type(CppTypeDeclaration {
-                kind: CppTypeDeclarationKind::Class,
+                kind,
                 path: full_name,
+                deprecation: get_deprecation(entity),
             }),
         )?;
         Ok(())
@@ -1492,12 +1722,48 @@ impl CppParser<'_, '_> {
         self.parse_types(entity)?;
         debug!("Parsing functions");
         self.parse_functions(entity)?;
+        if self.data.config.parse_macro_constants() {
+            debug!("Parsing macros");
+            self.parse_macros(entity)?;
+        }
         for hook in self.data.config.after_cpp_parser_hooks() {
             hook(self.data, &self.output)?;
         }
         Ok(())
     }
 
+    /// Parses a `std::hash<T>` specialization and adds a function
+    /// that can be used to implement `Hash` for `T`.
+    fn parse_std_hash_specialization(&mut self, entity: Entity<'_>) -> Result<()> {
+        let template = entity
+            .get_template()
+            .ok_or_else(|| err_msg("not a template specialization"))?;
+        if get_path(template)? != CppPath::from_good_str("std::hash") {
+            bail!("not a std::hash specialization");
+        }
+        let include_file = self
+            .entity_include_file(entity)
+            .with_context(|_| err_msg("Origin of std::hash specialization is unknown"))?;
+        let type1 = entity
+            .get_type()
+            .ok_or_else(|| err_msg("failed to get type"))?;
+        let arg_types = type1
+            .get_template_argument_types()
+            .ok_or_else(|| err_msg("failed to get template arguments"))?;
+        if arg_types.len() != 1 {
+            bail!("expected 1 template argument, got {}", arg_types.len());
+        }
+        let arg_type = arg_types[0].ok_or_else(|| err_msg("template argument is not a type"))?;
+        let class_type = self.parse_type(arg_type, &[])?;
+        let class_path = match &class_type {
+            CppType::Class(path) if !class_type.is_or_contains_template_parameter() => path,
+            _ => bail!("unsupported type: {}", class_type.to_cpp_pseudo_code()),
+        };
+        let function = cpp_hash_functions::create_std_hash_function(class_path);
+        self.add_output(include_file, entity, CppItem::Function(function))?;
+        Ok(())
+    }
+
     /// Parses type declarations in translation unit `entity`
     /// and saves them to `self`.
     fn parse_types(&mut self, entity: Entity<'_>) -> Result<()> {
@@ -1520,11 +1786,14 @@ impl CppParser<'_, '_> {
                     }
                 }
             }
-            EntityKind::ClassDecl | EntityKind::ClassTemplate | EntityKind::StructDecl => {
+            EntityKind::ClassDecl
+            | EntityKind::ClassTemplate
+            | EntityKind::StructDecl
+            | EntityKind::UnionDecl => {
                 if entity.get_accessibility() == Some(Accessibility::Private) {
                     return Ok(()); // skipping private stuff
                 }
-                let ok = entity.get_name().is_some() && // not an anonymous struct
+                let ok = entity.get_name().is_some() && // not an anonymous struct or union
                     entity.is_definition() && // not a forward declaration
                     entity.get_template().is_none(); // not a template specialization
                 if ok {
@@ -1536,13 +1805,21 @@ impl CppParser<'_, '_> {
                         );
                         trace!("entity: {:?}", entity);
                     }
+                } else if entity.is_definition() && entity.get_template().is_some() {
+                    if let Err(error) = self.parse_std_hash_specialization(entity) {
+                        trace!(
+                            "not a std::hash specialization: {}: {}",
+                            get_full_name_display(entity),
+                            error
+                        );
+                    }
                 }
             }
             EntityKind::Namespace => match get_path(entity) {
                 Ok(path) => {
                     self.add_output(
                         self.entity_include_file(entity)?,
-                        get_origin_location(entity).unwrap(),
+                        entity,
                         CppItem::Namespace(CppNamespace { path }),
                     )?;
                 }
@@ -1554,6 +1831,7 @@ impl CppParser<'_, '_> {
             EntityKind::TranslationUnit
             | EntityKind::Namespace
             | EntityKind::StructDecl
+            | EntityKind::UnionDecl
             | EntityKind::ClassDecl
             | EntityKind::UnexposedDecl
             | EntityKind::ClassTemplate => {
@@ -1566,6 +1844,81 @@ impl CppParser<'_, '_> {
         Ok(())
     }
 
+    /// Parses object-like macros in translation unit `entity`.
+    /// Macros are only available in the detailed preprocessing record,
+    /// so they are direct children of the translation unit.
+    fn parse_macros(&mut self, entity: Entity<'_>) -> Result<()> {
+        for child in entity.get_children() {
+            if child.get_kind() != EntityKind::MacroDefinition {
+                continue;
+            }
+            if let Err(error) = self.parse_macro_constant(child) {
+                trace!(
+                    "skipping macro: {}: {}",
+                    child.get_name().unwrap_or_default(),
+                    error
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a macro definition `entity` if it expands to a literal.
+    fn parse_macro_constant(&mut self, entity: Entity<'_>) -> Result<()> {
+        let name = entity
+            .get_name()
+            .ok_or_else(|| err_msg("failed to get macro name"))?;
+        let file_path = self.entity_include_path(entity)?;
+        if !self.current_target_paths.is_empty()
+            && !self
+                .current_target_paths
+                .iter()
+                .any(|x| file_path.starts_with(x))
+        {
+            bail!("macro is not in target include paths");
+        }
+        let path = CppPath::from_good_str(&name);
+        if let Some(hook) = self.data.config.cpp_parser_path_hook() {
+            if !hook(&path)? {
+                bail!("macro is blocked by cpp_parser_path_hook");
+            }
+        }
+
+        let range = entity
+            .get_range()
+            .ok_or_else(|| err_msg("failed to get macro range"))?;
+        let end_offset = range.get_end().get_file_location().offset;
+        // libclang may include an extra token after the end of the range
+        let tokens = range
+            .tokenize()
+            .into_iter()
+            .filter(|token| token.get_range().get_start().get_file_location().offset <= end_offset)
+            .collect_vec();
+        if tokens.is_empty() {
+            bail!("failed to tokenize macro");
+        }
+        if tokens.len() > 1 {
+            let name_end = tokens[0].get_range().get_end().get_file_location().offset;
+            let next_start = tokens[1].get_range().get_start().get_file_location().offset;
+            if tokens[1].get_spelling() == "(" && name_end == next_start {
+                bail!("function-like macros are not supported");
+            }
+        }
+        let spellings = tokens[1..].iter().map(|t| t.get_spelling()).collect_vec();
+        let value = parse_macro_value(&spellings, is_long_64_bit(self.data.config))?;
+        let include_file = self.entity_include_file(entity)?;
+        self.add_output(
+            include_file.clone(),
+            entity,
+            CppItem::MacroConstant(CppMacroConstant {
+                path,
+                include_file,
+                value,
+            }),
+        )?;
+        Ok(())
+    }
+
     /// Parses methods in translation unit `entity`.
     fn parse_functions(&mut self, entity: Entity<'_>) -> Result<()> {
         if !self.should_process_entity(entity)? {
@@ -1588,6 +1941,38 @@ impl CppParser<'_, '_> {
                     trace!("entity: {:?}", entity);
                 }
             }
+            EntityKind::VarDecl => {
+                // static class members are handled by `parse_class_field`
+                let is_namespace_scope = entity.get_semantic_parent().map_or(false, |p| {
+                    p.get_kind() == EntityKind::Namespace
+                        || p.get_kind() == EntityKind::TranslationUnit
+                });
+                if is_namespace_scope {
+                    if let Err(error) = self.parse_global_variable(entity) {
+                        debug!(
+                            "failed to parse variable: {}: {}",
+                            get_full_name_display(entity),
+                            error
+                        );
+                        trace!("entity: {:?}", entity);
+                    }
+                }
+            }
+            EntityKind::TypedefDecl | EntityKind::TypeAliasDecl => {
+                let is_public = entity
+                    .get_accessibility()
+                    .map_or(true, |a| a == Accessibility::Public);
+                if is_public {
+                    if let Err(error) = self.parse_type_alias(entity) {
+                        debug!(
+                            "failed to parse type alias: {}: {}",
+                            get_full_name_display(entity),
+                            error
+                        );
+                        trace!("entity: {:?}", entity);
+                    }
+                }
+            }
             EntityKind::StructDecl
             | EntityKind::ClassDecl
             | EntityKind::ClassTemplate
@@ -1622,6 +2007,7 @@ impl CppParser<'_, '_> {
             EntityKind::TranslationUnit
             | EntityKind::Namespace
             | EntityKind::StructDecl
+            | EntityKind::UnionDecl
             | EntityKind::ClassDecl
             | EntityKind::UnexposedDecl
             | EntityKind::ClassTemplate => {
@@ -1635,6 +2021,244 @@ impl CppParser<'_, '_> {
     }
 }
 
+/// Returns true if `long` is a 64-bit type on all targets the crate is generated for.
+/// If a cluster is configured, targets of its workers are used instead of the current target.
+fn is_long_64_bit(config: &Config) -> bool {
+    let is_64_bit =
+        |target: &Target| target.os != OS::Windows && target.pointer_width == PointerWidth::P64;
+    if let Some(cluster_config) = config.cluster_config() {
+        cluster_config
+            .workers
+            .iter()
+            .all(|worker| is_64_bit(&worker.target))
+    } else {
+        is_64_bit(&current_target())
+    }
+}
+
+/// Parses the body of an object-like macro consisting of `tokens`.
+/// Only integer, floating point and string literals are supported.
+/// `long_is_64_bit` specifies the size of `long` used for deducing types of integer literals.
+fn parse_macro_value(tokens: &[String], long_is_64_bit: bool) -> Result<CppMacroValue> {
+    let mut tokens = tokens;
+    while tokens.len() > 2 && tokens[0] == "(" && tokens[tokens.len() - 1] == ")" {
+        tokens = &tokens[1..tokens.len() - 1];
+    }
+    if tokens.is_empty() {
+        bail!("macro is empty");
+    }
+    if tokens[0].starts_with('"') {
+        let mut value = String::new();
+        for token in tokens {
+            value.push_str(&parse_string_literal(token)?);
+        }
+        return Ok(CppMacroValue::String(value));
+    }
+    let (is_negative, literal) = match tokens {
+        [literal] => (false, literal),
+        [sign, literal] if sign == "-" => (true, literal),
+        [sign, literal] if sign == "+" => (false, literal),
+        _ => bail!("unsupported macro value: {}", tokens.join(" ")),
+    };
+    let literal = literal.replace('\'', "");
+    let is_hex = literal.starts_with("0x") || literal.starts_with("0X");
+    if literal.contains('.') || (!is_hex && (literal.contains('e') || literal.contains('E'))) {
+        parse_floating_point_literal(&literal, is_negative)
+    } else {
+        parse_integer_literal(&literal, is_negative, long_is_64_bit)
+    }
+}
+
+/// Parses a C++ integer literal and deduces its type
+/// according to the C++ rules.
+fn parse_integer_literal(
+    literal: &str,
+    is_negative: bool,
+    long_is_64_bit: bool,
+) -> Result<CppMacroValue> {
+    use CppBuiltInNumericType::*;
+
+    let lowercase = literal.to_lowercase();
+    let digits_end = lowercase
+        .find(|c: char| c == 'u' || c == 'l')
+        .unwrap_or_else(|| lowercase.len());
+    let (number, suffix) = lowercase.split_at(digits_end);
+    let (radix, digits) = if number.starts_with("0x") {
+        (16, &number[2..])
+    } else if number.starts_with("0b") {
+        (2, &number[2..])
+    } else if number.len() > 1 && number.starts_with('0') {
+        (8, &number[1..])
+    } else {
+        (10, number)
+    };
+    let value = u64::from_str_radix(digits, radix)
+        .with_context(|_| format!("invalid integer literal: {}", literal))?;
+
+    // The type is the first type of the list that can represent the value.
+    // Unsigned types are only used for decimal literals with a `u` suffix.
+    let is_decimal = radix == 10;
+    let candidates: &[CppBuiltInNumericType] = match (suffix, is_decimal) {
+        ("", true) => &[Int, Long, LongLong],
+        ("", false) => &[Int, UInt, Long, ULong, LongLong, ULongLong],
+        ("u", _) => &[UInt, ULong, ULongLong],
+        ("l", true) => &[Long, LongLong],
+        ("l", false) => &[Long, ULong, LongLong, ULongLong],
+        ("ul", _) | ("lu", _) => &[ULong, ULongLong],
+        ("ll", true) => &[LongLong],
+        ("ll", false) => &[LongLong, ULongLong],
+        ("ull", _) | ("llu", _) => &[ULongLong],
+        _ => bail!("invalid integer literal suffix: {}", literal),
+    };
+    let max_value = |value_type: &CppBuiltInNumericType| match *value_type {
+        Int => i32::max_value() as u64,
+        UInt => u32::max_value() as u64,
+        Long if long_is_64_bit => i64::max_value() as u64,
+        Long => i32::max_value() as u64,
+        ULong if long_is_64_bit => u64::max_value(),
+        ULong => u32::max_value() as u64,
+        LongLong => i64::max_value() as u64,
+        _ => u64::max_value(),
+    };
+    let value_type = candidates
+        .iter()
+        .find(|t| value <= max_value(t))
+        .cloned()
+        .ok_or_else(|| format_err!("integer literal is too large: {}", literal))?;
+    if is_negative && !value_type.is_signed_integer() {
+        bail!("negative unsigned integer literal: {}", literal);
+    }
+    Ok(CppMacroValue::Integer {
+        value,
+        is_negative,
+        value_type,
+    })
+}
+
+/// Parses a C++ floating point literal.
+fn parse_floating_point_literal(literal: &str, is_negative: bool) -> Result<CppMacroValue> {
+    let lowercase = literal.to_lowercase();
+    let (number, value_type) = if lowercase.ends_with('f') {
+        (
+            &lowercase[..lowercase.len() - 1],
+            CppBuiltInNumericType::Float,
+        )
+    } else if lowercase.ends_with('l') {
+        (
+            &lowercase[..lowercase.len() - 1],
+            CppBuiltInNumericType::LongDouble,
+        )
+    } else {
+        (&lowercase[..], CppBuiltInNumericType::Double)
+    };
+    let value = f64::from_str(number)
+        .with_context(|_| format!("invalid floating point literal: {}", literal))?;
+    let is_in_range = if value_type == CppBuiltInNumericType::Float {
+        value.abs() <= f64::from(std::f32::MAX)
+    } else {
+        value.is_finite()
+    };
+    if !is_in_range {
+        bail!("floating point literal is out of range: {}", literal);
+    }
+    let sign = if is_negative { "-" } else { "" };
+    Ok(CppMacroValue::FloatingPoint {
+        value: format!("{}{:?}", sign, value),
+        value_type,
+    })
+}
+
+/// Parses a narrow C++ string literal and unescapes its content.
+fn parse_string_literal(literal: &str) -> Result<String> {
+    if literal.len() < 2 || !literal.starts_with('"') || !literal.ends_with('"') {
+        bail!("unsupported string literal: {}", literal);
+    }
+    let mut result = String::new();
+    let mut chars = literal[1..literal.len() - 1].chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        let escaped = chars
+            .next()
+            .ok_or_else(|| format_err!("invalid string literal: {}", literal))?;
+        let code = match escaped {
+            'n' => 0x0a,
+            't' => 0x09,
+            'r' => 0x0d,
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0c,
+            'v' => 0x0b,
+            '\\' | '\'' | '"' | '?' => escaped as u32,
+            'x' => {
+                let mut digits = String::new();
+                while let Some(&d) = chars.peek() {
+                    if !d.is_ascii_hexdigit() {
+                        break;
+                    }
+                    digits.push(d);
+                    chars.next();
+                }
+                u32::from_str_radix(&digits, 16)
+                    .with_context(|_| format!("invalid string literal: {}", literal))?
+            }
+            '0'..='7' => {
+                let mut digits = escaped.to_string();
+                while let Some(&d) = chars.peek() {
+                    if digits.len() == 3 || !('0'..='7').contains(&d) {
+                        break;
+                    }
+                    digits.push(d);
+                    chars.next();
+                }
+                u32::from_str_radix(&digits, 8)
+                    .with_context(|_| format!("invalid string literal: {}", literal))?
+            }
+            _ => bail!("unsupported escape sequence in string literal: {}", literal),
+        };
+        if code > 0x7f {
+            bail!("non-ASCII string literals are not supported: {}", literal);
+        }
+        result.push(code as u8 as char);
+    }
+    Ok(result)
+}
+
+/// Parses value of an integral non-type template argument, like `4` in `std::array<int, 4>`.
+fn parse_integral_template_argument(text: &str) -> Option<i64> {
+    let text = text.trim();
+    match text {
+        "true" => return Some(1),
+        "false" => return Some(0),
+        _ => {}
+    }
+    let digits = text.trim_end_matches(|c: char| c == 'u' || c == 'U' || c == 'l' || c == 'L');
+    digits.parse().ok()
+}
+
+/// Returns type of a class value with the specified path.
+/// Instantiations of `std::function` with a function type are
+/// represented as `CppType::StdFunction`.
+fn class_type(path: CppPath) -> CppType {
+    let items = path.items();
+    if items.len() == 2
+        && items[0].name == "std"
+        && items[0].template_arguments.is_none()
+        && items[1].name == "function"
+    {
+        if let Some(args) = &items[1].template_arguments {
+            if args.len() == 1 {
+                if let CppType::FunctionPointer(function) = &args[0] {
+                    return CppType::StdFunction(function.clone());
+                }
+            }
+        }
+    }
+    CppType::Class(path)
+}
+
 fn parse_template_args(str: &str) -> Option<(String, Vec<String>)> {
     let mut level = 0;
     let mut current_str = String::new();
@@ -1699,3 +2323,141 @@ fn should_parse_template_args_works() {
     assert_eq!(parse_template_args("name<arg,arg2"), None);
     assert_eq!(parse_template_args("name<arg<arg3,arg4>,arg2"), None);
 }
+
+#[test]
+fn class_type_works() {
+    let function = CppFunctionPointerType {
+        return_type: Box::new(CppType::Void),
+        arguments: vec![CppType::BuiltInNumeric(CppBuiltInNumericType::Int)],
+        allows_variadic_arguments: false,
+    };
+    let std_function_path = CppPath::from_items(vec![
+        CppPathItem::from_good_str("std"),
+        CppPathItem {
+            name: "function".into(),
+            template_arguments: Some(vec![CppType::FunctionPointer(function.clone())]),
+        },
+    ]);
+    assert_eq!(
+        class_type(std_function_path),
+        CppType::StdFunction(function)
+    );
+
+    let vector_path = CppPath::from_items(vec![
+        CppPathItem::from_good_str("std"),
+        CppPathItem {
+            name: "vector".into(),
+            template_arguments: Some(vec![CppType::BuiltInNumeric(CppBuiltInNumericType::Int)]),
+        },
+    ]);
+    assert_eq!(class_type(vector_path.clone()), CppType::Class(vector_path));
+}
+
+#[test]
+fn parse_macro_value_works() {
+    fn parse_with_long_size(tokens: &[&str], long_is_64_bit: bool) -> Option<CppMacroValue> {
+        let tokens = tokens.iter().map(|s| s.to_string()).collect_vec();
+        parse_macro_value(&tokens, long_is_64_bit).ok()
+    }
+    fn parse(tokens: &[&str]) -> Option<CppMacroValue> {
+        parse_with_long_size(tokens, true)
+    }
+    fn int(value: u64, is_negative: bool, value_type: CppBuiltInNumericType) -> CppMacroValue {
+        CppMacroValue::Integer {
+            value,
+            is_negative,
+            value_type,
+        }
+    }
+    use CppBuiltInNumericType::*;
+
+    assert_eq!(parse(&["42"]), Some(int(42, false, Int)));
+    assert_eq!(parse(&["(", "-", "1", ")"]), Some(int(1, true, Int)));
+    assert_eq!(parse(&["0x10"]), Some(int(16, false, Int)));
+    assert_eq!(parse(&["0xFFFFFFFF"]), Some(int(0xFFFF_FFFF, false, UInt)));
+    assert_eq!(parse(&["4294967295"]), Some(int(0xFFFF_FFFF, false, Long)));
+    assert_eq!(
+        parse_with_long_size(&["4294967295"], false),
+        Some(int(0xFFFF_FFFF, false, LongLong))
+    );
+    assert_eq!(parse(&["4294967295l"]), Some(int(0xFFFF_FFFF, false, Long)));
+    assert_eq!(
+        parse_with_long_size(&["0xFFFFFFFFl"], false),
+        Some(int(0xFFFF_FFFF, false, ULong))
+    );
+    assert_eq!(
+        parse(&["0xFFFFFFFFFFFFFFFF"]),
+        Some(int(u64::max_value(), false, ULong))
+    );
+    assert_eq!(parse(&["18446744073709551615"]), None);
+    assert_eq!(parse(&["010"]), Some(int(8, false, Int)));
+    assert_eq!(parse(&["0b101"]), Some(int(5, false, Int)));
+    assert_eq!(parse(&["1'000"]), Some(int(1000, false, Int)));
+    assert_eq!(parse(&["1u"]), Some(int(1, false, UInt)));
+    assert_eq!(parse(&["1UL"]), Some(int(1, false, ULong)));
+    assert_eq!(parse(&["1L"]), Some(int(1, false, Long)));
+    assert_eq!(parse(&["1ll"]), Some(int(1, false, LongLong)));
+    assert_eq!(parse(&["1ULL"]), Some(int(1, false, ULongLong)));
+    assert_eq!(parse(&["-", "1u"]), None);
+
+    assert_eq!(
+        parse(&["1.5f"]),
+        Some(CppMacroValue::FloatingPoint {
+            value: "1.5".to_string(),
+            value_type: Float,
+        })
+    );
+    assert_eq!(parse(&["1e40f"]), None);
+    assert_eq!(parse(&["1e400"]), None);
+    assert_eq!(
+        parse(&["-", "1e3"]),
+        Some(CppMacroValue::FloatingPoint {
+            value: "-1000.0".to_string(),
+            value_type: Double,
+        })
+    );
+
+    assert_eq!(
+        parse(&[r#""a\tb""#, r#""\x41\101""#]),
+        Some(CppMacroValue::String("a\tbAA".to_string()))
+    );
+    assert_eq!(parse(&["L\"a\""]), None);
+    assert_eq!(parse(&["A", "|", "B"]), None);
+    assert_eq!(parse(&[]), None);
+}
+
+#[test]
+fn parse_nullability_works() {
+    fn tokens(text: &str) -> Vec<String> {
+        text.split(' ').map(|s| s.to_string()).collect()
+    }
+    assert_eq!(
+        parse_nullability(&tokens("QObject * _Nonnull parent")),
+        Some(CppNullability::NonNull)
+    );
+    assert_eq!(
+        parse_nullability(&tokens("const char * _Nullable")),
+        Some(CppNullability::Nullable)
+    );
+    assert_eq!(parse_nullability(&tokens("QObject * parent")), None);
+}
+
+#[test]
+fn parse_deprecation_message_works() {
+    fn tokens(text: &str) -> Vec<String> {
+        text.split(' ').map(|s| s.to_string()).collect()
+    }
+    assert_eq!(parse_deprecation_message(&tokens("deprecated")), None);
+    assert_eq!(
+        parse_deprecation_message(&tokens(r#"deprecated ( "use f2" )"#)),
+        Some("use f2".to_string())
+    );
+    assert_eq!(
+        parse_deprecation_message(&tokens(r#"Q_DECL_DEPRECATED_X ( "use " "f2" )"#)),
+        Some("use f2".to_string())
+    );
+    assert_eq!(
+        parse_deprecation_message(&tokens(r#"nodiscard ( "x" )"#)),
+        None
+    );
+}
@@ -0,0 +1,251 @@
+use crate::cpp_data::{CppItem, CppPath, CppPathItem};
+use crate::cpp_function::CppFunction;
+use crate::cpp_type::{CppPointerLikeTypeKind, CppType};
+use crate::database::ItemWithSource;
+use crate::processor::ProcessorData;
+use log::trace;
+use ritual_common::errors::Result;
+use std::collections::HashSet;
+
+/// Name of the helper function template that returns functions operating on
+/// `std::shared_ptr<T>` and `std::weak_ptr<T>` objects.
+/// The template is declared in `global.h` of the C++ wrapper library.
+const SHARED_PTR_FUNCTIONS_NAME: &str = "shared_ptr_functions";
+/// Name of the helper function template that returns functions operating on
+/// `std::unique_ptr<T>` objects.
+/// The template is declared in `global.h` of the C++ wrapper library.
+const UNIQUE_PTR_FUNCTIONS_NAME: &str = "unique_ptr_functions";
+
+/// Standard smart pointer template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SmartPointerKind {
+    /// `std::shared_ptr<T>`
+    Shared,
+    /// `std::weak_ptr<T>`
+    Weak,
+    /// `std::unique_ptr<T>` with the default deleter
+    Unique,
+}
+
+impl SmartPointerKind {
+    /// Returns path to the Rust type that owns the smart pointer.
+    pub fn rust_type_path(self) -> &'static str {
+        match self {
+            SmartPointerKind::Shared => "cpp_core::SharedPtr",
+            SmartPointerKind::Weak => "cpp_core::WeakPtr",
+            SmartPointerKind::Unique => "cpp_core::UniquePtr",
+        }
+    }
+
+    /// Returns name of the helper function template for this kind of pointers.
+    /// `std::weak_ptr<T>` uses the same helper as `std::shared_ptr<T>`.
+    fn helper_name(self) -> &'static str {
+        match self {
+            SmartPointerKind::Shared | SmartPointerKind::Weak => SHARED_PTR_FUNCTIONS_NAME,
+            SmartPointerKind::Unique => UNIQUE_PTR_FUNCTIONS_NAME,
+        }
+    }
+}
+
+/// Returns the smart pointer kind and the pointee class if `path` is
+/// an instantiation of `std::shared_ptr`, `std::weak_ptr` or `std::unique_ptr`
+/// (with the default deleter) with a class type.
+pub fn smart_pointer_target(path: &CppPath) -> Option<(SmartPointerKind, &CppPath)> {
+    let items = path.items();
+    if items.len() != 2 || items[0].name != "std" || items[0].template_arguments.is_some() {
+        return None;
+    }
+    let kind = match items[1].name.as_str() {
+        "shared_ptr" => SmartPointerKind::Shared,
+        "weak_ptr" => SmartPointerKind::Weak,
+        "unique_ptr" => SmartPointerKind::Unique,
+        _ => return None,
+    };
+    let args = items[1].template_arguments.as_ref()?;
+    let target = match args.get(0) {
+        Some(CppType::Class(target)) if !args[0].is_or_contains_template_parameter() => target,
+        _ => return None,
+    };
+    if kind == SmartPointerKind::Unique && args.len() == 2 {
+        let default_delete = CppType::Class(CppPath::from_items(vec![
+            CppPathItem::from_good_str("std"),
+            CppPathItem {
+                name: "default_delete".into(),
+                template_arguments: Some(vec![args[0].clone()]),
+            },
+        ]));
+        if args[1] != default_delete {
+            return None;
+        }
+    } else if args.len() != 1 {
+        return None;
+    }
+    Some((kind, target))
+}
+
+/// Returns the smart pointer kind and the pointee class if `type1` is a smart pointer
+/// value or a reference to it.
+fn smart_pointer_type_target(type1: &CppType) -> Option<(SmartPointerKind, &CppPath)> {
+    let value_type = match type1 {
+        CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::Reference,
+            target,
+            ..
+        }
+        | CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::RValueReference,
+            target,
+            ..
+        } => target,
+        other => other,
+    };
+    if let CppType::Class(path) = value_type {
+        smart_pointer_target(path)
+    } else {
+        None
+    }
+}
+
+/// Creates `ritual::shared_ptr_functions<T>` or `ritual::unique_ptr_functions<T>`
+/// function that returns the table of functions operating on smart pointers to `class_path`.
+pub fn create_smart_pointer_functions(kind: SmartPointerKind, class_path: &CppPath) -> CppFunction {
+    CppFunction {
+        path: CppPath::from_items(vec![
+            CppPathItem::from_good_str("ritual"),
+            CppPathItem {
+                name: kind.helper_name().into(),
+                template_arguments: Some(vec![CppType::Class(class_path.clone())]),
+            },
+        ]),
+        member: None,
+        operator: None,
+        return_type: CppType::new_pointer(true, CppType::Void),
+        arguments: Vec::new(),
+        allows_variadic_arguments: false,
+        declaration_code: None,
+        deprecation: None,
+        cast: None,
+        return_nullability: None,
+    }
+}
+
+/// Returns the smart pointer kind and the pointee class if `function` is
+/// one of the helper functions added by this processing step.
+/// `SmartPointerKind::Shared` is returned for the helper that also serves
+/// `std::weak_ptr<T>`.
+pub fn smart_pointer_functions_target(
+    function: &CppFunction,
+) -> Option<(SmartPointerKind, &CppPath)> {
+    let items = function.path.items();
+    if items.len() != 2 || items[0].name != "ritual" {
+        return None;
+    }
+    let kind = if items[1].name == SHARED_PTR_FUNCTIONS_NAME {
+        SmartPointerKind::Shared
+    } else if items[1].name == UNIQUE_PTR_FUNCTIONS_NAME {
+        SmartPointerKind::Unique
+    } else {
+        return None;
+    };
+    match items[1].template_arguments.as_ref().map(|args| &args[..]) {
+        Some([CppType::Class(path)]) => Some((kind, path)),
+        _ => None,
+    }
+}
+
+/// Adds `ritual::shared_ptr_functions<T>` and `ritual::unique_ptr_functions<T>` functions
+/// for all classes of the current crate that are used in `std::shared_ptr<T>`,
+/// `std::weak_ptr<T>` or `std::unique_ptr<T>` in the API. These functions are used
+/// to implement `cpp_core::SharedPtrTarget` and `cpp_core::UniquePtrTarget`
+/// for the corresponding Rust types.
+pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
+    let current_crate_classes = data
+        .db
+        .cpp_items()
+        .filter_map(|item| item.item.as_type_ref())
+        .filter(|t| t.kind.is_class())
+        .map(|t| &t.path)
+        .collect::<HashSet<_>>();
+    let mut results = Vec::new();
+    for item in data.db.cpp_items() {
+        for type1 in item.item.all_involved_types() {
+            let (kind, class_path) = if let Some(target) = smart_pointer_type_target(&type1) {
+                target
+            } else {
+                continue;
+            };
+            if !current_crate_classes.contains(class_path) {
+                continue;
+            }
+            let new_function = create_smart_pointer_functions(kind, class_path);
+            results.push(ItemWithSource::new(
+                &item.id,
+                CppItem::Function(new_function),
+            ));
+        }
+    }
+    for item in results {
+        // The helper is a part of the wrapper library, so
+        // the hooks that filter items of the C++ library are not applied.
+        if let Some(id) = data
+            .db
+            .add_cpp_item_without_hook(Some(item.source_id), item.item)?
+        {
+            trace!("added smart pointer functions: {}", id);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn smart_pointer_target_works() {
+    let point = CppType::Class(CppPath::from_good_str("ns::Point"));
+    let std_path = |name: &str, args: Vec<CppType>| {
+        CppPath::from_items(vec![
+            CppPathItem::from_good_str("std"),
+            CppPathItem {
+                name: name.into(),
+                template_arguments: Some(args),
+            },
+        ])
+    };
+    let point_path = CppPath::from_good_str("ns::Point");
+
+    assert_eq!(
+        smart_pointer_target(&std_path("shared_ptr", vec![point.clone()])),
+        Some((SmartPointerKind::Shared, &point_path))
+    );
+    assert_eq!(
+        smart_pointer_target(&std_path("weak_ptr", vec![point.clone()])),
+        Some((SmartPointerKind::Weak, &point_path))
+    );
+    let default_delete = CppType::Class(std_path("default_delete", vec![point.clone()]));
+    assert_eq!(
+        smart_pointer_target(&std_path("unique_ptr", vec![point.clone(), default_delete])),
+        Some((SmartPointerKind::Unique, &point_path))
+    );
+
+    // custom deleters are not supported
+    let deleter = CppType::Class(CppPath::from_good_str("ns::PointDeleter"));
+    assert_eq!(
+        smart_pointer_target(&std_path("unique_ptr", vec![point.clone(), deleter])),
+        None
+    );
+    // pointers to non-class types are not supported
+    let int = CppType::BuiltInNumeric(crate::cpp_type::CppBuiltInNumericType::Int);
+    assert_eq!(
+        smart_pointer_target(&std_path("shared_ptr", vec![int])),
+        None
+    );
+
+    let helper = create_smart_pointer_functions(SmartPointerKind::Weak, &point_path);
+    assert_eq!(
+        smart_pointer_functions_target(&helper),
+        Some((SmartPointerKind::Shared, &point_path))
+    );
+    let helper = create_smart_pointer_functions(SmartPointerKind::Unique, &point_path);
+    assert_eq!(
+        smart_pointer_functions_target(&helper),
+        Some((SmartPointerKind::Unique, &point_path))
+    );
+}
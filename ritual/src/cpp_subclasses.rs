@@ -0,0 +1,355 @@
+use crate::cpp_data::{inherits, CppPath, CppPathItem, CppTypeDeclaration};
+use crate::cpp_ffi_data::{
+    CppFfiItem, CppSubclassMethod, CppSubclassWrapper, CppToFfiTypeConversion,
+};
+use crate::cpp_ffi_generator::ffi_type;
+use crate::cpp_function::CppFunction;
+use crate::cpp_type::{CppFunctionPointerType, CppType, CppTypeRole};
+use crate::database::{DatabaseClient, ItemId};
+use crate::processor::ProcessorData;
+use itertools::Itertools;
+use log::trace;
+use ritual_common::errors::{bail, Result};
+use ritual_common::utils::MapIfOk;
+use std::collections::HashSet;
+use std::iter::once;
+
+/// Returns true if `function` is a variant of another function
+/// produced by omitting arguments with default values.
+fn is_omitted_arguments_variant(
+    db: &DatabaseClient,
+    id: &ItemId,
+    function: &CppFunction,
+) -> Result<bool> {
+    let original = db.original_cpp_item(id)?;
+    Ok(original
+        .and_then(|item| item.item.as_function_ref())
+        .map_or(false, |original| {
+            original.arguments.len() != function.arguments.len()
+        }))
+}
+
+/// Returns true if `a` and `b` have the same name and signature,
+/// so that one overrides the other.
+fn is_same_signature(a: &CppFunction, b: &CppFunction) -> bool {
+    a.path.last().name == b.path.last().name
+        && a.arguments.len() == b.arguments.len()
+        && a.arguments
+            .iter()
+            .zip(&b.arguments)
+            .all(|(a, b)| a.argument_type == b.argument_type)
+        && a.member().map(|m| m.is_const) == b.member().map(|m| m.is_const)
+}
+
+/// Returns virtual methods of `class_path` and its bases that can be overridden.
+/// If a method is overridden in a derived class, only the most derived
+/// declaration is returned.
+fn overridable_methods(db: &DatabaseClient, class_path: &CppPath) -> Result<Vec<CppFunction>> {
+    let mut candidates = Vec::new();
+    for item in db.all_cpp_items() {
+        let function = if let Some(f) = item.item.as_function_ref() {
+            f
+        } else {
+            continue;
+        };
+        let is_regular = function.member().map_or(false, |m| m.kind.is_regular());
+        if !function.is_virtual()
+            || !is_regular
+            || function.is_private()
+            || function.is_operator()
+            || function.path.last().template_arguments.is_some()
+        {
+            continue;
+        }
+        if !inherits(db, class_path, &function.class_path()?) {
+            continue;
+        }
+        if is_omitted_arguments_variant(db, &item.id, function)? {
+            continue;
+        }
+        candidates.push(function.clone());
+    }
+
+    let mut result = Vec::new();
+    for function in &candidates {
+        let function_class = function.class_path()?;
+        let is_overridden = candidates.iter().any(|other| {
+            let other_class = other
+                .class_path()
+                .expect("candidates must be class members");
+            other_class != function_class
+                && is_same_signature(function, other)
+                && inherits(db, &other_class, &function_class)
+        });
+        if !is_overridden && !result.iter().any(|f| is_same_signature(function, f)) {
+            result.push(function.clone());
+        }
+    }
+    Ok(result)
+}
+
+/// Returns constructors of `class_path` that should be available in the subclass.
+/// Copy and move constructors are excluded.
+fn subclass_constructors(db: &DatabaseClient, class_path: &CppPath) -> Result<Vec<CppFunction>> {
+    let class_type = CppType::Class(class_path.clone());
+    let mut result = Vec::new();
+    for function in db
+        .all_cpp_items()
+        .filter_map(|item| item.item.as_function_ref())
+    {
+        if !function.is_constructor()
+            || function.is_private()
+            || function.class_path()? != *class_path
+            || function.path.last().template_arguments.is_some()
+        {
+            continue;
+        }
+        let is_copy_or_move = function.arguments.len() == 1
+            && function.arguments[0]
+                .argument_type
+                .pointer_like_to_target()
+                .ok()
+                == Some(&class_type);
+        if !is_copy_or_move && !result.iter().any(|f| f == function) {
+            result.push(function.clone());
+        }
+    }
+    Ok(result)
+}
+
+/// Generates information about a callback forwarding `function` to Rust.
+fn subclass_method(function: &CppFunction, name: String) -> Result<CppSubclassMethod> {
+    let arguments = function
+        .arguments
+        .iter()
+        .map_if_ok(|arg| ffi_type(&arg.argument_type, CppTypeRole::NotReturnType))?;
+    let return_type = ffi_type(&function.return_type, CppTypeRole::ReturnType)?;
+    for t in arguments.iter().chain(once(&return_type)) {
+        match t.conversion() {
+            CppToFfiTypeConversion::ValueToPointer { .. }
+            | CppToFfiTypeConversion::RValueReferenceToPointer => bail!(
+                "passing class values through callbacks is not supported: {}",
+                t.original_type().to_cpp_pseudo_code()
+            ),
+            _ => {}
+        }
+    }
+
+    let void_ptr = CppType::new_pointer(false, CppType::Void);
+    let function_type = CppFunctionPointerType {
+        return_type: Box::new(return_type.ffi_type().clone()),
+        arguments: once(void_ptr)
+            .chain(arguments.iter().map(|t| t.ffi_type().clone()))
+            .collect(),
+        allows_variadic_arguments: false,
+    };
+
+    Ok(CppSubclassMethod {
+        function: function.clone(),
+        name,
+        arguments,
+        return_type,
+        function_type,
+    })
+}
+
+/// Generates a subclass wrapper for `class`.
+fn generate_subclass_wrapper(
+    db: &DatabaseClient,
+    class: &CppTypeDeclaration,
+) -> Result<CppSubclassWrapper> {
+    let parent_path = &class.path.items()[..class.path.items().len() - 1];
+    if class
+        .path
+        .items()
+        .iter()
+        .any(|i| i.template_arguments.is_some())
+    {
+        bail!("template classes are not supported");
+    }
+    let is_nested = db
+        .all_cpp_items()
+        .filter_map(|item| item.item.as_type_ref())
+        .any(|t| t.path.items() == parent_path);
+    if is_nested {
+        bail!("nested classes are not supported");
+    }
+
+    let constructors = subclass_constructors(db, &class.path)?;
+    if constructors.is_empty() {
+        bail!("no accessible constructors");
+    }
+
+    let mut methods = Vec::new();
+    let mut names = HashSet::new();
+    for function in overridable_methods(db, &class.path)? {
+        let base_name = &function.path.last().name;
+        let mut num: Option<u32> = None;
+        let name = loop {
+            let name = format!(
+                "{}{}",
+                base_name,
+                num.map_or(String::new(), |n| n.to_string())
+            );
+            if !names.contains(&name) {
+                break name;
+            }
+            num = Some(num.map_or(1, |n| n + 1));
+        };
+        match subclass_method(&function, name.clone()) {
+            Ok(method) => {
+                names.insert(name);
+                methods.push(method);
+            }
+            Err(err) => {
+                if function.member().map_or(false, |m| m.is_pure_virtual) {
+                    bail!(
+                        "can't override pure virtual method {}: {}",
+                        function.short_text(),
+                        err
+                    );
+                }
+                trace!("can't override {}: {}", function.short_text(), err);
+            }
+        }
+    }
+    if methods.is_empty() {
+        bail!("no virtual methods to override");
+    }
+
+    let mut num: Option<u32> = None;
+    let class_path = loop {
+        let name = format!(
+            "{}Subclass{}",
+            class.path.last().name,
+            num.map_or(String::new(), |n| n.to_string())
+        );
+        let path = CppPath::from_items(
+            parent_path
+                .iter()
+                .cloned()
+                .chain(once(CppPathItem::from_good_str(&name)))
+                .collect_vec(),
+        );
+        let exists = db
+            .all_cpp_items()
+            .filter_map(|item| item.item.as_type_ref())
+            .any(|t| t.path == path);
+        if !exists {
+            break path;
+        }
+        num = Some(num.map_or(1, |n| n + 1));
+    };
+
+    Ok(CppSubclassWrapper {
+        base_class_path: class.path.clone(),
+        class_path,
+        constructors,
+        methods,
+    })
+}
+
+/// Generates subclass wrappers for classes selected by `Config::set_cpp_subclassing_hook`.
+pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
+    let hook = if let Some(hook) = data.config.cpp_subclassing_hook() {
+        hook
+    } else {
+        return Ok(());
+    };
+
+    let mut wrappers = Vec::new();
+    for item in data.db.cpp_items() {
+        let class = if let Some(t) = item.item.as_type_ref() {
+            t
+        } else {
+            continue;
+        };
+        if !class.kind.is_class() {
+            continue;
+        }
+        if data.db.source_ffi_item(&item.id)?.is_some() {
+            // don't subclass generated classes
+            continue;
+        }
+        let exists = data
+            .db
+            .ffi_items()
+            .filter_map(|item| item.item.as_subclass_wrapper_ref())
+            .any(|wrapper| wrapper.base_class_path == class.path);
+        if exists || !hook(&class.path)? {
+            continue;
+        }
+        match generate_subclass_wrapper(&data.db, class) {
+            Ok(wrapper) => wrappers.push(wrapper),
+            Err(err) => trace!(
+                "failed to add subclass wrapper for {}: {}",
+                class.path.to_cpp_pseudo_code(),
+                err
+            ),
+        }
+    }
+
+    for wrapper in wrappers {
+        let text = wrapper.base_class_path.to_cpp_pseudo_code();
+        let id = data
+            .db
+            .add_ffi_item(None, CppFfiItem::SubclassWrapper(wrapper))?;
+        if id.is_some() {
+            trace!("adding subclass wrapper for {}", text);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn subclass_method_callback_type() {
+    use crate::cpp_data::CppVisibility;
+    use crate::cpp_function::{CppFunctionArgument, CppFunctionKind, CppFunctionMemberData};
+    use crate::cpp_type::CppBuiltInNumericType;
+
+    let rect = CppType::Class(CppPath::from_good_str("Rect"));
+    let mut function = CppFunction {
+        path: CppPath::from_good_str("Widget::heightForRect"),
+        member: Some(CppFunctionMemberData {
+            kind: CppFunctionKind::Regular,
+            is_virtual: true,
+            is_pure_virtual: false,
+            is_const: true,
+            is_static: false,
+            visibility: CppVisibility::Protected,
+            is_signal: false,
+            is_slot: false,
+        }),
+        operator: None,
+        return_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+        arguments: vec![CppFunctionArgument {
+            name: "rect".to_string(),
+            argument_type: CppType::new_reference(true, rect.clone()),
+            has_default_value: false,
+            nullability: None,
+        }],
+        allows_variadic_arguments: false,
+        declaration_code: None,
+        deprecation: None,
+        cast: None,
+        return_nullability: None,
+    };
+    let method = subclass_method(&function, "heightForRect".to_string()).unwrap();
+    assert_eq!(method.setter_name(), "set_heightForRect_callback");
+    assert_eq!(method.base_caller_name(), "base_heightForRect");
+    assert_eq!(
+        method.function_type,
+        CppFunctionPointerType {
+            return_type: Box::new(CppType::BuiltInNumeric(CppBuiltInNumericType::Int)),
+            arguments: vec![
+                CppType::new_pointer(false, CppType::Void),
+                CppType::new_pointer(true, rect.clone()),
+            ],
+            allows_variadic_arguments: false,
+        }
+    );
+
+    // class values can't be returned from callbacks
+    function.return_type = rect;
+    assert!(subclass_method(&function, "heightForRect".to_string()).is_err());
+}
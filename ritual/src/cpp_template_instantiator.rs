@@ -1,4 +1,4 @@
-use crate::cpp_data::{CppItem, CppPath, CppPathItem, CppTypeDeclaration};
+use crate::cpp_data::{CppItem, CppPath, CppPathItem, CppTypeAlias, CppTypeDeclaration};
 use crate::cpp_function::{CppFunction, CppFunctionArgument, CppOperator};
 use crate::cpp_type::CppType;
 use crate::database::{DatabaseClient, ItemWithSource};
@@ -48,6 +48,7 @@ pub fn instantiate_function(
         new_method.arguments.push(CppFunctionArgument {
             name: arg.name.clone(),
             has_default_value: arg.has_default_value,
+            nullability: arg.nullability,
             argument_type: arg.argument_type.instantiate(nested_level, arguments)?,
         });
     }
@@ -182,6 +183,7 @@ fn instantiate_types(data: &mut ProcessorData<'_>) -> Result<()> {
                         .item
                         .path
                         .instantiate(substitution.nested_level, substitution.arguments)?,
+                    deprecation: type1.item.deprecation.clone(),
                 };
 
                 if data
@@ -275,11 +277,80 @@ fn instantiate_functions(data: &mut ProcessorData<'_>) -> Result<()> {
     Ok(())
 }
 
+/// Attempts to replace template parameters at `nested_level`
+/// within `alias` with `arguments`.
+fn instantiate_type_alias(
+    alias: &CppTypeAlias,
+    nested_level: usize,
+    arguments: &[CppType],
+) -> Result<CppTypeAlias> {
+    let new_alias = CppTypeAlias {
+        path: alias.path.instantiate(nested_level, arguments)?,
+        target_type: alias.target_type.instantiate(nested_level, arguments)?,
+    };
+    if new_alias.target_type.is_or_contains_template_parameter() {
+        bail!("extra template parameters left: {}", new_alias.short_text());
+    }
+    Ok(new_alias)
+}
+
+fn instantiate_type_aliases(data: &mut ProcessorData<'_>) -> Result<()> {
+    let mut new_aliases = Vec::<ItemWithSource<_>>::new();
+    for item in data.db.all_cpp_items() {
+        let alias = if let Some(alias) = item.item.as_type_alias_ref() {
+            alias
+        } else {
+            continue;
+        };
+
+        for substitution in find_suitable_template_arguments(&alias.path, data.db)? {
+            trace!("type alias: {}", alias.short_text());
+            trace!("found template instantiation: {:?}", substitution);
+
+            let new_alias = match instantiate_type_alias(
+                alias,
+                substitution.nested_level,
+                substitution.arguments,
+            ) {
+                Ok(new_alias) => new_alias,
+                Err(msg) => {
+                    trace!("failed: {}", msg);
+                    continue;
+                }
+            };
+            if let Err(msg) = check_template_type(&data, &new_alias.target_type) {
+                trace!("type alias is not accepted: {}", new_alias.short_text());
+                trace!("  {}", msg);
+                continue;
+            }
+            if data
+                .db
+                .all_cpp_items()
+                .filter_map(|item| item.item.as_type_alias_ref())
+                .any(|item| item.is_same(&new_alias))
+                || new_aliases.iter().any(|item| item.item == new_alias)
+            {
+                trace!("this type alias already exists");
+                continue;
+            }
+            new_aliases.push(ItemWithSource::new(&item.id, new_alias));
+        }
+    }
+    for new_alias in new_aliases {
+        data.add_cpp_item(
+            Some(new_alias.source_id),
+            CppItem::TypeAlias(new_alias.item),
+        )?;
+    }
+    Ok(())
+}
+
 /// Generates methods as template instantiations of
 /// methods of existing template classes and existing template methods.
 pub fn instantiate_templates(data: &mut ProcessorData<'_>) -> Result<()> {
     instantiate_types(data)?;
     instantiate_functions(data)?;
+    instantiate_type_aliases(data)?;
     Ok(())
 }
 
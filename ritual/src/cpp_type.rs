@@ -113,8 +113,14 @@ pub enum CppType {
     /// Template parameter, like `"T"` anywhere inside
     /// `QVector<T>` declaration
     TemplateParameter(CppTemplateParameter),
+    /// Value of an integral non-type template argument,
+    /// like `4` in `std::array<int, 4>`
+    IntegralConstant(i64),
     /// Function pointer type
     FunctionPointer(CppFunctionPointerType),
+    /// Instantiation of `std::function` with a function type,
+    /// like `std::function<void(int)>`
+    StdFunction(CppFunctionPointerType),
     PointerLike {
         kind: CppPointerLikeTypeKind,
         is_const: bool,
@@ -250,13 +256,21 @@ impl CppType {
         }
     }
 
+    /// Returns true if this is an rvalue reference type.
+    pub fn is_rvalue_reference(&self) -> bool {
+        match self {
+            CppType::PointerLike { kind, .. } => *kind == CppPointerLikeTypeKind::RValueReference,
+            _ => false,
+        }
+    }
+
     /// Returns true if this is a template parameter or a type that
     /// contains any template parameters.
     pub fn is_or_contains_template_parameter(&self) -> bool {
         match self {
             CppType::TemplateParameter { .. } => true,
             CppType::PointerLike { target, .. } => target.is_or_contains_template_parameter(),
-            CppType::FunctionPointer(type1) => {
+            CppType::FunctionPointer(type1) | CppType::StdFunction(type1) => {
                 type1.return_type.is_or_contains_template_parameter()
                     || type1
                         .arguments
@@ -282,7 +296,7 @@ impl CppType {
                 self_params.nested_level == param.nested_level && self_params.index == param.index
             }
             CppType::PointerLike { target, .. } => target.contains_template_parameter(param),
-            CppType::FunctionPointer(type1) => {
+            CppType::FunctionPointer(type1) | CppType::StdFunction(type1) => {
                 type1.return_type.contains_template_parameter(param)
                     || type1
                         .arguments
@@ -317,6 +331,7 @@ impl CppType {
             CppType::TemplateParameter { .. } => {
                 bail!("template parameters are not allowed in C++ code generator");
             }
+            CppType::IntegralConstant(value) => Ok(value.to_string()),
             CppType::FunctionPointer(CppFunctionPointerType {
                 return_type,
                 arguments,
@@ -340,6 +355,24 @@ impl CppType {
                     bail!("function_pointer_inner_text argument is missing");
                 }
             }
+            CppType::StdFunction(CppFunctionPointerType {
+                return_type,
+                arguments,
+                allows_variadic_arguments,
+            }) => {
+                if *allows_variadic_arguments {
+                    bail!("function types with variadic arguments are not supported");
+                }
+                let mut arg_texts = Vec::new();
+                for arg in arguments {
+                    arg_texts.push(arg.to_cpp_code(None)?);
+                }
+                Ok(format!(
+                    "std::function< {}({}) >",
+                    return_type.to_cpp_code(None)?,
+                    arg_texts.join(", ")
+                ))
+            }
             CppType::PointerLike {
                 kind,
                 is_const,
@@ -401,7 +434,15 @@ impl CppType {
             | CppType::Enum { path }
             | CppType::Class(path) => path.ascii_caption(),
             CppType::TemplateParameter(param) => param.name.to_string(),
+            CppType::IntegralConstant(value) => {
+                if *value < 0 {
+                    format!("minus_{}", -i128::from(*value))
+                } else {
+                    value.to_string()
+                }
+            }
             CppType::FunctionPointer(_) => "fn".into(),
+            CppType::StdFunction(_) => "std_function".into(),
             CppType::PointerLike {
                 kind,
                 is_const,
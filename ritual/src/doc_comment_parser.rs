@@ -0,0 +1,198 @@
+use crate::database::DocItem;
+
+/// Contents of a Doxygen-style comment split into sections.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DocComment {
+    brief: Vec<String>,
+    details: Vec<Vec<String>>,
+    params: Vec<(String, Vec<String>)>,
+    returns: Vec<String>,
+}
+
+/// Section of the comment the following text belongs to.
+enum Section {
+    Brief,
+    Details,
+    Param,
+    Returns,
+}
+
+/// Removes comment markers (`/**`, `///`, `*/`, leading `*`, etc.) from a raw comment.
+fn strip_comment_markers(comment: &str) -> Vec<String> {
+    comment
+        .lines()
+        .map(|line| {
+            let mut line = line.trim();
+            for prefix in &[
+                "/**<", "/*!<", "///<", "//!<", "/**", "/*!", "/*", "///", "//!", "//",
+            ] {
+                if line.starts_with(prefix) {
+                    line = &line[prefix.len()..];
+                    break;
+                }
+            }
+            if line.ends_with("*/") {
+                line = &line[..line.len() - 2];
+            }
+            let line = line.trim();
+            let line = if line.starts_with('*') {
+                &line[1..]
+            } else {
+                line
+            };
+            line.trim().to_string()
+        })
+        .collect()
+}
+
+/// Splits a raw Doxygen comment into sections.
+fn parse_sections(comment: &str) -> DocComment {
+    let mut output = DocComment::default();
+    let mut section = Section::Details;
+    let mut paragraph = Vec::new();
+    for line in strip_comment_markers(comment) {
+        if line.is_empty() {
+            if let Section::Details = section {
+                if !paragraph.is_empty() {
+                    output.details.push(paragraph);
+                    paragraph = Vec::new();
+                }
+            } else {
+                // A blank line ends `\brief`, `\param` and `\return` sections.
+                section = Section::Details;
+            }
+            continue;
+        }
+        let mut text = line.as_str();
+        if text.starts_with('\\') || text.starts_with('@') {
+            let command_end = text.find(char::is_whitespace).unwrap_or_else(|| text.len());
+            let command = &text[1..command_end];
+            let rest = text[command_end..].trim_start();
+            let new_section = match command {
+                "brief" | "short" => Some(Section::Brief),
+                "details" => Some(Section::Details),
+                "param" | "param[in]" | "param[out]" | "param[in,out]" => Some(Section::Param),
+                "return" | "returns" | "result" => Some(Section::Returns),
+                _ => None,
+            };
+            if let Some(new_section) = new_section {
+                if !paragraph.is_empty() {
+                    output.details.push(paragraph);
+                    paragraph = Vec::new();
+                }
+                text = rest;
+                if let Section::Param = new_section {
+                    let name_end = rest.find(char::is_whitespace).unwrap_or_else(|| rest.len());
+                    output
+                        .params
+                        .push((rest[..name_end].to_string(), Vec::new()));
+                    text = rest[name_end..].trim_start();
+                }
+                section = new_section;
+            }
+        }
+        if text.is_empty() {
+            continue;
+        }
+        let text = text.to_string();
+        match section {
+            Section::Brief => output.brief.push(text),
+            Section::Details => paragraph.push(text),
+            Section::Param => output
+                .params
+                .last_mut()
+                .expect("param section must have a param")
+                .1
+                .push(text),
+            Section::Returns => output.returns.push(text),
+        }
+    }
+    if !paragraph.is_empty() {
+        output.details.push(paragraph);
+    }
+    output
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn join_lines(lines: &[String]) -> String {
+    escape_html(&lines.join(" "))
+}
+
+/// Converts a raw Doxygen comment (as returned by `clang`) to a documentation item.
+/// `\brief`, `\param` and `\return` commands are recognized, other text is
+/// converted to plain paragraphs. Returns `None` if the comment is empty.
+pub fn parse_doc_comment(comment: &str) -> Option<DocItem> {
+    let comment = parse_sections(comment);
+    let mut html = String::new();
+    if !comment.brief.is_empty() {
+        html += &format!("<p>{}</p>", join_lines(&comment.brief));
+    }
+    for paragraph in &comment.details {
+        html += &format!("<p>{}</p>", join_lines(paragraph));
+    }
+    if !comment.params.is_empty() {
+        html += "<p>Parameters:</p><ul>";
+        for (name, description) in &comment.params {
+            html += &format!(
+                "<li><code>{}</code>: {}</li>",
+                escape_html(name),
+                join_lines(description)
+            );
+        }
+        html += "</ul>";
+    }
+    if !comment.returns.is_empty() {
+        html += &format!("<p>Returns: {}</p>", join_lines(&comment.returns));
+    }
+    if html.is_empty() {
+        return None;
+    }
+    Some(DocItem {
+        anchor: None,
+        html,
+        mismatched_declaration: None,
+        url: None,
+        cross_references: Vec::new(),
+    })
+}
+
+#[test]
+fn parse_doc_comment_works() {
+    let html = |comment: &str| parse_doc_comment(comment).map(|item| item.html);
+
+    assert_eq!(html("/** */"), None);
+    assert_eq!(
+        html("/// Adds two numbers."),
+        Some("<p>Adds two numbers.</p>".to_string())
+    );
+    assert_eq!(
+        html(
+            "/**
+              * \\brief Adds two
+              * numbers.
+              *
+              * Overflow is not checked.
+              *
+              * \\param a first number
+              * @param[in] b second
+              *   number
+              * \\return a + b
+              */"
+        ),
+        Some(
+            "<p>Adds two numbers.</p><p>Overflow is not checked.</p>\
+             <p>Parameters:</p><ul><li><code>a</code>: first number</li>\
+             <li><code>b</code>: second number</li></ul><p>Returns: a + b</p>"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        html("//! Returns true if x < y && y > 0"),
+        Some("<p>Returns true if x &lt; y &amp;&amp; y &gt; 0</p>".to_string())
+    );
+}
@@ -8,8 +8,9 @@ use crate::cpp_type::CppType;
 use crate::database::{DatabaseClient, DbItem, DocItem};
 use crate::rust_code_generator::rust_type_to_code;
 use crate::rust_info::{
-    RustEnumValue, RustFunction, RustFunctionKind, RustModule, RustModuleKind, RustQtReceiverType,
-    RustSpecialModuleKind, RustStruct, RustStructKind, RustWrapperTypeKind,
+    RustConstant, RustEnumValue, RustFunction, RustFunctionKind, RustModule, RustModuleKind,
+    RustQtReceiverType, RustSpecialModuleKind, RustStruct, RustStructKind, RustTypeAlias,
+    RustWrapperTypeKind,
 };
 use itertools::Itertools;
 use ritual_common::errors::{bail, err_msg, Result};
@@ -30,7 +31,7 @@ pub fn wrap_cpp_doc_block(html: &str) -> String {
 pub fn module_doc(module: DbItem<&RustModule>, database: &DatabaseClient) -> Result<String> {
     let mut output = String::new();
 
-    match module.item.kind {
+    match &module.item.kind {
         RustModuleKind::Special(kind) => match kind {
             RustSpecialModuleKind::CrateRoot => {
                 let doc_item = database.find_doc_for(&module.id)?;
@@ -78,6 +79,13 @@ pub fn module_doc(module: DbItem<&RustModule>, database: &DatabaseClient) -> Res
             let cpp_path_text = wrap_inline_cpp_code(&cpp_item.path.to_cpp_pseudo_code());
             write!(output, "C++ type: {}", cpp_path_text)?;
         }
+        RustModuleKind::CppHeader { include_file } => {
+            write!(
+                output,
+                "Constants defined in C++ header: {}",
+                wrap_inline_cpp_code(include_file)
+            )?;
+        }
     };
     Ok(output)
 }
@@ -115,7 +123,8 @@ pub fn struct_doc(type1: DbItem<&RustStruct>, database: &DatabaseClient) -> Resu
                 .to_cpp_pseudo_code();
 
             match kind {
-                RustWrapperTypeKind::EnumWrapper => {
+                RustWrapperTypeKind::EnumWrapper { .. }
+                | RustWrapperTypeKind::NativeEnum { .. } => {
                     writeln!(
                         output,
                         "C++ enum: {}.\n",
@@ -123,9 +132,14 @@ pub fn struct_doc(type1: DbItem<&RustStruct>, database: &DatabaseClient) -> Resu
                     )?;
                 }
                 RustWrapperTypeKind::ImmovableClassWrapper => {
+                    let is_union = cpp_item
+                        .item
+                        .as_type_ref()
+                        .map_or(false, |type1| type1.kind.is_union());
                     writeln!(
                         output,
-                        "C++ class: {}.\n",
+                        "C++ {}: {}.\n",
+                        if is_union { "union" } else { "class" },
                         wrap_inline_cpp_code(&cpp_type_code)
                     )?;
                 }
@@ -168,7 +182,9 @@ pub fn struct_doc(type1: DbItem<&RustStruct>, database: &DatabaseClient) -> Resu
                     .item;
 
                 let cpp_signal_arguments = match ffi_item {
-                    CppFfiItem::Function(_) => bail!("invalid source ffi item type"),
+                    CppFfiItem::Function(_) | CppFfiItem::SubclassWrapper(_) => {
+                        bail!("invalid source ffi item type")
+                    }
                     CppFfiItem::QtSlotWrapper(w) => &w.signal_arguments,
                     CppFfiItem::QtSignalWrapper(w) => &w.signal_arguments,
                 };
@@ -250,6 +266,38 @@ pub fn enum_value_doc(value: DbItem<&RustEnumValue>, database: &DatabaseClient)
     Ok(doc)
 }
 
+pub fn constant_doc(constant: DbItem<&RustConstant>, database: &DatabaseClient) -> Result<String> {
+    let cpp_item = database
+        .source_cpp_item(&constant.id)?
+        .ok_or_else(|| err_msg("source cpp item not found"))?
+        .item
+        .as_macro_constant_ref()
+        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+
+    Ok(format!(
+        "C++ macro: {}",
+        wrap_inline_cpp_code(&cpp_item.path.to_cpp_pseudo_code())
+    ))
+}
+
+pub fn type_alias_doc(alias: DbItem<&RustTypeAlias>, database: &DatabaseClient) -> Result<String> {
+    let cpp_item = database
+        .source_cpp_item(&alias.id)?
+        .ok_or_else(|| err_msg("source cpp item not found"))?
+        .item
+        .as_type_alias_ref()
+        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+
+    let mut doc = format!(
+        "C++ type alias: {}",
+        wrap_inline_cpp_code(&cpp_item.path.to_cpp_pseudo_code())
+    );
+    if let Some(doc_item) = database.find_doc_for(&alias.id)? {
+        doc = format!("{}\n\n{}", format_doc_item(doc_item.item), doc);
+    }
+    Ok(doc)
+}
+
 fn format_maybe_link(url: &Option<String>, text: &str) -> String {
     if let Some(url) = url {
         format!("<a href=\"{}\">{}</a>", url, text)
@@ -391,28 +439,44 @@ pub fn function_doc(function: DbItem<&RustFunction>, database: &DatabaseClient)
                     }*/
                 }
                 CppFfiFunctionKind::FieldAccessor { accessor_type } => {
-                    let cpp_item = cpp_item
-                        .item
-                        .as_field_ref()
-                        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
-                    let field_text =
-                        wrap_inline_cpp_code(&cpp_item.path.last().to_cpp_pseudo_code());
+                    let (field_text, item_kind) = if let Some(field) = cpp_item.item.as_field_ref()
+                    {
+                        let text = field.path.last().to_cpp_pseudo_code();
+                        (wrap_inline_cpp_code(&text), "field")
+                    } else if let Some(variable) = cpp_item.item.as_global_variable_ref() {
+                        let text = variable.path.to_cpp_pseudo_code();
+                        (wrap_inline_cpp_code(&text), "variable")
+                    } else {
+                        bail!("invalid source cpp item type");
+                    };
                     match *accessor_type {
                         CppFieldAccessorType::CopyGetter => {
-                            write!(output, "Returns the value of the {} field.", field_text)?;
+                            write!(
+                                output,
+                                "Returns the value of the {} {}.",
+                                field_text, item_kind
+                            )?;
                         }
                         CppFieldAccessorType::ConstRefGetter => {
-                            write!(output, "Returns a reference to the {} field.", field_text)?;
+                            write!(
+                                output,
+                                "Returns a reference to the {} {}.",
+                                field_text, item_kind
+                            )?;
                         }
                         CppFieldAccessorType::MutRefGetter => {
                             write!(
                                 output,
-                                "Returns a mutable reference to the {} field.",
-                                field_text
+                                "Returns a mutable reference to the {} {}.",
+                                field_text, item_kind
                             )?;
                         }
                         CppFieldAccessorType::Setter => {
-                            write!(output, "Sets the value of the {} field.", field_text)?;
+                            write!(
+                                output,
+                                "Sets the value of the {} {}.",
+                                field_text, item_kind
+                            )?;
                         }
                     };
                 }
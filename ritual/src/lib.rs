@@ -19,15 +19,20 @@ pub mod cpp_data;
 pub mod cpp_ffi_data;
 pub mod cpp_ffi_generator;
 pub mod cpp_function;
+mod cpp_hash_functions;
 mod cpp_implicit_methods;
 mod cpp_inheritance; // TODO: deal with inheritance for subclassing support
 mod cpp_omitting_arguments;
 mod cpp_operator;
+mod cpp_ostream_operators;
 pub mod cpp_parser;
+mod cpp_smart_pointers;
+mod cpp_subclasses;
 pub mod cpp_template_instantiator;
 pub mod cpp_type;
 mod crate_writer;
 pub mod database;
+mod doc_comment_parser;
 mod doc_formatter;
 mod download_db;
 pub mod processor;
@@ -4,8 +4,9 @@ use crate::cpp_data::CppItem;
 use crate::database::{DatabaseClient, ItemId};
 use crate::workspace::Workspace;
 use crate::{
-    cpp_casts, cpp_checker, cpp_ffi_generator, cpp_implicit_methods, cpp_omitting_arguments,
-    cpp_parser, cpp_template_instantiator, crate_writer, rust_generator,
+    cpp_casts, cpp_checker, cpp_ffi_generator, cpp_hash_functions, cpp_implicit_methods,
+    cpp_omitting_arguments, cpp_ostream_operators, cpp_parser, cpp_smart_pointers, cpp_subclasses,
+    cpp_template_instantiator, crate_writer, rust_generator,
 };
 use itertools::Itertools;
 use log::debug;
@@ -110,6 +111,19 @@ impl Default for ProcessingSteps {
                 cpp_omitting_arguments::run,
             );
             s.push(&format!("cpp_casts{}", suffix), cpp_casts::run);
+            s.push(
+                &format!("cpp_ostream_operators{}", suffix),
+                cpp_ostream_operators::run,
+            );
+            s.push(
+                &format!("cpp_hash_functions{}", suffix),
+                cpp_hash_functions::run,
+            );
+            s.push(
+                &format!("cpp_smart_pointers{}", suffix),
+                cpp_smart_pointers::run,
+            );
+            s.push(&format!("cpp_subclasses{}", suffix), cpp_subclasses::run);
             s.push(
                 &format!("cpp_ffi_generator{}", suffix),
                 cpp_ffi_generator::run,
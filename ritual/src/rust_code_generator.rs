@@ -1,29 +1,33 @@
 //! Types and functions used for Rust code generation.
 
 use crate::cpp_checks::Condition;
+use crate::cpp_data::{CppItem, CppPath, CppPathItem};
 use crate::cpp_ffi_data::{CppFfiArgumentMeaning, CppFfiItem};
 use crate::cpp_function::CppFunction;
 use crate::database::{DatabaseClient, DbItem, ItemId};
 use crate::doc_formatter;
 use crate::rust_generator::qt_core_path;
 use crate::rust_info::{
-    RustEnumValue, RustExtraImpl, RustExtraImplKind, RustFfiWrapperData, RustFunction,
-    RustFunctionArgument, RustFunctionKind, RustItem, RustModule, RustModuleKind,
-    RustQtReceiverType, RustSpecialModuleKind, RustStruct, RustStructKind, RustTraitImpl,
-    RustWrapperTypeKind,
+    RustConstant, RustEnumValue, RustExtraImpl, RustExtraImplKind, RustFfiWrapperData,
+    RustFunction, RustFunctionArgument, RustFunctionKind, RustItem, RustModule, RustModuleKind,
+    RustQtReceiverType, RustSpecialModuleKind, RustStruct, RustStructKind,
+    RustSubclassOverridesImpl, RustTraitImpl, RustTypeAlias, RustWrapperTypeKind,
 };
 use crate::rust_type::{
-    RustClosureToCallbackConversion, RustCommonType, RustFinalType, RustPath,
-    RustPointerLikeTypeKind, RustToFfiTypeConversion, RustType,
+    RustClosureToCallbackConversion, RustCommonType, RustFinalType, RustOutParamsConversion,
+    RustPath, RustPointerLikeTypeKind, RustToFfiTypeConversion, RustType,
 };
 use itertools::Itertools;
+use log::{debug, warn};
 use ritual_common::errors::{bail, err_msg, format_err, Result};
 use ritual_common::file_utils::{create_dir_all, create_file, file_to_string, File};
 use ritual_common::string_utils::trim_slice;
 use ritual_common::utils::MapIfOk;
+use std::collections::HashMap;
 use std::fmt::Write as _;
 use std::fs;
 use std::io::{self, BufWriter, Write};
+use std::iter::once;
 use std::path::{Path, PathBuf};
 
 fn wrap_unsafe(in_unsafe_context: bool, content: &str) -> String {
@@ -35,6 +39,162 @@ fn wrap_unsafe(in_unsafe_context: bool, content: &str) -> String {
     format!("{}{}{}", unsafe_start, content, unsafe_end)
 }
 
+/// Returns `base` or `base` with a numeric suffix
+/// if `base` is already used as a variable name.
+fn free_variable_name(base: &str, taken_names: &[String]) -> String {
+    let mut name = base.to_string();
+    let mut i = 1;
+    while taken_names.contains(&name) {
+        i += 1;
+        name = format!("{}{}", base, i);
+    }
+    name
+}
+
+/// Generates code that evaluates `expr` and returns its value
+/// along with the values of out-parameters.
+fn out_params_result(
+    out_params: &RustOutParamsConversion,
+    expr: &str,
+    is_unit: bool,
+    taken_names: &[String],
+) -> String {
+    let mut values = Vec::new();
+    let code = if is_unit {
+        format!("{{ {} }};\n", expr)
+    } else {
+        let name = free_variable_name("value", taken_names);
+        let code = format!("let {} = {{ {} }};\n", name, expr);
+        values.push(name);
+        code
+    };
+    values.extend(out_params.values.iter().map(|param| param.name.clone()));
+    let value = if values.len() == 1 {
+        values.pop().unwrap()
+    } else {
+        format!("({})", values.join(", "))
+    };
+    if let Some(ok_flag) = &out_params.ok_flag {
+        format!(
+            "{}if {} {{ Some({}) }} else {{ None }}",
+            code, ok_flag.name, value
+        )
+    } else {
+        format!("{}{}", code, value)
+    }
+}
+
+/// Generates code that performs the FFI call `ffi_call` catching C++ exceptions
+/// and returns `Ok(value)` if no exception was thrown or `Err` with the exception otherwise.
+/// `value` may refer to the call's result as `ffi_result`.
+/// If `has_output_buffer` is true, `ffi_result` is the buffer passed to the FFI function
+/// for the return value. The buffer is not initialized if an exception was thrown,
+/// so it is not dropped in that case.
+fn cpp_exception_result(
+    exception_var_name: &str,
+    ffi_call: &str,
+    value: &str,
+    has_output_buffer: bool,
+    in_unsafe_context: bool,
+) -> String {
+    let maybe_forget = if has_output_buffer {
+        "::std::mem::forget(ffi_result);\n"
+    } else {
+        ""
+    };
+    let error = wrap_unsafe(
+        in_unsafe_context,
+        &format!(
+            "::cpp_core::CppException::from_raw_message({})",
+            exception_var_name
+        ),
+    );
+    format!(
+        "let mut {exception}: *mut ::std::os::raw::c_char = ::std::ptr::null_mut();\n\
+         let ffi_result = {{ {ffi_call} }};\n\
+         if {exception}.is_null() {{\n\
+         Ok({{ {value} }})\n\
+         }} else {{\n\
+         {maybe_forget}Err({error})\n\
+         }}",
+        exception = exception_var_name,
+        ffi_call = ffi_call,
+        value = value,
+        maybe_forget = maybe_forget,
+        error = error,
+    )
+}
+
+#[test]
+fn cpp_exception_result_test() {
+    assert_eq!(
+        cpp_exception_result(
+            "exception",
+            "crate::__ffi::ctr_f(1, &mut exception)",
+            "ffi_result",
+            false,
+            true
+        ),
+        "let mut exception: *mut ::std::os::raw::c_char = ::std::ptr::null_mut();\n\
+         let ffi_result = { crate::__ffi::ctr_f(1, &mut exception) };\n\
+         if exception.is_null() {\n\
+         Ok({ ffi_result })\n\
+         } else {\n\
+         Err(::cpp_core::CppException::from_raw_message(exception))\n\
+         }"
+    );
+    assert_eq!(
+        cpp_exception_result(
+            "exception2",
+            "{\nlet mut object: Point = ::std::mem::uninitialized();\n\
+             crate::__ffi::ctr_g(&mut object, &mut exception2);object\n}",
+            "ffi_result",
+            true,
+            false
+        ),
+        "let mut exception2: *mut ::std::os::raw::c_char = ::std::ptr::null_mut();\n\
+         let ffi_result = { {\nlet mut object: Point = ::std::mem::uninitialized();\n\
+         crate::__ffi::ctr_g(&mut object, &mut exception2);object\n} };\n\
+         if exception2.is_null() {\n\
+         Ok({ ffi_result })\n\
+         } else {\n\
+         ::std::mem::forget(ffi_result);\n\
+         Err(unsafe { ::cpp_core::CppException::from_raw_message(exception2) })\n\
+         }"
+    );
+}
+
+#[test]
+fn out_params_result_test() {
+    use crate::rust_type::RustOutParam;
+
+    let param = |name: &str| RustOutParam {
+        ffi_index: 0,
+        name: name.to_string(),
+        value_type: RustType::Primitive("i32".into()),
+    };
+    let mut out_params = RustOutParamsConversion {
+        return_conversion: RustToFfiTypeConversion::None,
+        values: vec![param("min"), param("max")],
+        ok_flag: None,
+    };
+    assert_eq!(
+        out_params_result(&out_params, "f()", true, &[]),
+        "{ f() };\n(min, max)"
+    );
+    assert_eq!(
+        out_params_result(&out_params, "f()", false, &["value".to_string()]),
+        "let value2 = { f() };\n(value2, min, max)"
+    );
+
+    out_params.values.clear();
+    out_params.ok_flag = Some(param("ok"));
+    assert_eq!(
+        out_params_result(&out_params, "f()", false, &[]),
+        "let value = { f() };\nif ok { Some(value) } else { None }"
+    );
+}
+
 pub fn rust_common_type_to_code(rust_type: &RustCommonType, current_crate: Option<&str>) -> String {
     let mut code = rust_type.path.full_name(current_crate);
     if let Some(args) = &rust_type.generic_arguments {
@@ -138,6 +298,41 @@ impl Write for Generator<'_> {
     }
 }
 
+/// Returns the literal of enum value `value` in a native enum with `repr` type.
+/// Negative values of unsigned enums are stored in the bit pattern of `i64`,
+/// so they are converted to the corresponding unsigned value.
+fn native_enum_value_literal(value: i64, repr: &RustType) -> Result<String> {
+    if value >= 0 {
+        return Ok(value.to_string());
+    }
+    let name = if let RustType::Primitive(name) = repr {
+        name
+    } else {
+        bail!("native enum repr must be a primitive type: {:?}", repr);
+    };
+    if name.starts_with('i') {
+        return Ok(value.to_string());
+    }
+    let bits = name[1..]
+        .parse::<u32>()
+        .map_err(|_| format_err!("unexpected native enum repr: {}", name))?;
+    let mask = if bits >= 64 {
+        u64::max_value()
+    } else {
+        (1 << bits) - 1
+    };
+    Ok((value as u64 & mask).to_string())
+}
+
+#[test]
+fn native_enum_value_literal_test() {
+    let u8_type = RustType::Primitive("u8".to_string());
+    let i8_type = RustType::Primitive("i8".to_string());
+    assert_eq!(native_enum_value_literal(5, &u8_type).unwrap(), "5");
+    assert_eq!(native_enum_value_literal(-1, &u8_type).unwrap(), "255");
+    assert_eq!(native_enum_value_literal(-1, &i8_type).unwrap(), "-1");
+}
+
 /// Generates documentation comments containing
 /// markdown code `doc`.
 fn format_doc(doc: &str) -> String {
@@ -312,6 +507,12 @@ impl Generator<'_> {
             RustItem::EnumValue(_) => {
                 self.generate_enum_value(item.map(|i| i.as_enum_value_ref().unwrap()))
             }
+            RustItem::Constant(_) => {
+                self.generate_constant(item.map(|i| i.as_constant_ref().unwrap()))
+            }
+            RustItem::TypeAlias(_) => {
+                self.generate_type_alias(item.map(|i| i.as_type_alias_ref().unwrap()))
+            }
             RustItem::TraitImpl(_) => self.generate_trait_impl(
                 item.map(|i| i.as_trait_impl_ref().unwrap()),
                 &condition_texts,
@@ -372,6 +573,11 @@ impl Generator<'_> {
                 self,
                 "#![cfg_attr(feature = \"ritual_rustdoc_nightly\", feature(doc_cfg))]"
             )?;
+            if module.item.kind == RustModuleKind::Special(RustSpecialModuleKind::CrateRoot) {
+                // FFI functions, trait impls and other generated items refer to
+                // deprecated items; users still get warnings when they use them
+                writeln!(self, "#![allow(deprecated)]")?;
+            }
 
             if let Some(crate_template_src_path) = &self.crate_template_src_path {
                 let template_path = self.module_path(&module.item.path, crate_template_src_path)?;
@@ -401,7 +607,7 @@ impl Generator<'_> {
             writeln!(self, "{}", content)?;
         }
 
-        match module.item.kind {
+        match &module.item.kind {
             RustModuleKind::Special(RustSpecialModuleKind::Ffi) => {
                 writeln!(self, "include!(concat!(env!(\"OUT_DIR\"), \"/ffi.rs\"));")?;
             }
@@ -414,7 +620,8 @@ impl Generator<'_> {
             RustModuleKind::Special(RustSpecialModuleKind::CrateRoot)
             | RustModuleKind::Special(RustSpecialModuleKind::Ops)
             | RustModuleKind::CppNamespace { .. }
-            | RustModuleKind::CppNestedTypes { .. } => {
+            | RustModuleKind::CppNestedTypes { .. }
+            | RustModuleKind::CppHeader { .. } => {
                 self.generate_children(&module.item.path, None)?;
             }
         }
@@ -459,6 +666,8 @@ impl Generator<'_> {
         let doc = doc_formatter::struct_doc(rust_struct.clone(), self.current_database)?
             + &condition_texts.doc_text;
         write!(self, "{}", format_doc(&doc))?;
+        let deprecation = self.deprecation_attribute(&rust_struct.id)?;
+        write!(self, "{}", deprecation)?;
 
         let visibility = if rust_struct.item.is_public {
             "pub "
@@ -467,14 +676,23 @@ impl Generator<'_> {
         };
         match &rust_struct.item.kind {
             RustStructKind::WrapperType(kind) => match kind {
-                RustWrapperTypeKind::EnumWrapper => {
+                RustWrapperTypeKind::EnumWrapper { underlying_type } => {
                     writeln!(
                         self,
                         include_str!("../templates/crate/enum_wrapper.rs.in"),
                         vis = visibility,
-                        name = rust_struct.item.path.last()
+                        name = rust_struct.item.path.last(),
+                        underlying_type = self.rust_type_to_code(underlying_type),
                     )?;
                 }
+                RustWrapperTypeKind::NativeEnum { underlying_type } => {
+                    // enum values are generated as variants, so there are no other children
+                    return self.generate_native_enum(
+                        &rust_struct.item.path,
+                        visibility,
+                        underlying_type,
+                    );
+                }
                 RustWrapperTypeKind::ImmovableClassWrapper => {
                     writeln!(self, "#[repr(C)]")?;
                     writeln!(
@@ -524,6 +742,48 @@ impl Generator<'_> {
         Ok(())
     }
 
+    fn generate_native_enum(
+        &mut self,
+        path: &RustPath,
+        visibility: &str,
+        underlying_type: &RustType,
+    ) -> Result<()> {
+        let repr = self.rust_type_to_code(underlying_type);
+        let values = self
+            .current_database
+            .rust_children(path)
+            .filter_map(|item| item.filter_map(|item| item.as_enum_value_ref()))
+            .collect_vec();
+        writeln!(self, "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]")?;
+        writeln!(self, "#[repr({})]", repr)?;
+        writeln!(self, "#[allow(non_camel_case_types)]")?;
+        writeln!(self, "{}enum {} {{", visibility, path.last())?;
+        let mut match_arms = String::new();
+        for value in values {
+            let doc = doc_formatter::enum_value_doc(value.clone(), self.current_database)?;
+            write!(self, "{}", format_doc(&doc))?;
+            let literal = native_enum_value_literal(value.item.value, underlying_type)?;
+            writeln!(self, "{} = {},", value.item.path.last(), literal)?;
+            writeln!(
+                match_arms,
+                "{} => Ok({}::{}),",
+                literal,
+                path.last(),
+                value.item.path.last()
+            )?;
+        }
+        writeln!(self, "}}")?;
+        writeln!(self)?;
+        writeln!(
+            self,
+            include_str!("../templates/crate/native_enum_impl.rs.in"),
+            name = path.last(),
+            underlying_type = repr,
+            match_arms = match_arms,
+        )?;
+        Ok(())
+    }
+
     fn generate_enum_value(&mut self, value: DbItem<&RustEnumValue>) -> Result<()> {
         write!(
             self,
@@ -533,20 +793,84 @@ impl Generator<'_> {
                 self.current_database
             )?)
         )?;
-        let struct_path = self.rust_path_to_string(
-            &value
-                .item
-                .path
-                .parent()
-                .expect("enum value must have parent"),
-        );
+        let parent_path = value
+            .item
+            .path
+            .parent()
+            .expect("enum value must have parent");
+        let struct_path = self.rust_path_to_string(&parent_path);
+        let underlying_type = self
+            .current_database
+            .find_rust_item(&parent_path)
+            .and_then(|item| item.item.as_struct_ref())
+            .and_then(|rust_struct| {
+                if let RustStructKind::WrapperType(RustWrapperTypeKind::EnumWrapper {
+                    underlying_type,
+                }) = &rust_struct.kind
+                {
+                    Some(underlying_type)
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| err_msg("enum value's parent is not an enum wrapper"))?;
+        // Negative values of unsigned enums are stored in the bit pattern of `i64`,
+        // so they have to be converted to the underlying type explicitly.
+        // The literal is typed to avoid overflow of `i64::MIN` when it's negated.
+        let value_code = if value.item.value < 0 {
+            format!(
+                "{}i64 as {}",
+                value.item.value,
+                self.rust_type_to_code(underlying_type)
+            )
+        } else {
+            value.item.value.to_string()
+        };
         writeln!(self, "#[allow(non_upper_case_globals)]")?;
         writeln!(
             self,
             "pub const {value_name}: {struct_path} = {struct_path}({value});",
             value_name = value.item.path.last(),
             struct_path = struct_path,
-            value = value.item.value
+            value = value_code
+        )?;
+        Ok(())
+    }
+
+    fn generate_constant(&mut self, constant: DbItem<&RustConstant>) -> Result<()> {
+        write!(
+            self,
+            "{}",
+            format_doc(&doc_formatter::constant_doc(
+                constant.clone(),
+                self.current_database
+            )?)
+        )?;
+        writeln!(self, "#[allow(non_upper_case_globals)]")?;
+        writeln!(
+            self,
+            "pub const {}: {} = {};",
+            constant.item.path.last(),
+            self.rust_type_to_code(&constant.item.value_type),
+            constant.item.value
+        )?;
+        Ok(())
+    }
+
+    fn generate_type_alias(&mut self, alias: DbItem<&RustTypeAlias>) -> Result<()> {
+        write!(
+            self,
+            "{}",
+            format_doc(&doc_formatter::type_alias_doc(
+                alias.clone(),
+                self.current_database
+            )?)
+        )?;
+        writeln!(
+            self,
+            "pub type {} = {};",
+            alias.item.path.last(),
+            self.rust_type_to_code(&alias.item.target_type)
         )?;
         Ok(())
     }
@@ -612,6 +936,19 @@ impl Generator<'_> {
                 let code = format!("{}::QBox::from_raw({})", self.qt_core_prefix(), source_expr);
                 wrap_unsafe(in_unsafe_context, &code)
             }
+            RustToFfiTypeConversion::RefOrBoxToPtr => {
+                bail!("RefOrBoxToPtr is not convertable from FFI type");
+            }
+            RustToFfiTypeConversion::SmartPtrToPtr { api_type } => {
+                let api_type = api_type.as_common()?;
+                let code = format!(
+                    "{}::from_raw({}).expect(\"attempted to construct a null {}\")",
+                    self.rust_path_to_string(&api_type.path),
+                    source_expr,
+                    api_type.path.last()
+                );
+                wrap_unsafe(in_unsafe_context, &code)
+            }
             RustToFfiTypeConversion::QPtrToPtr
             | RustToFfiTypeConversion::UtilsPtrToPtr { .. }
             | RustToFfiTypeConversion::UtilsRefToPtr { .. }
@@ -672,10 +1009,41 @@ impl Generator<'_> {
                     source_expr
                 )
             }
+            RustToFfiTypeConversion::FlagsToInt { .. } => {
+                let mut flags_type = type1.api_type().clone();
+                let int_type = if let RustType::Common(RustCommonType {
+                    generic_arguments, ..
+                }) = &mut flags_type
+                {
+                    let int_type =
+                        generic_arguments.as_ref().expect("flags must be generic")[1].clone();
+                    *generic_arguments = None;
+                    int_type
+                } else {
+                    unreachable!();
+                };
+                format!(
+                    "{}::from({} as {})",
+                    self.rust_type_to_code(&flags_type),
+                    source_expr,
+                    self.rust_type_to_code(&int_type)
+                )
+            }
             RustToFfiTypeConversion::UnitToAnything => format!("let _ = {};", source_expr),
             RustToFfiTypeConversion::AsCast { api_type } => {
                 format!("{} as {}", source_expr, self.rust_type_to_code(api_type))
             }
+            RustToFfiTypeConversion::NativeEnumToInt { api_type } => {
+                // the C++ library may return a value that doesn't match any variant
+                let enum_type = self.rust_type_to_code(api_type);
+                format!(
+                    "<{enum_type} as ::std::convert::TryFrom<{int_type}>>::try_from({expr})\
+                     .unwrap_or_else(|value| panic!(\"invalid {enum_type} value: {{}}\", value))",
+                    enum_type = enum_type,
+                    int_type = self.rust_type_to_code(type1.ffi_type()),
+                    expr = source_expr,
+                )
+            }
             RustToFfiTypeConversion::RefTo(conversion) => {
                 let intermediate =
                     RustFinalType::new(type1.ffi_type().clone(), (**conversion).clone())?;
@@ -690,9 +1058,18 @@ impl Generator<'_> {
             RustToFfiTypeConversion::ImplCastInto(_) => {
                 bail!("ImplCastInto is not convertable from FFI type");
             }
+            RustToFfiTypeConversion::UpcastBoxToPtr { .. } => {
+                bail!("UpcastBoxToPtr is not convertable from FFI type");
+            }
             RustToFfiTypeConversion::ClosureToCallback { .. } => {
                 bail!("ClosureToCallback is not convertable from FFI type");
             }
+            RustToFfiTypeConversion::CppExceptionResult(_) => {
+                bail!("CppExceptionResult must be handled by generate_ffi_call");
+            }
+            RustToFfiTypeConversion::OutParams(_) => {
+                bail!("OutParams must be handled by generate_ffi_call");
+            }
         };
         Ok(code1 + &code2)
     }
@@ -728,6 +1105,23 @@ impl Generator<'_> {
             RustToFfiTypeConversion::CppBoxToPtr | RustToFfiTypeConversion::QBoxToPtr => {
                 format!("{}.into_raw_ptr()", expr)
             }
+            RustToFfiTypeConversion::RefOrBoxToPtr => format!("{}.as_raw_ptr()", expr),
+            RustToFfiTypeConversion::UpcastBoxToPtr { .. } => {
+                let target = type1.ffi_type().pointer_like_to_target()?;
+                format!(
+                    "::cpp_core::StaticUpcast::<{}>::static_upcast({}.into_ptr()).as_mut_raw_ptr()",
+                    self.rust_type_to_code(&target),
+                    expr
+                )
+            }
+            RustToFfiTypeConversion::SmartPtrToPtr { .. } => {
+                if type1.ffi_type().is_const_pointer_like()? {
+                    format!("{}.as_raw_ptr()", expr)
+                } else {
+                    // the C++ smart pointer object is moved or deleted by the FFI function
+                    format!("{}.into_raw_ptr()", expr)
+                }
+            }
             RustToFfiTypeConversion::UtilsPtrToPtr { .. }
             | RustToFfiTypeConversion::UtilsRefToPtr { .. }
             | RustToFfiTypeConversion::QPtrToPtr { .. } => {
@@ -750,15 +1144,35 @@ impl Generator<'_> {
                 }
             }
             RustToFfiTypeConversion::OptionUtilsRefToPtr { .. } => {
-                bail!("OptionUtilsRefToPtr is not supported in argument position");
+                if type1.ffi_type().is_const_pointer_like()? {
+                    format!(
+                        "{}.map_or(::std::ptr::null(), ::cpp_core::Ref::as_raw_ptr)",
+                        expr
+                    )
+                } else {
+                    format!(
+                        "{}.map_or(::std::ptr::null_mut(), ::cpp_core::Ref::as_mut_raw_ptr)",
+                        expr
+                    )
+                }
             }
             RustToFfiTypeConversion::QFlagsToUInt { .. } => format!("{}.to_int()", expr),
+            RustToFfiTypeConversion::FlagsToInt { .. } => format!(
+                "{}.to_int() as {}",
+                expr,
+                self.rust_type_to_code(type1.ffi_type())
+            ),
             RustToFfiTypeConversion::UnitToAnything => {
                 bail!("UnitToAnything is not possible to use in argument position");
             }
             RustToFfiTypeConversion::AsCast { .. } => {
                 format!("{} as {}", expr, self.rust_type_to_code(type1.ffi_type()))
             }
+            RustToFfiTypeConversion::NativeEnumToInt { .. } => format!(
+                "{}::from({})",
+                self.rust_type_to_code(type1.ffi_type()),
+                expr
+            ),
             RustToFfiTypeConversion::RefTo(conversion) => {
                 let intermediate =
                     RustFinalType::new(type1.ffi_type().clone(), (**conversion).clone())?;
@@ -780,8 +1194,16 @@ impl Generator<'_> {
                 );
                 self.convert_type_to_ffi(&intermediate_expr, &intermediate)?
             }
-            RustToFfiTypeConversion::ClosureToCallback { .. } => {
-                "Some(ffi_callback::<T>), Some(deleter::<T>), data".to_string()
+            RustToFfiTypeConversion::ClosureToCallback { .. } => format!(
+                "Some(ffi_callback::<T>), Some(deleter::<T>), \
+                 Box::into_raw(Box::new({})) as *mut ::std::ffi::c_void",
+                expr
+            ),
+            RustToFfiTypeConversion::CppExceptionResult(_) => {
+                bail!("CppExceptionResult is not possible to use in argument position");
+            }
+            RustToFfiTypeConversion::OutParams(_) => {
+                bail!("OutParams is not possible to use in argument position");
             }
         };
         Ok(code)
@@ -805,6 +1227,40 @@ impl Generator<'_> {
         format!("T: FnMut({}){} + 'static", args_text, return_type_text)
     }
 
+    fn upcast_bound_code(&self, type1: &RustFinalType) -> Result<String> {
+        let (box_conversion, type_parameter) = if let RustToFfiTypeConversion::UpcastBoxToPtr {
+            box_conversion,
+            type_parameter,
+        } = type1.conversion()
+        {
+            (box_conversion, type_parameter)
+        } else {
+            bail!(
+                "expected UpcastBoxToPtr conversion, got {:?}",
+                type1.conversion()
+            );
+        };
+        let target = type1.ffi_type().pointer_like_to_target()?;
+        let mut code = format!(
+            "{}: ::cpp_core::StaticUpcast<{}> + ::cpp_core::CppDeletable",
+            type_parameter,
+            self.rust_type_to_code(&target)
+        );
+        if **box_conversion == RustToFfiTypeConversion::QBoxToPtr {
+            // required by `QBox<T>`
+            let q_object = RustType::Common(RustCommonType {
+                path: self.qt_core_path().join("QObject"),
+                generic_arguments: None,
+            });
+            write!(
+                code,
+                " + ::cpp_core::StaticUpcast<{}>",
+                self.rust_type_to_code(&q_object)
+            )?;
+        }
+        Ok(code)
+    }
+
     fn callback_glue_code(&self, conversion: &RustClosureToCallbackConversion) -> Result<String> {
         let mut code = String::new();
         writeln!(
@@ -816,16 +1272,23 @@ impl Generator<'_> {
             }}"
         )?;
 
+        let return_type = &conversion.closure_return_type;
+        let return_type_text = if return_type.api_type().is_unit() {
+            String::new()
+        } else {
+            format!(" -> {}", self.rust_type_to_code(return_type.ffi_type()))
+        };
         writeln!(
             code,
-            "extern \"C\" fn ffi_callback<{}>(data: *mut ::std::ffi::c_void, {}) {{",
+            "extern \"C\" fn ffi_callback<{}>(data: *mut ::std::ffi::c_void, {}){} {{",
             self.callback_bound_code(conversion),
             conversion
                 .closure_arguments
                 .iter()
                 .enumerate()
                 .map(|(i, arg)| format!("arg{}: {}", i, self.rust_type_to_code(arg.ffi_type())))
-                .join(", ")
+                .join(", "),
+            return_type_text,
         )?;
 
         let func_args = conversion
@@ -837,16 +1300,15 @@ impl Generator<'_> {
             })?
             .join(", ");
 
-        code.push_str(&wrap_unsafe(
-            false,
-            &format!("(*(data as *mut T))({})", func_args),
-        ));
+        let call = wrap_unsafe(false, &format!("(*(data as *mut T))({})", func_args));
+        if return_type.api_type().is_unit() {
+            code.push_str(&call);
+        } else {
+            writeln!(code, "let result = {};", call)?;
+            code.push_str(&self.convert_type_to_ffi("result", return_type)?);
+        }
 
         writeln!(code, "}}")?;
-        writeln!(
-            code,
-            "let data = Box::into_raw(Box::new(callback)) as *mut ::std::ffi::c_void;"
-        )?;
 
         Ok(code)
     }
@@ -889,6 +1351,64 @@ impl Generator<'_> {
             .as_function_ref()
             .ok_or_else(|| err_msg("invalid source ffi item type"))?;
 
+        // If the function catches C++ exceptions, the value is converted
+        // only after checking that no exception was thrown.
+        let inner_return_type;
+        let return_type = if let RustToFfiTypeConversion::CppExceptionResult(conversion) =
+            return_type.conversion()
+        {
+            inner_return_type =
+                RustFinalType::new(return_type.ffi_type().clone(), (**conversion).clone())?;
+            &inner_return_type
+        } else {
+            return_type
+        };
+
+        let mut taken_names = arguments.iter().map(|arg| arg.name.clone()).collect_vec();
+
+        // Storage for out-parameters is allocated before the call, and their values
+        // are added to the converted return value.
+        let inner_out_params_return_type;
+        let (return_type, out_params) =
+            if let RustToFfiTypeConversion::OutParams(conversion) = return_type.conversion() {
+                inner_out_params_return_type = RustFinalType::new(
+                    return_type.ffi_type().clone(),
+                    conversion.return_conversion.clone(),
+                )?;
+                (&inner_out_params_return_type, Some(&**conversion))
+            } else {
+                (return_type, None)
+            };
+        let mut out_params_code = String::new();
+        if let Some(out_params) = out_params {
+            for param in out_params.values.iter().chain(&out_params.ok_flag) {
+                writeln!(
+                    out_params_code,
+                    "let mut {}: {} = ::std::default::Default::default();",
+                    param.name,
+                    self.rust_type_to_code(&param.value_type)
+                )?;
+                if final_args.len() <= param.ffi_index {
+                    final_args.resize(param.ffi_index + 1, None);
+                }
+                final_args[param.ffi_index] = Some(format!("&mut {}", param.name));
+                taken_names.push(param.name.clone());
+            }
+        }
+
+        let mut maybe_exception_var_name = None;
+        if let Some(i) = ffi_item
+            .arguments
+            .iter()
+            .position(|arg| arg.meaning == CppFfiArgumentMeaning::Exception)
+        {
+            let exception_var_name = free_variable_name("exception", &taken_names);
+            taken_names.push(exception_var_name.clone());
+            final_args.resize(i + 1, None);
+            final_args[i] = Some(format!("&mut {}", exception_var_name));
+            maybe_exception_var_name = Some(exception_var_name);
+        }
+
         let return_type_ffi_index = ffi_item
             .arguments
             .iter()
@@ -897,12 +1417,8 @@ impl Generator<'_> {
             .map(|(index, _arg)| index);
 
         if let Some(i) = return_type_ffi_index {
-            let mut return_var_name = "object".to_string();
-            let mut ii = 1;
-            while arguments.iter().any(|x| x.name == return_var_name) {
-                ii += 1;
-                return_var_name = format!("object{}", ii);
-            }
+            let return_var_name = free_variable_name("object", &taken_names);
+            taken_names.push(return_var_name.clone());
             let struct_name = if return_type.conversion() == &RustToFfiTypeConversion::CppBoxToPtr {
                 if let RustType::Common(RustCommonType {
                     generic_arguments, ..
@@ -933,9 +1449,17 @@ impl Generator<'_> {
             final_args[i] = Some(format!("&mut {}", return_var_name));
             maybe_result_var_name = Some(return_var_name);
         }
+        // deleter and data of a callback are passed along with the callback
         let final_args = final_args
             .into_iter()
-            .map_if_ok(|x| x.ok_or_else(|| err_msg("ffi argument is missing")))?;
+            .enumerate()
+            .filter(|(index, _)| {
+                ffi_item
+                    .arguments
+                    .get(*index)
+                    .map_or(true, |arg| !arg.meaning.is_callback_deleter_or_data())
+            })
+            .map_if_ok(|(_, x)| x.ok_or_else(|| err_msg("ffi argument is missing")))?;
 
         result.push(wrap_unsafe(
             in_unsafe_context,
@@ -954,10 +1478,43 @@ impl Generator<'_> {
             result.push(format!("{}\n}}", name));
         }
         let code = result.join("");
-        if maybe_result_var_name.is_none() {
-            self.convert_type_from_ffi(&return_type, code, in_unsafe_context, true)
+        let with_out_params = |value: String| {
+            if let Some(out_params) = out_params {
+                out_params_result(
+                    out_params,
+                    &value,
+                    return_type.api_type().is_unit(),
+                    &taken_names,
+                )
+            } else {
+                value
+            }
+        };
+        if let Some(exception_var_name) = &maybe_exception_var_name {
+            let has_output_buffer = maybe_result_var_name.is_some();
+            let value = if has_output_buffer {
+                "ffi_result".to_string()
+            } else {
+                self.convert_type_from_ffi(
+                    &return_type,
+                    "ffi_result".to_string(),
+                    in_unsafe_context,
+                    false,
+                )?
+            };
+            Ok(out_params_code
+                + &cpp_exception_result(
+                    exception_var_name,
+                    &code,
+                    &with_out_params(value),
+                    has_output_buffer,
+                    in_unsafe_context,
+                ))
+        } else if maybe_result_var_name.is_none() {
+            let value = self.convert_type_from_ffi(&return_type, code, in_unsafe_context, true)?;
+            Ok(out_params_code + &with_out_params(value))
         } else {
-            Ok(code)
+            Ok(out_params_code + &with_out_params(code))
         }
     }
 
@@ -1019,6 +1576,25 @@ impl Generator<'_> {
         })
     }
 
+    /// Returns `#[deprecated]` attribute for the Rust item `id`
+    /// if its source C++ item is deprecated, or an empty string otherwise.
+    fn deprecation_attribute(&self, id: &ItemId) -> Result<String> {
+        let source_item = self.current_database.source_cpp_item(id)?;
+        let deprecation = source_item.as_ref().and_then(|item| match item.item {
+            CppItem::Function(function) => function.deprecation.as_ref(),
+            CppItem::Type(type1) => type1.deprecation.as_ref(),
+            _ => None,
+        });
+        let text = match deprecation {
+            None => String::new(),
+            Some(deprecation) => match &deprecation.message {
+                Some(message) => format!("#[deprecated(note = {:?})]\n", message),
+                None => "#[deprecated]\n".to_string(),
+            },
+        };
+        Ok(text)
+    }
+
     /// Generates complete code of a Rust wrapper function.
     fn generate_function(
         &mut self,
@@ -1033,6 +1609,13 @@ impl Generator<'_> {
             ""
         };
         let maybe_unsafe = if func.item.is_unsafe { "unsafe " } else { "" };
+        // Deprecation attributes have no effect on trait impl items,
+        // and raw FFI functions are not part of the public API.
+        let deprecation = if is_in_trait_context || func.item.kind.is_ffi_function() {
+            String::new()
+        } else {
+            self.deprecation_attribute(&func.id)?
+        };
 
         let body = match &func.item.kind {
             RustFunctionKind::FfiWrapper(data) => Some(self.generate_ffi_call(
@@ -1077,22 +1660,22 @@ impl Generator<'_> {
                 self.rust_type_to_code(func.item.return_type.api_type())
             )
         };
-        let generic_args = func
-            .item
-            .arguments
-            .iter()
-            .filter_map(|x| {
-                if let Some(lifetime) = x.argument_type.api_type().lifetime() {
-                    Some(format!("'{}", lifetime))
-                } else if let RustToFfiTypeConversion::ClosureToCallback(conversion) =
-                    x.argument_type.conversion()
-                {
-                    Some(self.callback_bound_code(conversion))
-                } else {
-                    None
-                }
-            })
-            .collect_vec();
+        let mut generic_args = Vec::new();
+        for arg in &func.item.arguments {
+            if let Some(lifetime) = arg.argument_type.api_type().lifetime() {
+                generic_args.push(format!("'{}", lifetime));
+            } else if let RustToFfiTypeConversion::ClosureToCallback(conversion) =
+                arg.argument_type.conversion()
+            {
+                generic_args.push(self.callback_bound_code(conversion));
+            } else if let RustToFfiTypeConversion::UpcastBoxToPtr { .. } =
+                arg.argument_type.conversion()
+            {
+                generic_args.push(self.upcast_bound_code(&arg.argument_type)?);
+            }
+        }
+        // lifetimes must be declared before type parameters
+        generic_args.sort_by_key(|arg| !arg.starts_with('\''));
 
         let generic_args_text = if generic_args.is_empty() {
             String::new()
@@ -1105,10 +1688,11 @@ impl Generator<'_> {
             + &condition_texts.doc_text;
         writeln!(
             self,
-            "{doc}{maybe_inline}{condition}{maybe_pub}{maybe_unsafe} \
+            "{doc}{deprecation}{maybe_inline}{condition}{maybe_pub}{maybe_unsafe} \
              fn {name}{generic_args_text}({args}){return_type} \
              {maybe_body}\n\n",
             doc = format_doc(&doc),
+            deprecation = deprecation,
             maybe_inline = if body.is_some() {
                 "#[inline(always)]\n"
             } else {
@@ -1177,14 +1761,12 @@ impl Generator<'_> {
     ) -> Result<()> {
         match &item.item.kind {
             RustExtraImplKind::FlagEnum(data) => {
-                let enum_path = self.rust_path_to_string(&data.enum_path);
-                let qflags = self.rust_path_to_string(&self.qt_core_path().join("QFlags"));
-
                 writeln!(
                     self,
                     include_str!("../templates/crate/flag_enum_impl.rs.in"),
-                    e = enum_path,
-                    qflags = qflags
+                    e = self.rust_path_to_string(&data.enum_path),
+                    flags = self.rust_type_to_code(&data.flags_type),
+                    int = self.rust_type_to_code(&data.int_type),
                 )?;
             }
             RustExtraImplKind::QtReceiverImpl(data) => {
@@ -1195,7 +1777,9 @@ impl Generator<'_> {
                     .item;
 
                 let receiver_id = match wrapper {
-                    CppFfiItem::Function(_) => bail!("invalid source ffi item type"),
+                    CppFfiItem::Function(_) | CppFfiItem::SubclassWrapper(_) => {
+                        bail!("invalid source ffi item type")
+                    }
                     CppFfiItem::QtSlotWrapper(w) => CppFunction::receiver_id_from_data(
                         RustQtReceiverType::Slot,
                         "slot_",
@@ -1230,11 +1814,338 @@ impl Generator<'_> {
                     )?;
                 }
             }
+            RustExtraImplKind::SubclassOverrides(data) => {
+                self.generate_subclass_overrides(&item.id, data, condition_texts)?;
+            }
+            RustExtraImplKind::OstreamDisplay(data) => {
+                writeln!(
+                    self,
+                    include_str!("../templates/crate/ostream_display.rs.in"),
+                    type_path = self.rust_path_to_string(&data.target_path),
+                    ffi_function = self.rust_path_to_string(&data.ffi_function_path),
+                    condition_attribute = condition_texts.attribute,
+                )?;
+            }
+            RustExtraImplKind::Hash(data) => {
+                writeln!(
+                    self,
+                    include_str!("../templates/crate/hash_impl.rs.in"),
+                    type_path = self.rust_path_to_string(&data.target_path),
+                    ffi_function = self.rust_path_to_string(&data.ffi_function_path),
+                    condition_attribute = condition_texts.attribute,
+                )?;
+            }
+            RustExtraImplKind::SharedPtrTarget(data) => {
+                writeln!(
+                    self,
+                    include_str!("../templates/crate/smart_pointer_impl.rs.in"),
+                    pointer_types = "`cpp_core::SharedPtr` and `cpp_core::WeakPtr`",
+                    trait_name = "SharedPtrTarget",
+                    function_name = "shared_ptr_functions",
+                    functions_type = "SharedPtrFunctions",
+                    type_path = self.rust_path_to_string(&data.target_path),
+                    ffi_function = self.rust_path_to_string(&data.ffi_function_path),
+                    condition_attribute = condition_texts.attribute,
+                )?;
+            }
+            RustExtraImplKind::UniquePtrTarget(data) => {
+                writeln!(
+                    self,
+                    include_str!("../templates/crate/smart_pointer_impl.rs.in"),
+                    pointer_types = "`cpp_core::UniquePtr`",
+                    trait_name = "UniquePtrTarget",
+                    function_name = "unique_ptr_functions",
+                    functions_type = "UniquePtrFunctions",
+                    type_path = self.rust_path_to_string(&data.target_path),
+                    ffi_function = self.rust_path_to_string(&data.ffi_function_path),
+                    condition_attribute = condition_texts.attribute,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns Rust wrappers of the C++ methods of `class_path`, indexed by C++ path.
+    fn ffi_wrappers_of_class(
+        &self,
+        class_path: &CppPath,
+    ) -> Result<HashMap<CppPath, &RustFunction>> {
+        let mut result = HashMap::new();
+        for item in self.current_database.rust_items() {
+            let function = if let Some(function) = item.item.as_function_ref() {
+                function
+            } else {
+                continue;
+            };
+            if !function.kind.is_ffi_wrapper() {
+                continue;
+            }
+            let cpp_item =
+                if let Some(cpp_item) = self.current_database.source_cpp_item(&item.id)? {
+                    cpp_item
+                } else {
+                    continue;
+                };
+            if let Some(cpp_function) = cpp_item.item.as_function_ref() {
+                if cpp_function.path.parent().ok().as_ref() == Some(class_path) {
+                    result.insert(cpp_function.path.clone(), function);
+                }
+            }
         }
+        Ok(result)
+    }
+
+    fn generate_subclass_overrides(
+        &mut self,
+        id: &ItemId,
+        data: &RustSubclassOverridesImpl,
+        condition_texts: &ConditionTexts,
+    ) -> Result<()> {
+        let wrapper = self
+            .current_database
+            .source_ffi_item(id)?
+            .ok_or_else(|| err_msg("source ffi item not found"))?
+            .item
+            .as_subclass_wrapper_ref()
+            .ok_or_else(|| err_msg("invalid source ffi item type"))?;
+        let subclass_type = self.rust_path_to_string(&data.target_path);
+        let ffi_wrappers = self.ffi_wrappers_of_class(&wrapper.class_path)?;
+
+        let mut trait_methods = String::new();
+        let mut setter_calls = String::new();
+        for method in &wrapper.methods {
+            let is_pure_virtual = method
+                .function
+                .member()
+                .map_or(false, |m| m.is_pure_virtual);
+            let setter_path = wrapper
+                .class_path
+                .join(CppPathItem::from_good_str(&method.setter_name()));
+            let setter = ffi_wrappers.get(&setter_path);
+            let conversion = setter.and_then(|setter| {
+                setter
+                    .arguments
+                    .iter()
+                    .filter_map(|arg| arg.argument_type.conversion().as_callback_ref())
+                    .next()
+            });
+            let (setter, conversion) = match (setter, conversion) {
+                (Some(setter), Some(conversion)) => (setter, conversion),
+                _ => {
+                    if is_pure_virtual {
+                        // `rust_generator` doesn't generate such subclasses,
+                        // the C++ subclass would abort when calling this method
+                        warn!(
+                            "skipping overrides of {}: callback setter for pure virtual \
+                             method {} is not available",
+                            wrapper.base_class_path.to_cpp_pseudo_code(),
+                            method.function.short_text()
+                        );
+                        return Ok(());
+                    }
+                    debug!(
+                        "can't override {}: callback setter is not available",
+                        method.function.short_text()
+                    );
+                    continue;
+                }
+            };
+
+            let base_caller_path = wrapper
+                .class_path
+                .join(CppPathItem::from_good_str(&method.base_caller_name()));
+            let base_caller = ffi_wrappers
+                .get(&base_caller_path)
+                .filter(|f| f.return_type.api_type() == conversion.closure_return_type.api_type());
+            if !is_pure_virtual && base_caller.is_none() {
+                // The C++ subclass calls the base implementation
+                // because no callback is set for this method.
+                debug!(
+                    "can't override {}: default implementation can't be provided",
+                    method.function.short_text()
+                );
+                continue;
+            }
+
+            let argument_types = conversion
+                .closure_arguments
+                .iter()
+                .map(|arg| self.rust_type_to_code(arg.api_type()))
+                .collect_vec();
+            let return_type = if conversion.closure_return_type.api_type().is_unit() {
+                None
+            } else {
+                Some(self.rust_type_to_code(conversion.closure_return_type.api_type()))
+            };
+            let code = SubclassOverrideCode {
+                cpp_method: method.function.short_text(),
+                subclass_type: subclass_type.clone(),
+                setter_name: setter.path.last().to_string(),
+                base_caller_name: base_caller.map(|f| f.path.last().to_string()),
+                argument_types,
+                return_type,
+            };
+            trait_methods.push_str(&code.trait_method()?);
+            setter_calls.push_str(&code.setter_call()?);
+        }
+        if setter_calls.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            self,
+            include_str!("../templates/crate/subclass_overrides.rs.in"),
+            base_type = wrapper.base_class_path.to_cpp_pseudo_code(),
+            subclass_type = subclass_type,
+            trait_name = data.trait_path.last(),
+            trait_methods = trait_methods,
+            setter_calls = setter_calls,
+            condition_attribute = condition_texts.attribute,
+        )?;
         Ok(())
     }
 }
 
+/// Data for generating a method of the overrides trait of a subclass wrapper
+/// and the code that forwards the corresponding callback to it.
+struct SubclassOverrideCode {
+    /// Short text of the overridden C++ method
+    cpp_method: String,
+    /// Rust type of the subclass wrapper
+    subclass_type: String,
+    /// Name of the Rust method that sets the callback (`set_{name}_callback`)
+    setter_name: String,
+    /// Name of the Rust method that calls the base implementation, if available
+    base_caller_name: Option<String>,
+    /// Rust types of the arguments of the closure
+    argument_types: Vec<String>,
+    /// Rust return type of the closure, if it's not unit
+    return_type: Option<String>,
+}
+
+impl SubclassOverrideCode {
+    /// Returns name of the trait method.
+    /// It's the name of the setter without the prefix and the suffix.
+    fn name(&self) -> Result<&str> {
+        let prefix = "set_";
+        let suffix = "_callback";
+        if !self.setter_name.starts_with(prefix)
+            || !self.setter_name.ends_with(suffix)
+            || self.setter_name.len() <= prefix.len() + suffix.len()
+        {
+            bail!("unexpected callback setter name: {}", self.setter_name);
+        }
+        Ok(&self.setter_name[prefix.len()..self.setter_name.len() - suffix.len()])
+    }
+
+    fn argument_names(&self) -> Vec<String> {
+        (0..self.argument_types.len())
+            .map(|num| format!("arg{}", num))
+            .collect()
+    }
+
+    /// Generates the trait method. The method calls the base implementation by default
+    /// and has no default implementation if the base implementation is not available.
+    fn trait_method(&self) -> Result<String> {
+        let arg_names = self.argument_names();
+        let args = self
+            .argument_types
+            .iter()
+            .zip(&arg_names)
+            .map(|(arg_type, name)| format!(", {}: {}", name, arg_type))
+            .join("");
+        let return_type = self
+            .return_type
+            .as_ref()
+            .map_or(String::new(), |t| format!(" -> {}", t));
+        let signature = format!(
+            "unsafe fn {}(&self, this: ::cpp_core::Ptr<{}>{}){}",
+            self.name()?,
+            self.subclass_type,
+            args,
+            return_type
+        );
+        let mut code = format!("    /// Overrides C++ method `{}`.\n", self.cpp_method);
+        if let Some(base_caller_name) = &self.base_caller_name {
+            writeln!(
+                code,
+                "    {} {{\n        this.{}({})\n    }}",
+                signature,
+                base_caller_name,
+                arg_names.join(", ")
+            )?;
+        } else {
+            writeln!(code, "    {};", signature)?;
+        }
+        Ok(code)
+    }
+
+    /// Generates the code that sets the callback forwarding calls to the trait method.
+    fn setter_call(&self) -> Result<String> {
+        let arg_names = self.argument_names();
+        Ok(format!(
+            "        {{\n            \
+             let overrides = ::std::rc::Rc::clone(&overrides);\n            \
+             self.{}(move |{}| unsafe {{ overrides.{}({}) }});\n        \
+             }}\n",
+            self.setter_name,
+            arg_names.join(", "),
+            self.name()?,
+            once("this".to_string()).chain(arg_names).join(", ")
+        ))
+    }
+}
+
+#[test]
+fn subclass_override_code_test() {
+    let mut code = SubclassOverrideCode {
+        cpp_method: "virtual int Widget::setData(int value)".to_string(),
+        subclass_type: "WidgetSubclass".to_string(),
+        setter_name: "set_set_data_callback".to_string(),
+        base_caller_name: Some("base_set_data".to_string()),
+        argument_types: vec!["i32".to_string()],
+        return_type: Some("i32".to_string()),
+    };
+    assert_eq!(code.name().unwrap(), "set_data");
+    assert_eq!(
+        code.trait_method().unwrap(),
+        "    /// Overrides C++ method `virtual int Widget::setData(int value)`.\n    \
+         unsafe fn set_data(&self, this: ::cpp_core::Ptr<WidgetSubclass>, \
+         arg0: i32) -> i32 {\n        \
+         this.base_set_data(arg0)\n    \
+         }\n"
+    );
+    assert_eq!(
+        code.setter_call().unwrap(),
+        "        {\n            \
+         let overrides = ::std::rc::Rc::clone(&overrides);\n            \
+         self.set_set_data_callback(move |arg0| unsafe { \
+         overrides.set_data(this, arg0) });\n        \
+         }\n"
+    );
+
+    // pure virtual methods don't have a default implementation
+    code.base_caller_name = None;
+    code.argument_types.clear();
+    code.return_type = None;
+    code.cpp_method = "virtual void Widget::setData() = 0".to_string();
+    assert_eq!(
+        code.trait_method().unwrap(),
+        "    /// Overrides C++ method `virtual void Widget::setData() = 0`.\n    \
+         unsafe fn set_data(&self, this: ::cpp_core::Ptr<WidgetSubclass>);\n"
+    );
+    assert_eq!(
+        code.setter_call().unwrap(),
+        "        {\n            \
+         let overrides = ::std::rc::Rc::clone(&overrides);\n            \
+         self.set_set_data_callback(move || unsafe { overrides.set_data(this) });\n        \
+         }\n"
+    );
+
+    code.setter_name = "set__callback".to_string();
+    assert!(code.name().is_err());
+}
+
 pub fn generate(
     current_database: &DatabaseClient,
     output_src_path: impl Into<PathBuf>,
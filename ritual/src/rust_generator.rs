@@ -1,14 +1,25 @@
-use crate::config::CrateDependencyKind;
+use crate::config::{
+    CppNullabilityAnnotation, CppOutParam, CppOutParamKind, CppOwnershipTransfer,
+    CrateDependencyKind,
+};
 use crate::cpp_checks::CppChecks;
 use crate::cpp_data::{
-    inherits, CppItem, CppPath, CppPathItem, CppTypeDeclaration, CppTypeDeclarationKind,
+    inherits, CppItem, CppMacroConstant, CppMacroValue, CppPath, CppPathItem, CppTypeDeclaration,
+    CppTypeDeclarationKind,
 };
 use crate::cpp_ffi_data::{
     CppCast, CppFfiArgumentMeaning, CppFfiFunction, CppFfiFunctionKind, CppFfiItem, CppFfiType,
-    CppFieldAccessorType, CppToFfiTypeConversion,
+    CppFieldAccessorType, CppSubclassWrapper, CppToFfiTypeConversion,
 };
 use crate::cpp_ffi_generator::ffi_type;
-use crate::cpp_function::{CppFunction, CppOperator, ReturnValueAllocationPlace};
+use crate::cpp_function::{
+    CppFunction, CppFunctionArgument, CppNullability, CppOperator, ReturnValueAllocationPlace,
+};
+use crate::cpp_hash_functions;
+use crate::cpp_ostream_operators;
+use crate::cpp_smart_pointers::{
+    smart_pointer_functions_target, smart_pointer_target, SmartPointerKind,
+};
 use crate::cpp_type::{
     is_qflags, CppBuiltInNumericType, CppFunctionPointerType, CppPointerLikeTypeKind,
     CppSpecificNumericType, CppSpecificNumericTypeKind, CppType, CppTypeRole,
@@ -16,17 +27,19 @@ use crate::cpp_type::{
 use crate::database::{DbItem, ItemId, ItemWithSource};
 use crate::processor::ProcessorData;
 use crate::rust_info::{
-    NameType, RustEnumValue, RustExtraImpl, RustExtraImplKind, RustFfiWrapperData,
+    NameType, RustConstant, RustEnumValue, RustExtraImpl, RustExtraImplKind, RustFfiWrapperData,
     RustFlagEnumImpl, RustFunction, RustFunctionArgument, RustFunctionCaptionStrategy,
-    RustFunctionKind, RustFunctionSelfArgKind, RustItem, RustModule, RustModuleKind, RustPathScope,
-    RustQtReceiverData, RustQtReceiverImpl, RustQtReceiverType, RustReexport, RustReexportSource,
-    RustSignalOrSlotGetter, RustSizedType, RustSpecialModuleKind, RustStruct, RustStructKind,
-    RustTraitAssociatedType, RustTraitImpl, RustTraitImplExtraKind, RustTypeCaptionStrategy,
-    RustWrapperTypeKind, UnnamedRustFunction,
+    RustFunctionKind, RustFunctionSelfArgKind, RustHashImpl, RustItem, RustModule, RustModuleKind,
+    RustOstreamDisplayImpl, RustPathScope, RustQtReceiverData, RustQtReceiverImpl,
+    RustQtReceiverType, RustReexport, RustReexportSource, RustSignalOrSlotGetter, RustSizedType,
+    RustSmartPointerImpl, RustSpecialModuleKind, RustStruct, RustStructKind,
+    RustSubclassOverridesImpl, RustTraitAssociatedType, RustTraitImpl, RustTraitImplExtraKind,
+    RustTypeAlias, RustTypeCaptionStrategy, RustWrapperTypeKind, UnnamedRustFunction,
 };
 use crate::rust_type::{
     RustClosureToCallbackConversion, RustCommonType, RustFinalType, RustFunctionPointerType,
-    RustPath, RustPointerLikeTypeKind, RustToFfiTypeConversion, RustType,
+    RustOutParam, RustOutParamsConversion, RustPath, RustPointerLikeTypeKind,
+    RustToFfiTypeConversion, RustType,
 };
 use itertools::Itertools;
 use log::{debug, trace};
@@ -87,7 +100,6 @@ enum ProcessedFfiItem {
 enum ReturnTypeConstraint {
     Bool,
     Usize,
-    #[allow(dead_code)]
     Unit,
     Any,
 }
@@ -349,6 +361,126 @@ impl TraitImplInfo {
                 return_type_constraint: ReturnTypeConstraint::Any,
                 target_is_reference: false,
             },
+            CppOperator::AdditionAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::AddAssign",
+                function_name: "add_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::SubtractionAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::SubAssign",
+                function_name: "sub_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::MultiplicationAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::MulAssign",
+                function_name: "mul_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::DivisionAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::DivAssign",
+                function_name: "div_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::ModuloAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::RemAssign",
+                function_name: "rem_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::BitwiseAndAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::BitAndAssign",
+                function_name: "bitand_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::BitwiseOrAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::BitOrAssign",
+                function_name: "bitor_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::BitwiseXorAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::BitXorAssign",
+                function_name: "bitxor_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::BitwiseLeftShiftAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::ShlAssign",
+                function_name: "shl_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
+            CppOperator::BitwiseRightShiftAssignment => TraitImplInfo {
+                trait_path: "cpp_core::ops::ShrAssign",
+                function_name: "shr_assign",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Unit,
+                target_is_reference: false,
+            },
             CppOperator::Conversion(_)
             | CppOperator::Assignment
             | CppOperator::UnaryPlus
@@ -367,22 +499,43 @@ impl TraitImplInfo {
             | CppOperator::New
             | CppOperator::NewArray
             | CppOperator::Delete
-            | CppOperator::DeleteArray
-            | CppOperator::AdditionAssignment
-            | CppOperator::SubtractionAssignment
-            | CppOperator::MultiplicationAssignment
-            | CppOperator::DivisionAssignment
-            | CppOperator::ModuloAssignment
-            | CppOperator::BitwiseAndAssignment
-            | CppOperator::BitwiseOrAssignment
-            | CppOperator::BitwiseXorAssignment
-            | CppOperator::BitwiseLeftShiftAssignment
-            | CppOperator::BitwiseRightShiftAssignment => return None,
+            | CppOperator::DeleteArray => return None,
         })
     }
 
     fn new(function: &CppFunction) -> Option<TraitImplInfo> {
         if let Some(operator) = &function.operator {
+            if let CppOperator::Subscript = operator {
+                let member = function.member.as_ref()?;
+                let info = if member.is_const {
+                    TraitImplInfo {
+                        trait_path: "cpp_core::ops::Index",
+                        function_name: "index",
+                        is_unsafe: true,
+                        is_inherent: true,
+                        self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                        has_output_associated_type: true,
+                        trait_arg_is_second_arg_type: true,
+                        second_arg_is_reference: false,
+                        return_type_constraint: ReturnTypeConstraint::Any,
+                        target_is_reference: false,
+                    }
+                } else {
+                    TraitImplInfo {
+                        trait_path: "cpp_core::ops::IndexMut",
+                        function_name: "index_mut",
+                        is_unsafe: true,
+                        is_inherent: true,
+                        self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                        has_output_associated_type: true,
+                        trait_arg_is_second_arg_type: true,
+                        second_arg_is_reference: false,
+                        return_type_constraint: ReturnTypeConstraint::Any,
+                        target_is_reference: false,
+                    }
+                };
+                return Some(info);
+            }
             return Self::from_operator(operator);
         }
         if let Some(member) = &function.member {
@@ -544,9 +697,320 @@ fn check_trait_impl_uniqueness(
     Ok(())
 }
 
+/// Returns the `#[repr]` type of `rust_item` if it's a native enum
+/// (see `Config::set_native_enums`).
+fn native_enum_underlying_type(rust_item: &RustItem) -> Option<&RustType> {
+    if let RustStructKind::WrapperType(RustWrapperTypeKind::NativeEnum { underlying_type }) =
+        &rust_item.as_struct_ref()?.kind
+    {
+        Some(underlying_type)
+    } else {
+        None
+    }
+}
+
+/// Returns the Rust primitive type that can be used as `#[repr]` of a native enum
+/// with `underlying_type`. Returns `None` if the size of the type is platform-dependent.
+fn native_enum_repr(underlying_type: &CppType) -> Option<String> {
+    let name = match underlying_type {
+        CppType::BuiltInNumeric(numeric) => match numeric {
+            CppBuiltInNumericType::Char | CppBuiltInNumericType::SChar => "i8",
+            CppBuiltInNumericType::UChar => "u8",
+            CppBuiltInNumericType::Short => "i16",
+            CppBuiltInNumericType::UShort => "u16",
+            CppBuiltInNumericType::Int => "i32",
+            CppBuiltInNumericType::UInt => "u32",
+            CppBuiltInNumericType::LongLong => "i64",
+            CppBuiltInNumericType::ULongLong => "u64",
+            _ => return None,
+        },
+        CppType::SpecificNumeric(CppSpecificNumericType {
+            bits,
+            kind: CppSpecificNumericTypeKind::Integer { is_signed },
+            ..
+        }) => return Some(format!("{}{}", if *is_signed { "i" } else { "u" }, bits)),
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+#[test]
+fn native_enum_repr_test() {
+    assert_eq!(
+        native_enum_repr(&CppType::BuiltInNumeric(CppBuiltInNumericType::UChar)),
+        Some("u8".to_string())
+    );
+    assert_eq!(
+        native_enum_repr(&CppType::BuiltInNumeric(CppBuiltInNumericType::Int)),
+        Some("i32".to_string())
+    );
+    assert_eq!(
+        native_enum_repr(&CppType::BuiltInNumeric(CppBuiltInNumericType::Long)),
+        None
+    );
+}
+
+/// Returns the kind of the out-parameter annotation that applies to
+/// `argument` of `function`, if any.
+fn cpp_out_param_kind(
+    out_params: &[CppOutParam],
+    function: &CppFunction,
+    argument: &CppFunctionArgument,
+) -> Option<CppOutParamKind> {
+    let function_path = function.path.to_cpp_pseudo_code();
+    out_params
+        .iter()
+        .find(|param| param.function == function_path && param.argument == argument.name)
+        .map(|param| param.kind)
+}
+
+#[test]
+fn cpp_out_param_kind_test() {
+    let int_ptr = CppType::new_pointer(false, CppType::BuiltInNumeric(CppBuiltInNumericType::Int));
+    let function = CppFunction {
+        path: CppPath::from_good_str("ns::getRange"),
+        member: None,
+        operator: None,
+        return_type: CppType::Void,
+        arguments: vec![
+            CppFunctionArgument {
+                name: "min".to_string(),
+                argument_type: int_ptr.clone(),
+                has_default_value: false,
+                nullability: None,
+            },
+            CppFunctionArgument {
+                name: "max".to_string(),
+                argument_type: int_ptr,
+                has_default_value: false,
+                nullability: None,
+            },
+        ],
+        allows_variadic_arguments: false,
+        declaration_code: None,
+        deprecation: None,
+        cast: None,
+        return_nullability: None,
+    };
+    let out_params = vec![
+        CppOutParam {
+            function: "ns::getRange".to_string(),
+            argument: "max".to_string(),
+            kind: CppOutParamKind::Value,
+        },
+        CppOutParam {
+            function: "getRange".to_string(),
+            argument: "min".to_string(),
+            kind: CppOutParamKind::Value,
+        },
+    ];
+    assert_eq!(
+        cpp_out_param_kind(&out_params, &function, &function.arguments[0]),
+        None
+    );
+    assert_eq!(
+        cpp_out_param_kind(&out_params, &function, &function.arguments[1]),
+        Some(CppOutParamKind::Value)
+    );
+}
+
+/// Returns nullability of `argument` of `function` (or its return value
+/// if `argument` is `None`) specified by `annotations` or by clang attributes.
+fn cpp_nullability(
+    annotations: &[CppNullabilityAnnotation],
+    function: &CppFunction,
+    argument: Option<&CppFunctionArgument>,
+) -> Option<CppNullability> {
+    let function_path = function.path.to_cpp_pseudo_code();
+    let argument_name = argument.map(|arg| arg.name.as_str());
+    let annotation = annotations.iter().find(|annotation| {
+        annotation.function == function_path
+            && annotation.argument.as_ref().map(String::as_str) == argument_name
+    });
+    if let Some(annotation) = annotation {
+        return Some(annotation.nullability);
+    }
+    match argument {
+        Some(arg) => arg.nullability,
+        None => function.return_nullability,
+    }
+}
+
+/// Returns the conversion that replaces `Ptr<T>` produced by `conversion`
+/// with `Ref<T>` or `Option<Ref<T>>`, depending on `nullability`.
+/// Returns `None` if `conversion` doesn't produce `Ptr<T>`.
+fn nullable_pointer_conversion(
+    conversion: &RustToFfiTypeConversion,
+    nullability: CppNullability,
+) -> Option<RustToFfiTypeConversion> {
+    let is_argument = match conversion {
+        RustToFfiTypeConversion::UtilsPtrToPtr {} => false,
+        RustToFfiTypeConversion::ImplCastInto(inner) => match **inner {
+            RustToFfiTypeConversion::UtilsPtrToPtr {} => true,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(match nullability {
+        CppNullability::NonNull => {
+            if is_argument {
+                RustToFfiTypeConversion::ImplCastInto(Box::new(
+                    RustToFfiTypeConversion::UtilsRefToPtr {},
+                ))
+            } else {
+                RustToFfiTypeConversion::UtilsRefToPtr {}
+            }
+        }
+        CppNullability::Nullable => RustToFfiTypeConversion::OptionUtilsRefToPtr {},
+    })
+}
+
+/// Returns the conversion of an argument with `ffi_type` that accepts Rust strings:
+/// `impl CastInto<Ptr<c_char>>` (accepting `&CStr`) for `const char*` and
+/// `impl CastInto<RefOrBox<T>>` (accepting `&str`) for a class from `string_types`
+/// passed by value or by constant reference. Returns `None` for other arguments.
+fn string_argument_conversion(
+    ffi_type: &CppType,
+    conversion: &RustToFfiTypeConversion,
+    string_types: &[CppPath],
+) -> Option<RustToFfiTypeConversion> {
+    let target = if let CppType::PointerLike {
+        kind: CppPointerLikeTypeKind::Pointer,
+        is_const: true,
+        target,
+    } = ffi_type
+    {
+        target
+    } else {
+        return None;
+    };
+    let inner = match &**target {
+        CppType::BuiltInNumeric(CppBuiltInNumericType::Char)
+            if conversion == &RustToFfiTypeConversion::None =>
+        {
+            RustToFfiTypeConversion::UtilsPtrToPtr {}
+        }
+        CppType::Class(path)
+            if string_types.contains(path)
+                && conversion
+                    == &RustToFfiTypeConversion::ImplCastInto(Box::new(
+                        RustToFfiTypeConversion::UtilsRefToPtr {},
+                    )) =>
+        {
+            RustToFfiTypeConversion::RefOrBoxToPtr
+        }
+        _ => return None,
+    };
+    Some(RustToFfiTypeConversion::ImplCastInto(Box::new(inner)))
+}
+
+#[test]
+fn string_argument_conversion_test() {
+    let string_types = [CppPath::from_good_str("QString")];
+    let q_string = CppType::Class(CppPath::from_good_str("QString"));
+    let ref_arg =
+        RustToFfiTypeConversion::ImplCastInto(Box::new(RustToFfiTypeConversion::UtilsRefToPtr {}));
+    assert_eq!(
+        string_argument_conversion(
+            &CppType::new_pointer(true, q_string.clone()),
+            &ref_arg,
+            &string_types
+        ),
+        Some(RustToFfiTypeConversion::ImplCastInto(Box::new(
+            RustToFfiTypeConversion::RefOrBoxToPtr
+        )))
+    );
+    // non-const references may be modified by the function
+    assert_eq!(
+        string_argument_conversion(
+            &CppType::new_pointer(false, q_string),
+            &ref_arg,
+            &string_types
+        ),
+        None
+    );
+    assert_eq!(
+        string_argument_conversion(
+            &CppType::new_pointer(true, CppType::Class(CppPath::from_good_str("QPoint"))),
+            &ref_arg,
+            &string_types
+        ),
+        None
+    );
+
+    let char_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Char);
+    assert_eq!(
+        string_argument_conversion(
+            &CppType::new_pointer(true, char_type.clone()),
+            &RustToFfiTypeConversion::None,
+            &string_types
+        ),
+        Some(RustToFfiTypeConversion::ImplCastInto(Box::new(
+            RustToFfiTypeConversion::UtilsPtrToPtr {}
+        )))
+    );
+    assert_eq!(
+        string_argument_conversion(
+            &CppType::new_pointer(false, char_type),
+            &RustToFfiTypeConversion::None,
+            &string_types
+        ),
+        None
+    );
+}
+
+#[test]
+fn nullable_pointer_conversion_test() {
+    let ptr_arg =
+        RustToFfiTypeConversion::ImplCastInto(Box::new(RustToFfiTypeConversion::UtilsPtrToPtr {}));
+    assert_eq!(
+        nullable_pointer_conversion(&ptr_arg, CppNullability::NonNull),
+        Some(RustToFfiTypeConversion::ImplCastInto(Box::new(
+            RustToFfiTypeConversion::UtilsRefToPtr {}
+        )))
+    );
+    assert_eq!(
+        nullable_pointer_conversion(&ptr_arg, CppNullability::Nullable),
+        Some(RustToFfiTypeConversion::OptionUtilsRefToPtr {})
+    );
+    assert_eq!(
+        nullable_pointer_conversion(
+            &RustToFfiTypeConversion::UtilsPtrToPtr {},
+            CppNullability::NonNull
+        ),
+        Some(RustToFfiTypeConversion::UtilsRefToPtr {})
+    );
+    // `QPtr<T>` is not affected
+    assert_eq!(
+        nullable_pointer_conversion(&RustToFfiTypeConversion::QPtrToPtr, CppNullability::NonNull),
+        None
+    );
+}
+
+/// Returns true if `function` takes ownership of the object passed
+/// as the argument with `index` according to `transfers`.
+fn is_cpp_ownership_transfer(
+    transfers: &[CppOwnershipTransfer],
+    function: &CppFunction,
+    index: usize,
+) -> bool {
+    let function_path = function.path.to_cpp_pseudo_code();
+    transfers
+        .iter()
+        .any(|transfer| transfer.function == function_path && transfer.argument == index)
+}
+
 struct State<'b, 'a> {
     data: &'b mut ProcessorData<'a>,
     special_module_paths: HashMap<RustSpecialModuleKind, RustPath>,
+    /// FFI items of the helper functions added by `cpp_smart_pointers`,
+    /// indexed by the helper kind and the pointee class
+    smart_pointer_ffi_items: HashMap<(SmartPointerKind, CppPath), ItemId>,
+    /// Paths of the modules containing macro constants, indexed by the header name
+    header_module_paths: HashMap<String, RustPath>,
+    /// C++ functions taking rvalue references that have an overload
+    /// taking const references instead
+    rvalue_reference_overloads: HashSet<ItemId>,
 }
 
 impl State<'_, '_> {
@@ -558,7 +1022,14 @@ impl State<'_, '_> {
                 is_const,
                 target,
             } => {
-                let rust_target = if target.deref() == &CppType::Void {
+                let is_smart_pointer = if let CppType::Class(path) = target.deref() {
+                    smart_pointer_target(path).is_some()
+                } else {
+                    false
+                };
+                // smart pointer objects are opaque to Rust and are only
+                // accessed through `cpp_core::SharedPtrFunctions` and similar tables
+                let rust_target = if target.deref() == &CppType::Void || is_smart_pointer {
                     RustType::Common(RustCommonType {
                         path: RustPath::from_good_str("std::ffi::c_void"),
                         generic_arguments: None,
@@ -646,6 +1117,11 @@ impl State<'_, '_> {
             }
             CppType::Enum { path } | CppType::Class(path) => {
                 let rust_item = self.find_wrapper_type(path)?;
+                if let Some(underlying_type) = native_enum_underlying_type(rust_item.item) {
+                    // a native enum can't hold values that don't match its variants,
+                    // so the underlying integer type is used in FFI
+                    return Ok(underlying_type.clone());
+                }
                 let path = rust_item
                     .item
                     .path()
@@ -678,7 +1154,9 @@ impl State<'_, '_> {
                     generic_arguments: Some(vec![pointer]),
                 })
             }
-            CppType::TemplateParameter { .. } => bail!("invalid cpp type"),
+            CppType::TemplateParameter { .. }
+            | CppType::IntegralConstant(_)
+            | CppType::StdFunction(_) => bail!("invalid cpp type"),
         };
 
         Ok(rust_type)
@@ -700,6 +1178,388 @@ impl State<'_, '_> {
         })
     }
 
+    /// Returns `cpp_core::Flags<E, T>` type that replaces integer type `cpp_ffi_type`
+    /// for a value marked by `Config::set_cpp_flags_hook`. `cpp_ffi_type` must have
+    /// the same size as the underlying type of the enum, so that conversions are lossless.
+    fn flags_final_type(
+        &self,
+        cpp_ffi_type: &CppFfiType,
+        rust_type: &RustFinalType,
+        enum_path: &CppPath,
+    ) -> Result<RustFinalType> {
+        let is_integer = match cpp_ffi_type.ffi_type() {
+            CppType::BuiltInNumeric(numeric) => {
+                numeric.is_signed_integer() || numeric.is_unsigned_integer()
+            }
+            CppType::SpecificNumeric(CppSpecificNumericType { kind, .. }) => {
+                kind != &CppSpecificNumericTypeKind::FloatingPoint
+            }
+            _ => false,
+        };
+        if !is_integer || rust_type.conversion() != &RustToFfiTypeConversion::None {
+            bail!(
+                "flags must be an integer type, got {}",
+                cpp_ffi_type.original_type().to_cpp_pseudo_code()
+            );
+        }
+        let rust_enum_type = self.find_wrapper_type(enum_path)?;
+        let rust_enum_path = rust_enum_type.item.path().ok_or_else(|| {
+            format_err!(
+                "failed to get path from Rust enum type: {:?}",
+                rust_enum_type
+            )
+        })?;
+        let underlying_type = self
+            .data
+            .db
+            .source_cpp_item(&rust_enum_type.id)?
+            .and_then(|item| item.item.as_type_ref())
+            .and_then(|type1| {
+                if let CppTypeDeclarationKind::Enum {
+                    underlying_type, ..
+                } = &type1.kind
+                {
+                    Some(underlying_type)
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| format_err!("not an enum: {}", enum_path.to_cpp_pseudo_code()))?;
+        let ffi_type = cpp_ffi_type.ffi_type();
+        let is_same_size = ffi_type == underlying_type
+            || match (integer_bits(ffi_type), integer_bits(underlying_type)) {
+                (Some(bits1), Some(bits2)) => bits1 == bits2,
+                _ => false,
+            };
+        if !is_same_size {
+            bail!(
+                "flags type {} doesn't match underlying type {} of enum {}",
+                ffi_type.to_cpp_pseudo_code(),
+                underlying_type.to_cpp_pseudo_code(),
+                enum_path.to_cpp_pseudo_code()
+            );
+        }
+        let api_type = cpp_core_flags_type(
+            rust_enum_path,
+            self.ffi_type_to_rust_ffi_type(underlying_type)?,
+        );
+        RustFinalType::new(
+            rust_type.ffi_type().clone(),
+            RustToFfiTypeConversion::FlagsToInt { api_type },
+        )
+    }
+
+    /// Replaces types of arguments and return value marked by `Config::set_cpp_flags_hook`
+    /// with `cpp_core::Flags<E>`.
+    fn apply_cpp_flags_hook(
+        &self,
+        function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+        arguments: &mut [RustFunctionArgument],
+        return_type: &mut RustFinalType,
+    ) -> Result<()> {
+        let hook = if let Some(hook) = self.data.config.cpp_flags_hook() {
+            hook
+        } else {
+            return Ok(());
+        };
+        for arg in arguments {
+            let ffi_arg = &function.arguments[arg.ffi_index];
+            if let CppFfiArgumentMeaning::Argument(index) = ffi_arg.meaning {
+                if let Some(enum_path) = hook(cpp_function, Some(index))? {
+                    arg.argument_type = self.flags_final_type(
+                        &ffi_arg.argument_type,
+                        &arg.argument_type,
+                        &enum_path,
+                    )?;
+                }
+            }
+        }
+        if let Some(enum_path) = hook(cpp_function, None)? {
+            *return_type = self.flags_final_type(&function.return_type, return_type, &enum_path)?;
+        }
+        Ok(())
+    }
+
+    /// Removes arguments marked by `Config::add_cpp_out_param` from `arguments`
+    /// and returns the conversion that adds their values to the return type.
+    /// Returns `None` if the function doesn't have out-parameters.
+    fn apply_cpp_out_params(
+        &self,
+        id: &ItemId,
+        function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+        arguments: &mut Vec<RustFunctionArgument>,
+    ) -> Result<Option<RustOutParamsConversion>> {
+        let out_params = self.data.config.cpp_out_params();
+        if out_params.is_empty() {
+            return Ok(None);
+        }
+        if let Some(original) = self
+            .data
+            .db
+            .original_cpp_item(id)?
+            .and_then(|item| item.item.as_function_ref())
+        {
+            let omitted = original.arguments.iter().skip(cpp_function.arguments.len());
+            for arg in omitted {
+                if cpp_out_param_kind(out_params, original, arg).is_some() {
+                    bail!("omitted argument is an out-parameter: {}", arg.name);
+                }
+            }
+        }
+
+        let mut values = Vec::new();
+        let mut ok_flag = None;
+        let mut other_arguments = Vec::new();
+        for arg in arguments.drain(..) {
+            let ffi_arg = &function.arguments[arg.ffi_index];
+            let kind = if let CppFfiArgumentMeaning::Argument(index) = ffi_arg.meaning {
+                cpp_out_param_kind(out_params, cpp_function, &cpp_function.arguments[index])
+            } else {
+                None
+            };
+            let kind = if let Some(kind) = kind {
+                kind
+            } else {
+                other_arguments.push(arg);
+                continue;
+            };
+
+            let original_type = ffi_arg.argument_type.original_type();
+            let target = match original_type {
+                CppType::PointerLike {
+                    kind: CppPointerLikeTypeKind::Pointer,
+                    is_const: false,
+                    target,
+                }
+                | CppType::PointerLike {
+                    kind: CppPointerLikeTypeKind::Reference,
+                    is_const: false,
+                    target,
+                } => &**target,
+                _ => bail!(
+                    "out-parameter must be a non-const pointer or reference, got {}",
+                    original_type.to_cpp_pseudo_code()
+                ),
+            };
+            match target {
+                CppType::BuiltInNumeric(_)
+                | CppType::SpecificNumeric(_)
+                | CppType::PointerSizedInteger { .. } => {}
+                _ => bail!(
+                    "out-parameter must point to a primitive type, got {}",
+                    original_type.to_cpp_pseudo_code()
+                ),
+            }
+            let param = RustOutParam {
+                ffi_index: arg.ffi_index,
+                name: arg.name,
+                value_type: arg.argument_type.ffi_type().pointer_like_to_target()?,
+            };
+            match kind {
+                CppOutParamKind::Value => values.push(param),
+                CppOutParamKind::OkFlag => {
+                    if target != &CppType::BuiltInNumeric(CppBuiltInNumericType::Bool) {
+                        bail!(
+                            "ok flag must point to bool, got {}",
+                            original_type.to_cpp_pseudo_code()
+                        );
+                    }
+                    if ok_flag.is_some() {
+                        bail!("multiple ok flags are not supported");
+                    }
+                    ok_flag = Some(param);
+                }
+            }
+        }
+        *arguments = other_arguments;
+        if values.is_empty() && ok_flag.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(RustOutParamsConversion {
+            return_conversion: RustToFfiTypeConversion::None,
+            values,
+            ok_flag,
+        }))
+    }
+
+    /// Replaces `Ptr<T>` types of arguments and return value with `Ref<T>` or
+    /// `Option<Ref<T>>` if their nullability is known.
+    fn apply_cpp_nullability(
+        &self,
+        function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+        arguments: &mut [RustFunctionArgument],
+        return_type: &mut RustFinalType,
+    ) -> Result<()> {
+        let annotations = self.data.config.cpp_nullability();
+        for arg in arguments {
+            let ffi_arg = &function.arguments[arg.ffi_index];
+            if let CppFfiArgumentMeaning::Argument(index) = ffi_arg.meaning {
+                let cpp_arg = &cpp_function.arguments[index];
+                if let Some(nullability) = cpp_nullability(annotations, cpp_function, Some(cpp_arg))
+                {
+                    if let Some(conversion) =
+                        nullable_pointer_conversion(arg.argument_type.conversion(), nullability)
+                    {
+                        arg.argument_type =
+                            RustFinalType::new(arg.argument_type.ffi_type().clone(), conversion)?;
+                    }
+                }
+            }
+        }
+        if let Some(nullability) = cpp_nullability(annotations, cpp_function, None) {
+            if let Some(conversion) =
+                nullable_pointer_conversion(return_type.conversion(), nullability)
+            {
+                *return_type = RustFinalType::new(return_type.ffi_type().clone(), conversion)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces `impl CastInto<Ptr<T>>` types of arguments annotated with
+    /// `Config::add_cpp_ownership_transfer` with `CppBox<U>` or `QBox<U>`
+    /// (where `U: StaticUpcast<T>`), so that the object is consumed by the function.
+    fn apply_cpp_ownership_transfers(
+        &self,
+        function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+        arguments: &mut [RustFunctionArgument],
+        checks: &CppChecks,
+    ) -> Result<()> {
+        let transfers = self.data.config.cpp_ownership_transfers();
+        let mut type_parameters_count = 0;
+        for arg in arguments {
+            let ffi_arg = &function.arguments[arg.ffi_index];
+            let index = if let CppFfiArgumentMeaning::Argument(index) = ffi_arg.meaning {
+                index
+            } else {
+                continue;
+            };
+            if !is_cpp_ownership_transfer(transfers, cpp_function, index) {
+                continue;
+            }
+            let is_mut_ptr = match ffi_arg.argument_type.ffi_type() {
+                CppType::PointerLike {
+                    kind: CppPointerLikeTypeKind::Pointer,
+                    is_const: false,
+                    ..
+                } => true,
+                _ => false,
+            };
+            let is_ptr_conversion = arg.argument_type.conversion()
+                == &RustToFfiTypeConversion::ImplCastInto(Box::new(
+                    RustToFfiTypeConversion::UtilsPtrToPtr {},
+                ));
+            if !is_mut_ptr || !is_ptr_conversion {
+                trace!(
+                    "ownership transfer annotation ignored for argument {} of {}: \
+                     not a non-const pointer to class",
+                    index,
+                    cpp_function.short_text()
+                );
+                continue;
+            }
+            let ffi_type = ffi_arg.argument_type.ffi_type();
+            if let Some(conversion) = self.box_conversion(ffi_type, checks)? {
+                // Accept boxes of derived classes as well. The pointer is converted
+                // to the base class only after the box is released, so the object
+                // is never deleted through a non-virtual base class destructor.
+                type_parameters_count += 1;
+                let type_parameter = if type_parameters_count == 1 {
+                    "U".to_string()
+                } else {
+                    format!("U{}", type_parameters_count)
+                };
+                arg.argument_type = RustFinalType::new(
+                    arg.argument_type.ffi_type().clone(),
+                    RustToFfiTypeConversion::UpcastBoxToPtr {
+                        box_conversion: Box::new(conversion),
+                        type_parameter,
+                    },
+                )?;
+            } else {
+                trace!(
+                    "ownership transfer annotation ignored for argument {} of {}: \
+                     type is not deletable",
+                    index,
+                    cpp_function.short_text()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces types of `const char*` arguments and arguments of string types
+    /// added by `Config::add_cpp_string_type`, so that they accept Rust strings.
+    fn apply_cpp_string_types(
+        &self,
+        function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+        arguments: &mut [RustFunctionArgument],
+    ) -> Result<()> {
+        if cpp_function.is_operator() {
+            // keep argument types of operator trait implementations
+            return Ok(());
+        }
+        let string_types = self.data.config.cpp_string_types();
+        for arg in arguments {
+            let ffi_arg = &function.arguments[arg.ffi_index];
+            let is_argument = match ffi_arg.meaning {
+                CppFfiArgumentMeaning::Argument(_) => true,
+                _ => false,
+            };
+            if !is_argument {
+                continue;
+            }
+            if let Some(conversion) = string_argument_conversion(
+                ffi_arg.argument_type.ffi_type(),
+                arg.argument_type.conversion(),
+                string_types,
+            ) {
+                arg.argument_type =
+                    RustFinalType::new(arg.argument_type.ffi_type().clone(), conversion)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `CppBoxToPtr` or `QBoxToPtr` conversion for a pointer to
+    /// an owned object of a class, or `None` if the class can't be deleted.
+    fn box_conversion(
+        &self,
+        ffi_type: &CppType,
+        checks: &CppChecks,
+    ) -> Result<Option<RustToFfiTypeConversion>> {
+        if !self.is_type_deletable(ffi_type, checks)? {
+            return Ok(None);
+        }
+        let inherits_qobject = if let CppType::Class(path) = ffi_type.pointer_like_to_target()? {
+            inherits(&self.data.db, &path, &CppPath::from_good_str("QObject"))
+        } else {
+            false
+        };
+        Ok(Some(if inherits_qobject {
+            RustToFfiTypeConversion::QBoxToPtr
+        } else {
+            RustToFfiTypeConversion::CppBoxToPtr
+        }))
+    }
+
+    /// Returns true if the source C++ function of `function` takes rvalue references
+    /// and there is an overload taking const references instead.
+    fn has_const_reference_overload(&self, function: &DbItem<&CppFfiFunction>) -> Result<bool> {
+        Ok(self
+            .data
+            .db
+            .source_cpp_item(&function.id)?
+            .map_or(false, |item| {
+                self.rvalue_reference_overloads.contains(&item.id)
+            }))
+    }
+
     fn is_type_deletable(&self, ffi_type: &CppType, checks: &CppChecks) -> Result<bool> {
         debug!(
             "is_type_deletable(ffi_type={:?}, checks={:?}",
@@ -748,6 +1608,106 @@ impl State<'_, '_> {
         Ok(is_deletable)
     }
 
+    /// Returns true if the helper function that operates on smart pointers to `class_path`
+    /// is available whenever `checks` pass.
+    fn has_smart_pointer_functions(
+        &self,
+        kind: SmartPointerKind,
+        class_path: &CppPath,
+        checks: &CppChecks,
+    ) -> Result<bool> {
+        // `std::weak_ptr<T>` uses the helper of `std::shared_ptr<T>`
+        let helper_kind = if kind == SmartPointerKind::Weak {
+            SmartPointerKind::Shared
+        } else {
+            kind
+        };
+        let ffi_id = if let Some(id) = self
+            .smart_pointer_ffi_items
+            .get(&(helper_kind, class_path.clone()))
+        {
+            id
+        } else {
+            debug!("    ffi item for smart pointer functions not found");
+            return Ok(false);
+        };
+        let helper_checks = self.data.db.cpp_checks(ffi_id)?;
+        Ok(!helper_checks.is_empty() && helper_checks.is_always_success_for(checks))
+    }
+
+    /// Returns conversion for a `std::shared_ptr<T>`, `std::weak_ptr<T>`
+    /// or `std::unique_ptr<T>` value or reference.
+    fn smart_pointer_conversion(
+        &self,
+        cpp_ffi_type: &CppFfiType,
+        argument_meaning: &CppFfiArgumentMeaning,
+        allocation_place: ReturnValueAllocationPlace,
+        checks: Option<&CppChecks>,
+        kind: SmartPointerKind,
+        class_path: &CppPath,
+    ) -> Result<RustToFfiTypeConversion> {
+        if let Some(checks) = checks {
+            if !self.has_smart_pointer_functions(kind, class_path, checks)? {
+                bail!(
+                    "smart pointer functions are not available for {}",
+                    class_path.to_cpp_pseudo_code()
+                );
+            }
+        }
+        let target_path = self
+            .find_wrapper_type(class_path)?
+            .item
+            .path()
+            .ok_or_else(|| err_msg("RustDatabaseItem for class has no path"))?
+            .clone();
+        let api_type = RustType::Common(RustCommonType {
+            path: RustPath::from_good_str(kind.rust_type_path()),
+            generic_arguments: Some(vec![RustType::Common(RustCommonType {
+                path: target_path,
+                generic_arguments: None,
+            })]),
+        });
+        let owned = RustToFfiTypeConversion::SmartPtrToPtr { api_type };
+
+        let is_return_value = argument_meaning == &CppFfiArgumentMeaning::ReturnValue;
+        let is_const = match cpp_ffi_type.ffi_type() {
+            CppType::PointerLike { is_const, .. } => *is_const,
+            _ => false,
+        };
+        let conversion = match (cpp_ffi_type.conversion(), is_return_value) {
+            (CppToFfiTypeConversion::ValueToPointer { .. }, true) => {
+                if allocation_place != ReturnValueAllocationPlace::Heap {
+                    bail!("smart pointers can only be returned in a heap-allocated object");
+                }
+                owned
+            }
+            (CppToFfiTypeConversion::ValueToPointer { .. }, false)
+            | (CppToFfiTypeConversion::ReferenceToPointer, false)
+                if is_const =>
+            {
+                // the object is copied (or only read) by the FFI function
+                if kind == SmartPointerKind::Shared {
+                    RustToFfiTypeConversion::ImplCastInto(Box::new(owned))
+                } else {
+                    RustToFfiTypeConversion::RefTo(Box::new(owned))
+                }
+            }
+            (CppToFfiTypeConversion::RValueReferenceToPointer, false) => {
+                // the object is moved into the argument and deleted by the FFI function
+                if kind == SmartPointerKind::Shared {
+                    RustToFfiTypeConversion::ImplCastInto(Box::new(owned))
+                } else {
+                    owned
+                }
+            }
+            _ => bail!(
+                "unsupported smart pointer type: {}",
+                cpp_ffi_type.original_type().to_cpp_pseudo_code()
+            ),
+        };
+        Ok(conversion)
+    }
+
     /// Generates `CompleteType` from `CppFfiType`, adding
     /// Rust API type, Rust FFI type and conversion between them.
     #[allow(clippy::collapsible_if)]
@@ -759,6 +1719,23 @@ impl State<'_, '_> {
         checks: Option<&CppChecks>,
     ) -> Result<RustFinalType> {
         let rust_ffi_type = self.ffi_type_to_rust_ffi_type(cpp_ffi_type.ffi_type())?;
+        let value_type = match cpp_ffi_type.original_type() {
+            CppType::PointerLike { target, .. } => &**target,
+            other => other,
+        };
+        if let CppType::Class(path) = value_type {
+            if let Some((kind, class_path)) = smart_pointer_target(path) {
+                let conversion = self.smart_pointer_conversion(
+                    cpp_ffi_type,
+                    argument_meaning,
+                    allocation_place,
+                    checks,
+                    kind,
+                    class_path,
+                )?;
+                return RustFinalType::new(rust_ffi_type, conversion);
+            }
+        }
         let mut api_to_ffi_conversion = RustToFfiTypeConversion::None;
         if let RustType::PointerLike { .. } = &rust_ffi_type {
             let target = cpp_ffi_type.ffi_type().pointer_like_to_target()?;
@@ -768,7 +1745,20 @@ impl State<'_, '_> {
                 false
             };
 
-            if let CppToFfiTypeConversion::ValueToPointer { .. } = cpp_ffi_type.conversion() {
+            if cpp_ffi_type.conversion() == &CppToFfiTypeConversion::RValueReferenceToPointer {
+                // the object is moved into the argument and deleted by the FFI function,
+                // so the box is consumed
+                let is_deletable = if let Some(checks) = checks {
+                    self.is_type_deletable(cpp_ffi_type.ffi_type(), checks)?
+                } else {
+                    true
+                };
+                if !is_deletable {
+                    bail!("rvalue reference to a class that can't be deleted");
+                }
+                api_to_ffi_conversion = RustToFfiTypeConversion::CppBoxToPtr;
+            } else if let CppToFfiTypeConversion::ValueToPointer { .. } = cpp_ffi_type.conversion()
+            {
                 if argument_meaning == &CppFfiArgumentMeaning::ReturnValue {
                     match allocation_place {
                         ReturnValueAllocationPlace::Stack => {
@@ -842,6 +1832,21 @@ impl State<'_, '_> {
                 }
             }
         }
+        if let CppType::Enum { path } = cpp_ffi_type.ffi_type() {
+            let rust_item = self.find_wrapper_type(path)?;
+            if native_enum_underlying_type(rust_item.item).is_some() {
+                let rust_path = rust_item
+                    .item
+                    .path()
+                    .ok_or_else(|| err_msg("native enum item has no path"))?;
+                api_to_ffi_conversion = RustToFfiTypeConversion::NativeEnumToInt {
+                    api_type: RustType::Common(RustCommonType {
+                        path: rust_path.clone(),
+                        generic_arguments: None,
+                    }),
+                };
+            }
+        }
         if cpp_ffi_type.conversion() == &CppToFfiTypeConversion::QFlagsToInt {
             let qflags_type = match cpp_ffi_type.original_type() {
                 CppType::PointerLike {
@@ -894,6 +1899,16 @@ impl State<'_, '_> {
                 api_type: self.create_qflags(rust_enum_path),
             };
         };
+        if let CppToFfiTypeConversion::StdFunctionToCallback {
+            arguments,
+            return_type,
+        } = cpp_ffi_type.conversion()
+        {
+            // `std::function` argument is constructed from a Rust closure
+            let conversion = self.closure_to_callback_conversion(arguments, return_type, checks)?;
+            api_to_ffi_conversion =
+                RustToFfiTypeConversion::ClosureToCallback(Box::new(conversion));
+        }
 
         RustFinalType::new(rust_ffi_type, api_to_ffi_conversion)
     }
@@ -1117,9 +2132,211 @@ impl State<'_, '_> {
                 path: trait_path,
                 generic_arguments: None,
             },
-            associated_types: Vec::new(),
-            functions: vec![function],
-            extra_kind: RustTraitImplExtraKind::Normal,
+            associated_types: Vec::new(),
+            functions: vec![function],
+            extra_kind: RustTraitImplExtraKind::Normal,
+        })
+    }
+
+    /// Generates an implementation of a `cpp_core` trait (`trait_path`) for `target_type`
+    /// with a single unsafe function `function_name` wrapping a constructor.
+    fn constructor_trait_impl(
+        function: UnnamedRustFunction,
+        allocation_place: ReturnValueAllocationPlace,
+        target_type: RustType,
+        trait_path: RustPath,
+        function_name: &str,
+        crate_name: &str,
+        trait_types: &[TraitTypes],
+    ) -> Result<RustTraitImpl> {
+        if allocation_place != ReturnValueAllocationPlace::Heap {
+            bail!(
+                "{} is only implemented for heap-allocated types",
+                trait_path.last()
+            );
+        }
+        if let RustToFfiTypeConversion::CppExceptionResult(_) = function.return_type.conversion() {
+            bail!(
+                "{} can't be implemented with a function that catches exceptions",
+                trait_path.last()
+            );
+        }
+
+        let parent_path = if let RustType::Common(RustCommonType { path, .. }) = &target_type {
+            if path.crate_name() != crate_name {
+                bail!("self type is outside current crate");
+            }
+            path.parent()?
+        } else {
+            bail!("can't get parent for target_type: {:?}", target_type);
+        };
+
+        let trait_type = RustCommonType {
+            path: trait_path.clone(),
+            generic_arguments: None,
+        };
+        check_trait_impl_uniqueness(trait_types, &target_type, &trait_type)?;
+
+        let mut function = function.with_path(trait_path.join(function_name));
+        function.is_unsafe = true;
+
+        Ok(RustTraitImpl {
+            target_type,
+            parent_path,
+            trait_type,
+            associated_types: Vec::new(),
+            functions: vec![function],
+            extra_kind: RustTraitImplExtraKind::Normal,
+        })
+    }
+
+    fn process_copy_constructor(
+        mut unnamed_function: UnnamedRustFunction,
+        allocation_place: ReturnValueAllocationPlace,
+        crate_name: &str,
+        trait_types: &[TraitTypes],
+    ) -> Result<RustTraitImpl> {
+        if unnamed_function.arguments.len() != 1 {
+            bail!("copy constructor must have one argument");
+        }
+        let target_type = unnamed_function.arguments[0]
+            .argument_type
+            .ffi_type()
+            .pointer_like_to_target()?;
+
+        let arg = &mut unnamed_function.arguments[0];
+        arg.argument_type = RustFinalType::new(
+            arg.argument_type.ffi_type().clone(),
+            RustToFfiTypeConversion::RefToPtr { lifetime: None },
+        )?;
+        arg.name = "self".to_string();
+
+        State::constructor_trait_impl(
+            unnamed_function,
+            allocation_place,
+            target_type,
+            RustPath::from_good_str("cpp_core::CppClone"),
+            "clone",
+            crate_name,
+            trait_types,
+        )
+    }
+
+    fn process_default_constructor(
+        unnamed_function: UnnamedRustFunction,
+        allocation_place: ReturnValueAllocationPlace,
+        crate_name: &str,
+        trait_types: &[TraitTypes],
+    ) -> Result<RustTraitImpl> {
+        if !unnamed_function.arguments.is_empty() {
+            bail!("default constructor must not have arguments");
+        }
+        let target_type = unnamed_function
+            .return_type
+            .ffi_type()
+            .pointer_like_to_target()?;
+
+        State::constructor_trait_impl(
+            unnamed_function,
+            allocation_place,
+            target_type,
+            RustPath::from_good_str("cpp_core::CppDefault"),
+            "default",
+            crate_name,
+            trait_types,
+        )
+    }
+
+    /// Returns paths to the target type and the FFI function of a `ritual::*<T>(const T&)`
+    /// helper function used to implement a trait for `T`.
+    fn helper_function_paths(
+        unnamed_function: &UnnamedRustFunction,
+        crate_name: &str,
+    ) -> Result<(RustPath, RustPath)> {
+        if unnamed_function.arguments.len() != 1 {
+            bail!("helper function must have one argument");
+        }
+        let ffi_function_path = if let RustFunctionKind::FfiWrapper(data) = &unnamed_function.kind {
+            data.ffi_function_path.clone()
+        } else {
+            bail!("helper function must be an FFI wrapper");
+        };
+        let target_type = unnamed_function.arguments[0]
+            .argument_type
+            .ffi_type()
+            .pointer_like_to_target()?;
+        let target_path = if let RustType::Common(RustCommonType { path, .. }) = target_type {
+            if path.crate_name() != crate_name {
+                bail!("self type is outside current crate");
+            }
+            path
+        } else {
+            bail!("can't get path for target_type: {:?}", target_type);
+        };
+        Ok((target_path, ffi_function_path))
+    }
+
+    fn process_ostream_to_string(
+        unnamed_function: UnnamedRustFunction,
+        crate_name: &str,
+    ) -> Result<RustExtraImpl> {
+        let (target_path, ffi_function_path) =
+            State::helper_function_paths(&unnamed_function, crate_name)?;
+        Ok(RustExtraImpl {
+            parent_path: target_path.parent()?,
+            kind: RustExtraImplKind::OstreamDisplay(RustOstreamDisplayImpl {
+                target_path,
+                ffi_function_path,
+            }),
+        })
+    }
+
+    fn process_hash_function(
+        unnamed_function: UnnamedRustFunction,
+        crate_name: &str,
+    ) -> Result<RustExtraImpl> {
+        let (target_path, ffi_function_path) =
+            State::helper_function_paths(&unnamed_function, crate_name)?;
+        Ok(RustExtraImpl {
+            parent_path: target_path.parent()?,
+            kind: RustExtraImplKind::Hash(RustHashImpl {
+                target_path,
+                ffi_function_path,
+            }),
+        })
+    }
+
+    fn process_smart_pointer_functions(
+        &self,
+        unnamed_function: UnnamedRustFunction,
+        kind: SmartPointerKind,
+        class_path: &CppPath,
+    ) -> Result<RustExtraImpl> {
+        let ffi_function_path = if let RustFunctionKind::FfiWrapper(data) = &unnamed_function.kind {
+            data.ffi_function_path.clone()
+        } else {
+            bail!("helper function must be an FFI wrapper");
+        };
+        let target_path = self
+            .find_wrapper_type(class_path)?
+            .item
+            .path()
+            .ok_or_else(|| err_msg("RustDatabaseItem for class has no path"))?
+            .clone();
+        if target_path.crate_name() != self.data.db.crate_name() {
+            bail!("self type is outside current crate");
+        }
+        let data = RustSmartPointerImpl {
+            target_path,
+            ffi_function_path,
+        };
+        Ok(RustExtraImpl {
+            parent_path: data.target_path.parent()?,
+            kind: if kind == SmartPointerKind::Unique {
+                RustExtraImplKind::UniquePtrTarget(data)
+            } else {
+                RustExtraImplKind::SharedPtrTarget(data)
+            },
         })
     }
 
@@ -1245,31 +2462,40 @@ impl State<'_, '_> {
             return Ok(());
         };
 
-        let wrapper = self
+        let source_ffi_item = self
             .data
             .db
             .source_ffi_item(id)?
-            .ok_or_else(|| err_msg("source ffi item not found"))?
-            .item
-            .as_slot_wrapper_ref()
-            .ok_or_else(|| err_msg("invalid source ffi item type"))?;
+            .ok_or_else(|| err_msg("source ffi item not found"))?;
 
-        let closure_arguments = wrapper.arguments.iter().map_if_ok(|arg| {
-            self.rust_final_type(
-                arg,
-                // closure argument should be handled in the same way
-                // as return type (value is produced behind FFI)
-                &CppFfiArgumentMeaning::ReturnValue,
-                ReturnValueAllocationPlace::NotApplicable,
-                Some(&checks),
-            )
-        })?;
-        let closure_return_type = self.rust_final_type(
-            &CppFfiType::void(),
-            // TODO: not sure about the meaning.
-            &CppFfiArgumentMeaning::Argument(0),
-            ReturnValueAllocationPlace::NotApplicable,
-            Some(&checks),
+        let (callback_arguments, callback_return_type) = match source_ffi_item.item {
+            CppFfiItem::QtSlotWrapper(wrapper) => (&wrapper.arguments, CppFfiType::void()),
+            CppFfiItem::SubclassWrapper(wrapper) => {
+                let cpp_item = self
+                    .data
+                    .db
+                    .source_cpp_item(id)?
+                    .ok_or_else(|| err_msg("source cpp item not found"))?;
+                let setter_name = &cpp_item
+                    .item
+                    .as_function_ref()
+                    .ok_or_else(|| err_msg("invalid source cpp item type"))?
+                    .path
+                    .last()
+                    .name;
+                let method = wrapper
+                    .methods
+                    .iter()
+                    .find(|method| &method.setter_name() == setter_name)
+                    .ok_or_else(|| err_msg("subclass wrapper method not found"))?;
+                (&method.arguments, method.return_type.clone())
+            }
+            _ => bail!("invalid source ffi item type"),
+        };
+        let conversion = self.closure_to_callback_conversion(
+            callback_arguments,
+            &callback_return_type,
+            Some(checks),
         )?;
 
         function.arguments.drain(function.arguments.len() - 2..);
@@ -1279,15 +2505,55 @@ impl State<'_, '_> {
             .expect("function must have enough args");
         arg.argument_type = RustFinalType::new(
             arg.argument_type.ffi_type().clone(),
-            RustToFfiTypeConversion::ClosureToCallback(Box::new(RustClosureToCallbackConversion {
-                closure_arguments,
-                closure_return_type,
-            })),
+            RustToFfiTypeConversion::ClosureToCallback(Box::new(conversion)),
         )?;
 
         Ok(())
     }
 
+    /// Generates conversion of a Rust closure to a callback that receives
+    /// arguments of `callback_arguments` types and returns `callback_return_type`.
+    fn closure_to_callback_conversion(
+        &self,
+        callback_arguments: &[CppFfiType],
+        callback_return_type: &CppFfiType,
+        checks: Option<&CppChecks>,
+    ) -> Result<RustClosureToCallbackConversion> {
+        let closure_arguments = callback_arguments.iter().map_if_ok(|arg| {
+            self.rust_final_type(
+                arg,
+                // closure argument should be handled in the same way
+                // as return type (value is produced behind FFI)
+                &CppFfiArgumentMeaning::ReturnValue,
+                ReturnValueAllocationPlace::NotApplicable,
+                checks,
+            )
+        })?;
+        let closure_return_type = self.rust_final_type(
+            callback_return_type,
+            // return value is passed from the closure to C++
+            // in the same way as an argument
+            &CppFfiArgumentMeaning::Argument(0),
+            ReturnValueAllocationPlace::NotApplicable,
+            checks,
+        )?;
+        // `impl Trait` is not allowed in the closure's return type
+        let closure_return_type = if let RustToFfiTypeConversion::ImplCastInto(conversion) =
+            closure_return_type.conversion()
+        {
+            RustFinalType::new(
+                closure_return_type.ffi_type().clone(),
+                (**conversion).clone(),
+            )?
+        } else {
+            closure_return_type
+        };
+        Ok(RustClosureToCallbackConversion {
+            closure_arguments,
+            closure_return_type,
+        })
+    }
+
     /// Converts one function to a `RustSingleMethod`.
     fn process_rust_function(
         &self,
@@ -1304,7 +2570,11 @@ impl State<'_, '_> {
 
         let mut arguments = Vec::new();
         for (arg_index, arg) in function.arguments.iter().enumerate() {
-            if arg.meaning != CppFfiArgumentMeaning::ReturnValue {
+            // deleter and data of a callback are passed along with the callback
+            if arg.meaning != CppFfiArgumentMeaning::ReturnValue
+                && arg.meaning != CppFfiArgumentMeaning::Exception
+                && !arg.meaning.is_callback_deleter_or_data()
+            {
                 let arg_type = self.rust_final_type(
                     &arg.argument_type,
                     &arg.meaning,
@@ -1322,6 +2592,23 @@ impl State<'_, '_> {
                 });
             }
         }
+        let closure_count = arguments
+            .iter()
+            .filter(|arg| arg.argument_type.conversion().as_callback_ref().is_some())
+            .count();
+        if closure_count > 1 {
+            bail!("functions with multiple closure arguments are not supported");
+        }
+        let source_cpp_function = self
+            .data
+            .db
+            .source_cpp_item(&item.id)?
+            .and_then(|item| item.item.as_function_ref());
+        let out_params = if let Some(cpp_function) = source_cpp_function {
+            self.apply_cpp_out_params(&item.id, function, cpp_function, &mut arguments)?
+        } else {
+            None
+        };
         let mut return_type = if let Some(arg) = function
             .arguments
             .iter()
@@ -1382,6 +2669,34 @@ impl State<'_, '_> {
             }
         }
 
+        if let Some(cpp_function) = source_cpp_function {
+            self.apply_cpp_ownership_transfers(function, cpp_function, &mut arguments, checks)?;
+            self.apply_cpp_string_types(function, cpp_function, &mut arguments)?;
+            self.apply_cpp_nullability(function, cpp_function, &mut arguments, &mut return_type)?;
+            self.apply_cpp_flags_hook(function, cpp_function, &mut arguments, &mut return_type)?;
+        }
+
+        if let Some(mut out_params) = out_params {
+            out_params.return_conversion = return_type.conversion().clone();
+            return_type = RustFinalType::new(
+                return_type.ffi_type().clone(),
+                RustToFfiTypeConversion::OutParams(Box::new(out_params)),
+            )?;
+        }
+
+        if function
+            .arguments
+            .iter()
+            .any(|arg| arg.meaning == CppFfiArgumentMeaning::Exception)
+        {
+            return_type = RustFinalType::new(
+                return_type.ffi_type().clone(),
+                RustToFfiTypeConversion::CppExceptionResult(Box::new(
+                    return_type.conversion().clone(),
+                )),
+            )?;
+        }
+
         let mut unnamed_function = UnnamedRustFunction {
             is_public: true,
             arguments,
@@ -1403,6 +2718,24 @@ impl State<'_, '_> {
                 .as_function_ref()
                 .ok_or_else(|| err_msg("invalid source cpp item type"))?;
 
+            if cpp_ostream_operators::is_ostream_to_string(cpp_function) {
+                let item =
+                    State::process_ostream_to_string(unnamed_function, self.data.db.crate_name())?;
+                results.push(ProcessedFfiItem::Item(RustItem::ExtraImpl(item)));
+                return Ok(results);
+            }
+            if cpp_hash_functions::is_hash_function(cpp_function) {
+                let item =
+                    State::process_hash_function(unnamed_function, self.data.db.crate_name())?;
+                results.push(ProcessedFfiItem::Item(RustItem::ExtraImpl(item)));
+                return Ok(results);
+            }
+            if let Some((kind, class_path)) = smart_pointer_functions_target(cpp_function) {
+                let item =
+                    self.process_smart_pointer_functions(unnamed_function, kind, class_path)?;
+                results.push(ProcessedFfiItem::Item(RustItem::ExtraImpl(item)));
+                return Ok(results);
+            }
             if cpp_function.is_destructor() {
                 let item = State::process_destructor(unnamed_function, function.allocation_place)?;
                 results.push(ProcessedFfiItem::Item(RustItem::TraitImpl(item)));
@@ -1417,6 +2750,38 @@ impl State<'_, '_> {
                 );
                 return Ok(results);
             }
+            if cpp_function.is_copy_constructor() {
+                match State::process_copy_constructor(
+                    unnamed_function.clone(),
+                    function.allocation_place,
+                    self.data.db.crate_name(),
+                    trait_types,
+                ) {
+                    Ok(item) => {
+                        results.push(ProcessedFfiItem::Item(RustItem::TraitImpl(item)));
+                    }
+                    Err(err) => {
+                        debug!("failed to add CppClone impl: {}", err);
+                        debug!("function: {} {:?}", item.id, function);
+                    }
+                }
+            }
+            if cpp_function.is_constructor() && cpp_function.arguments.is_empty() {
+                match State::process_default_constructor(
+                    unnamed_function.clone(),
+                    function.allocation_place,
+                    self.data.db.crate_name(),
+                    trait_types,
+                ) {
+                    Ok(item) => {
+                        results.push(ProcessedFfiItem::Item(RustItem::TraitImpl(item)));
+                    }
+                    Err(err) => {
+                        debug!("failed to add CppDefault impl: {}", err);
+                        debug!("function: {} {:?}", item.id, function);
+                    }
+                }
+            }
             if cpp_function.operator.as_ref() == Some(&CppOperator::NotEqualTo) {
                 bail!("NotEqualTo is not needed in public API because PartialEq is used");
             }
@@ -1582,6 +2947,10 @@ impl State<'_, '_> {
     fn type_list_caption(&self, types: &[CppType], context: &RustPath) -> Result<String> {
         let mut captions = Vec::new();
         for arg in types {
+            if let CppType::IntegralConstant(_) = arg {
+                captions.push(arg.ascii_caption());
+                continue;
+            }
             let rust_type = self.rust_final_type(
                 &ffi_type(arg, CppTypeRole::NotReturnType)?,
                 &CppFfiArgumentMeaning::Argument(0),
@@ -1622,6 +2991,8 @@ impl State<'_, '_> {
                 if cpp_function.is_constructor() {
                     if cpp_function.is_copy_constructor() {
                         Some("new_copy".to_string())
+                    } else if cpp_function.is_move_constructor() {
+                        Some("new_move".to_string())
                     } else {
                         Some("new".to_string())
                     }
@@ -1641,7 +3012,18 @@ impl State<'_, '_> {
                                     .caption(context, RustTypeCaptionStrategy::LastName)?
                             ))
                         }
-                        CppOperator::Assignment => Some("copy_from".to_string()),
+                        CppOperator::Assignment => {
+                            let is_move = cpp_function
+                                .arguments
+                                .get(0)
+                                .map_or(false, |arg| arg.argument_type.is_rvalue_reference());
+                            Some(if is_move { "move_from" } else { "copy_from" }.to_string())
+                        }
+                        CppOperator::Subscript
+                            if cpp_function.member().map_or(false, |m| !m.is_const) =>
+                        {
+                            Some("index_mut".to_string())
+                        }
                         _ => Some(operator_function_name(operator)?.to_string()),
                     }
                 } else {
@@ -1649,11 +3031,15 @@ impl State<'_, '_> {
                 }
             }
             CppFfiFunctionKind::FieldAccessor { accessor_type } => {
-                let field = cpp_item
-                    .as_field_ref()
-                    .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+                if cpp_item.as_field_ref().is_none() && cpp_item.as_global_variable_ref().is_none()
+                {
+                    bail!("invalid source cpp item type");
+                }
+                let path = cpp_item
+                    .path()
+                    .ok_or_else(|| err_msg("field or variable must have a path"))?;
 
-                let name = &field.path.last().name;
+                let name = &path.last().name;
                 let function_name = match accessor_type {
                     CppFieldAccessorType::CopyGetter | CppFieldAccessorType::ConstRefGetter => {
                         name.to_string()
@@ -1749,6 +3135,11 @@ impl State<'_, '_> {
                     self.special_function_rust_name(function.clone(), &scope.path)?
                 {
                     last_name_override
+                } else if self.has_const_reference_overload(function)? {
+                    // the name without suffix is taken by the `const T&` overload
+                    let name =
+                        self.cpp_path_item_to_name(cpp_path.last(), &scope.path, &name_type)?;
+                    format!("{}_move", name)
                 } else {
                     self.cpp_path_item_to_name(cpp_path.last(), &scope.path, &name_type)?
                 };
@@ -1842,7 +3233,105 @@ impl State<'_, '_> {
             CppFfiItem::QtSignalWrapper(_) => {
                 bail!("signal wrappers do not need to be processed here");
             }
+            CppFfiItem::SubclassWrapper(_) => {
+                bail!("subclass wrappers do not need to be processed here");
+            }
+        }
+    }
+
+    /// Returns `#[repr]` type if the scoped enum at `enum_path` can be converted
+    /// to a Rust `enum`. Enums with duplicate values and enums used in `QFlags`
+    /// are kept as newtype structs because they can hold values that
+    /// don't correspond to a single variant.
+    fn native_enum_repr(&self, enum_path: &CppPath, underlying_type: &CppType) -> Option<String> {
+        let repr = native_enum_repr(underlying_type)?;
+        let values = self
+            .data
+            .db
+            .cpp_items()
+            .filter_map(|item| item.item.as_enum_value_ref())
+            .filter(|value| value.path.parent().ok().as_ref() == Some(enum_path))
+            .map(|value| value.value)
+            .collect_vec();
+        if values.is_empty() || values.iter().unique().count() != values.len() {
+            return None;
+        }
+        if self.is_qflags_enum(enum_path) {
+            return None;
+        }
+        Some(repr)
+    }
+
+    /// Returns true if `QFlags<E>` is instantiated for the enum at `enum_path`.
+    fn is_qflags_enum(&self, enum_path: &CppPath) -> bool {
+        let enum_type = CppType::Enum {
+            path: enum_path.clone(),
+        };
+        self.data
+            .db
+            .all_cpp_items()
+            .filter_map(|item| item.item.as_type_ref())
+            .any(|t| {
+                is_qflags(&t.path)
+                    && t.path.last().template_arguments.as_ref().unwrap()[0] == enum_type
+            })
+    }
+
+    /// Checks that callbacks of all pure virtual methods of `wrapper` can be set from Rust.
+    /// The C++ subclass aborts if a pure virtual method is called without a callback,
+    /// so the subclass is not generated otherwise.
+    fn check_pure_virtual_callbacks(&self, wrapper: &CppSubclassWrapper) -> Result<()> {
+        let pure_virtual_methods = wrapper
+            .methods
+            .iter()
+            .filter(|method| {
+                method
+                    .function
+                    .member()
+                    .map_or(false, |member| member.is_pure_virtual)
+            })
+            .collect_vec();
+        if pure_virtual_methods.is_empty() {
+            return Ok(());
+        }
+
+        let mut available_setters = HashSet::new();
+        for ffi_item in self.data.db.ffi_items() {
+            if !ffi_item.item.is_function() {
+                continue;
+            }
+            let cpp_function = self
+                .data
+                .db
+                .source_cpp_item(&ffi_item.id)?
+                .and_then(|item| item.item.as_function_ref());
+            if let Some(cpp_function) = cpp_function {
+                if cpp_function.path.parent().ok().as_ref() == Some(&wrapper.class_path)
+                    && self.data.db.cpp_checks(&ffi_item.id)?.any_success()
+                {
+                    available_setters.insert(cpp_function.path.last().name.clone());
+                }
+            }
+        }
+
+        for method in pure_virtual_methods {
+            if !available_setters.contains(&method.setter_name()) {
+                bail!(
+                    "callback setter of pure virtual method {} is not available",
+                    method.function.short_text()
+                );
+            }
+            if let Err(err) =
+                self.closure_to_callback_conversion(&method.arguments, &method.return_type, None)
+            {
+                bail!(
+                    "callback of pure virtual method {} can't be converted: {}",
+                    method.function.short_text(),
+                    err
+                );
+            }
         }
+        Ok(())
     }
 
     #[allow(clippy::useless_let_if_seq)]
@@ -1850,6 +3339,10 @@ impl State<'_, '_> {
         trace!("process_cpp_class: {:?}", item);
         let data = item.item;
 
+        if smart_pointer_target(&data.path).is_some() {
+            bail!("smart pointers are mapped to cpp_core types");
+        }
+
         // TODO: do something about `QUrlTwoFlags<T1, T2>`
         if is_qflags(&data.path) {
             let argument = &data.path.last().template_arguments.as_ref().unwrap()[0];
@@ -1864,6 +3357,16 @@ impl State<'_, '_> {
                         parent_path: rust_type_path.parent()?,
                         kind: RustExtraImplKind::FlagEnum(RustFlagEnumImpl {
                             enum_path: rust_type_path.clone(),
+                            flags_type: RustType::Common(RustCommonType {
+                                path: self.qt_core_path().join("QFlags"),
+                                generic_arguments: Some(vec![RustType::Common(RustCommonType {
+                                    path: rust_type_path.clone(),
+                                    generic_arguments: None,
+                                })]),
+                            }),
+                            int_type: self.ffi_type_to_rust_ffi_type(&CppType::BuiltInNumeric(
+                                CppBuiltInNumericType::Int,
+                            ))?,
                         }),
                     });
                     return Ok(vec![rust_item]);
@@ -1873,14 +3376,21 @@ impl State<'_, '_> {
 
         let mut qt_slot_wrapper = None;
         let mut qt_signal_wrapper = None;
+        let mut subclass_wrapper = None;
         if let Some(source_ffi_item) = self.data.db.source_ffi_item(&item.id)? {
             if let Some(item) = source_ffi_item
                 .clone()
                 .filter_map(|i| i.as_slot_wrapper_ref())
             {
                 qt_slot_wrapper = Some(item);
-            } else if let Some(item) = source_ffi_item.filter_map(|i| i.as_signal_wrapper_ref()) {
+            } else if let Some(item) = source_ffi_item
+                .clone()
+                .filter_map(|i| i.as_signal_wrapper_ref())
+            {
                 qt_signal_wrapper = Some(item);
+            } else if let Some(item) = source_ffi_item.filter_map(|i| i.as_subclass_wrapper_ref()) {
+                self.check_pure_virtual_callbacks(item.item)?;
+                subclass_wrapper = Some(item);
             }
         }
 
@@ -1951,6 +3461,24 @@ impl State<'_, '_> {
         });
         rust_items.push(nested_types_rust_item);
 
+        if let Some(wrapper) = subclass_wrapper {
+            let base_type = self.find_wrapper_type(&wrapper.item.base_class_path)?;
+            let base_path = base_type
+                .item
+                .path()
+                .expect("wrapper type rust item must have path");
+            let impl_item = RustItem::ExtraImpl(RustExtraImpl {
+                parent_path: public_path.parent()?,
+                kind: RustExtraImplKind::SubclassOverrides(RustSubclassOverridesImpl {
+                    target_path: public_path.clone(),
+                    trait_path: public_path
+                        .parent()?
+                        .join(format!("{}Overrides", base_path.last())),
+                }),
+            });
+            rust_items.push(impl_item);
+        }
+
         let qt_receiver_data;
         if let Some(wrapper) = qt_slot_wrapper {
             let arg_types = wrapper
@@ -2009,6 +3537,50 @@ impl State<'_, '_> {
         Ok(rust_items)
     }
 
+    /// Generates a Rust constant for `constant`. Constants are placed in
+    /// a module corresponding to the header that defines the macro.
+    fn process_macro_constant(&self, constant: DbItem<&CppMacroConstant>) -> Result<Vec<RustItem>> {
+        let (value_type, value) = match &constant.item.value {
+            CppMacroValue::Integer {
+                value,
+                is_negative,
+                value_type,
+            } => {
+                let rust_type =
+                    self.ffi_type_to_rust_ffi_type(&CppType::BuiltInNumeric(value_type.clone()))?;
+                let sign = if *is_negative { "-" } else { "" };
+                (rust_type, format!("{}{}", sign, value))
+            }
+            CppMacroValue::FloatingPoint { value, value_type } => {
+                let rust_type =
+                    self.ffi_type_to_rust_ffi_type(&CppType::BuiltInNumeric(value_type.clone()))?;
+                (rust_type, value.clone())
+            }
+            CppMacroValue::String(value) => {
+                let rust_type = RustType::PointerLike {
+                    kind: RustPointerLikeTypeKind::Reference {
+                        lifetime: Some("static".into()),
+                    },
+                    is_const: true,
+                    target: Box::new(RustType::Primitive("str".into())),
+                };
+                (rust_type, format!("{:?}", value))
+            }
+        };
+
+        let module_path = self
+            .header_module_paths
+            .get(&constant.item.include_file)
+            .ok_or_else(|| err_msg("header module not found"))?;
+        let name = sanitize_rust_identifier(&constant.item.path.last().name, false);
+        let rust_item = RustItem::Constant(RustConstant {
+            path: self.data.db.make_unique_rust_path(&module_path.join(name)),
+            value_type,
+            value,
+        });
+        Ok(vec![rust_item])
+    }
+
     fn process_cpp_item(&self, cpp_item: DbItem<&CppItem>) -> Result<Vec<RustItem>> {
         if let Some(ffi_item) = self.data.db.source_ffi_item(&cpp_item.id)? {
             if !self.data.db.cpp_checks(&ffi_item.id)?.any_success() {
@@ -2032,24 +3604,62 @@ impl State<'_, '_> {
                 Ok(vec![rust_item])
             }
             CppItem::Type(data) => match data.kind {
-                CppTypeDeclarationKind::Class { .. } => {
+                CppTypeDeclarationKind::Class { .. } | CppTypeDeclarationKind::Union => {
                     self.process_cpp_class(cpp_item.map(|v| v.as_type_ref().unwrap()))
                 }
-                CppTypeDeclarationKind::Enum => {
+                CppTypeDeclarationKind::Enum {
+                    ref underlying_type,
+                    is_scoped,
+                } => {
                     let rust_path = self.generate_rust_path(
                         &data.path,
                         NameType::Type {
                             is_from_other_crate: false,
                         },
                     )?;
-                    let rust_item = RustItem::Struct(RustStruct {
-                        path: rust_path,
-                        kind: RustStructKind::WrapperType(RustWrapperTypeKind::EnumWrapper),
-                        is_public: true,
-                        qt_receiver_data: None,
-                    });
-
-                    Ok(vec![rust_item])
+                    let is_flag_enum = if let Some(hook) = self.data.config.cpp_flag_enums_hook() {
+                        hook(&data.path)?
+                    } else {
+                        false
+                    };
+                    let native_repr =
+                        if is_scoped && !is_flag_enum && self.data.config.native_enums() {
+                            self.native_enum_repr(&data.path, underlying_type)
+                        } else {
+                            None
+                        };
+                    let kind = if let Some(repr) = native_repr {
+                        RustWrapperTypeKind::NativeEnum {
+                            underlying_type: RustType::Primitive(repr),
+                        }
+                    } else {
+                        RustWrapperTypeKind::EnumWrapper {
+                            underlying_type: self.ffi_type_to_rust_ffi_type(underlying_type)?,
+                        }
+                    };
+                    let mut rust_items = Vec::new();
+                    // `QFlags` instantiations already produce the conversion
+                    if is_flag_enum && !self.is_qflags_enum(&data.path) {
+                        let int_type = self.ffi_type_to_rust_ffi_type(underlying_type)?;
+                        rust_items.push(RustItem::ExtraImpl(RustExtraImpl {
+                            parent_path: rust_path.parent()?,
+                            kind: RustExtraImplKind::FlagEnum(RustFlagEnumImpl {
+                                enum_path: rust_path.clone(),
+                                flags_type: cpp_core_flags_type(&rust_path, int_type.clone()),
+                                int_type,
+                            }),
+                        }));
+                    }
+                    rust_items.insert(
+                        0,
+                        RustItem::Struct(RustStruct {
+                            path: rust_path,
+                            kind: RustStructKind::WrapperType(kind),
+                            is_public: true,
+                            qt_receiver_data: None,
+                        }),
+                    );
+                    Ok(rust_items)
                 }
             },
             CppItem::EnumValue(value) => {
@@ -2156,13 +3766,54 @@ impl State<'_, '_> {
                 };
                 Ok(vec![RustItem::Function(rust_function)])
             }
-            CppItem::ClassField(_) | CppItem::ClassBase(_) => {
+            CppItem::MacroConstant(_) => {
+                self.process_macro_constant(cpp_item.map(|v| v.as_macro_constant_ref().unwrap()))
+            }
+            CppItem::TypeAlias(alias) => {
+                let is_template = alias.path.items().iter().any(|item| {
+                    item.template_arguments.as_ref().map_or(false, |args| {
+                        args.iter().any(CppType::is_or_contains_template_parameter)
+                    })
+                });
+                if is_template || alias.target_type.is_or_contains_template_parameter() {
+                    bail!("template type aliases are not supported");
+                }
+                let rust_path = self.generate_rust_path(
+                    &alias.path,
+                    NameType::Type {
+                        is_from_other_crate: false,
+                    },
+                )?;
+                let rust_item = RustItem::TypeAlias(RustTypeAlias {
+                    path: rust_path,
+                    target_type: self.alias_target_type(&alias.target_type)?,
+                });
+                Ok(vec![rust_item])
+            }
+            CppItem::ClassField(_) | CppItem::ClassBase(_) | CppItem::GlobalVariable(_) => {
                 // only need to process FFI items
                 Ok(Vec::new())
             }
         }
     }
 
+    /// Returns the Rust type a type alias refers to. The type matches the type
+    /// used in the Rust API when a function returns `cpp_type`, except that
+    /// classes are referred to by value.
+    fn alias_target_type(&self, cpp_type: &CppType) -> Result<RustType> {
+        let cpp_ffi_type = ffi_type(cpp_type, CppTypeRole::ReturnType)?;
+        if let CppToFfiTypeConversion::ValueToPointer { .. } = cpp_ffi_type.conversion() {
+            return self.ffi_type_to_rust_ffi_type(cpp_type);
+        }
+        let final_type = self.rust_final_type(
+            &cpp_ffi_type,
+            &CppFfiArgumentMeaning::ReturnValue,
+            ReturnValueAllocationPlace::NotApplicable,
+            None,
+        )?;
+        Ok(final_type.api_type().clone())
+    }
+
     fn generate_crate_reexport(&mut self, crate_name: &str) -> Result<()> {
         let path = RustPath::from_parts(vec![
             self.data.config.crate_properties().name().to_string(),
@@ -2239,6 +3890,52 @@ impl State<'_, '_> {
         Ok(())
     }
 
+    /// Generates modules for all headers that define macro constants.
+    fn generate_header_modules(&mut self) -> Result<()> {
+        let include_files = self
+            .data
+            .db
+            .cpp_items()
+            .filter_map(|item| item.item.as_macro_constant_ref())
+            .map(|constant| constant.include_file.clone())
+            .collect::<BTreeSet<_>>();
+        let mut existing_paths = self
+            .data
+            .db
+            .rust_items()
+            .filter_map(|item| item.item.as_module_ref())
+            .filter_map(|module| match &module.kind {
+                RustModuleKind::CppHeader { include_file } => {
+                    Some((include_file.clone(), module.path.clone()))
+                }
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        for include_file in include_files {
+            let path = if let Some(path) = existing_paths.remove(&include_file) {
+                path
+            } else {
+                let kind = RustModuleKind::CppHeader {
+                    include_file: include_file.clone(),
+                };
+                let stem = include_file.split('.').next().unwrap_or_default();
+                let name = sanitize_rust_identifier(&stem.to_snake_case(), true);
+                let crate_root = &self.special_module_paths[&RustSpecialModuleKind::CrateRoot];
+                let path = self.data.db.make_unique_rust_path(&crate_root.join(name));
+                let rust_item = RustItem::Module(RustModule {
+                    is_public: true,
+                    path: path.clone(),
+                    kind,
+                });
+                self.add_rust_item(None, rust_item)?;
+                path
+            };
+            self.header_module_paths.insert(include_file, path);
+        }
+        Ok(())
+    }
+
     fn process_cpp_items(&mut self) -> Result<()> {
         let mut processed_ids = HashSet::new();
         let all_cpp_item_ids = self.data.db.cpp_item_ids().collect_vec();
@@ -2406,10 +4103,108 @@ impl State<'_, '_> {
     }
 }
 
+/// Returns FFI items of the helper functions added by `cpp_smart_pointers`,
+/// indexed by the helper kind and the pointee class.
+fn smart_pointer_ffi_items(
+    data: &ProcessorData<'_>,
+) -> HashMap<(SmartPointerKind, CppPath), ItemId> {
+    let helpers = data
+        .db
+        .all_cpp_items()
+        .filter_map(|item| {
+            let function = item.item.as_function_ref()?;
+            let (kind, class_path) = smart_pointer_functions_target(function)?;
+            Some((item.id.clone(), (kind, class_path.clone())))
+        })
+        .collect::<HashMap<_, _>>();
+    data.db
+        .all_ffi_items()
+        .filter_map(|item| {
+            let helper = helpers.get(item.source_id.as_ref()?)?;
+            Some((helper.clone(), item.id.clone()))
+        })
+        .collect()
+}
+
+/// Returns IDs of C++ functions taking rvalue references that have an overload
+/// taking const references instead.
+fn rvalue_reference_overloads(data: &ProcessorData<'_>) -> HashSet<ItemId> {
+    let mut functions_by_path = HashMap::new();
+    for item in data.db.all_cpp_items() {
+        if let Some(function) = item.item.as_function_ref() {
+            functions_by_path
+                .entry(&function.path)
+                .or_insert_with(Vec::new)
+                .push((item.id, function));
+        }
+    }
+    let mut result = HashSet::new();
+    for functions in functions_by_path.values() {
+        for (id, function) in functions {
+            let has_rvalue_reference = function
+                .arguments
+                .iter()
+                .any(|arg| arg.argument_type.is_rvalue_reference());
+            if has_rvalue_reference
+                && functions
+                    .iter()
+                    .any(|(_, other)| function.is_rvalue_reference_overload_of(other))
+            {
+                result.insert(id.clone());
+            }
+        }
+    }
+    result
+}
+
+/// Returns `cpp_core::Flags<E, T>` type for the Rust enum at `enum_path`
+/// with values stored as `int_type`.
+fn cpp_core_flags_type(enum_path: &RustPath, int_type: RustType) -> RustType {
+    RustType::Common(RustCommonType {
+        path: RustPath::from_good_str("cpp_core::Flags"),
+        generic_arguments: Some(vec![
+            RustType::Common(RustCommonType {
+                path: enum_path.clone(),
+                generic_arguments: None,
+            }),
+            int_type,
+        ]),
+    })
+}
+
+/// Returns size of the integer type `cpp_type` in bits
+/// if it's the same on all supported targets.
+fn integer_bits(cpp_type: &CppType) -> Option<usize> {
+    match cpp_type {
+        CppType::BuiltInNumeric(numeric) => match numeric {
+            CppBuiltInNumericType::Char
+            | CppBuiltInNumericType::SChar
+            | CppBuiltInNumericType::UChar => Some(8),
+            CppBuiltInNumericType::Short | CppBuiltInNumericType::UShort => Some(16),
+            CppBuiltInNumericType::Int | CppBuiltInNumericType::UInt => Some(32),
+            CppBuiltInNumericType::LongLong | CppBuiltInNumericType::ULongLong => Some(64),
+            _ => None,
+        },
+        CppType::SpecificNumeric(CppSpecificNumericType { bits, kind, .. }) => {
+            if *kind == CppSpecificNumericTypeKind::FloatingPoint {
+                None
+            } else {
+                Some(*bits)
+            }
+        }
+        _ => None,
+    }
+}
+
 pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
+    let smart_pointer_ffi_items = smart_pointer_ffi_items(data);
+    let rvalue_reference_overloads = rvalue_reference_overloads(data);
     let mut state = State {
         data,
         special_module_paths: HashMap::new(),
+        smart_pointer_ffi_items,
+        header_module_paths: HashMap::new(),
+        rvalue_reference_overloads,
     };
     for &module in &[
         RustSpecialModuleKind::CrateRoot,
@@ -2432,6 +4227,7 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
         state.generate_crate_reexport(dependency.name())?;
     }
 
+    state.generate_header_modules()?;
     state.process_cpp_items()?;
     let grouped_functions = state.process_ffi_items()?;
     state.finalize_functions(grouped_functions)?;
@@ -2527,7 +4323,20 @@ impl FunctionWithDesiredPath {
 
                 let types = types_with_counts
                     .map_if_ok(|(count, arg)| -> Result<String> {
-                        let text = arg.api_type().caption(&context, type_strategy)?;
+                        let text = if let RustToFfiTypeConversion::UpcastBoxToPtr {
+                            box_conversion,
+                            ..
+                        } = arg.conversion()
+                        {
+                            // use the base class instead of the type parameter
+                            let box_type = RustFinalType::new(
+                                arg.ffi_type().clone(),
+                                (**box_conversion).clone(),
+                            )?;
+                            box_type.api_type().caption(&context, type_strategy)?
+                        } else {
+                            arg.api_type().caption(&context, type_strategy)?
+                        };
                         Ok(if count == 1 {
                             text
                         } else {
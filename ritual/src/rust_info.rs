@@ -19,6 +19,24 @@ pub struct RustEnumValue {
     pub value: i64,
 }
 
+/// A constant generated from a C++ macro
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct RustConstant {
+    pub path: RustPath,
+    /// Type of the constant
+    pub value_type: RustType,
+    /// Rust expression of the value
+    pub value: String,
+}
+
+/// A Rust type alias generated from a C++ `typedef` or `using` declaration
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct RustTypeAlias {
+    pub path: RustPath,
+    /// Type the alias refers to
+    pub target_type: RustType,
+}
+
 /// Information about a Qt slot wrapper on Rust side
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct RustQtSlotWrapper {
@@ -29,9 +47,19 @@ pub struct RustQtSlotWrapper {
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum RustWrapperTypeKind {
-    EnumWrapper,
+    EnumWrapper {
+        /// Rust type matching the underlying type of the C++ enum
+        underlying_type: RustType,
+    },
+    /// Rust `enum` generated for a C++ scoped enum (see `Config::set_native_enums`)
+    NativeEnum {
+        /// Primitive integer type used as `#[repr]` of the enum
+        underlying_type: RustType,
+    },
     ImmovableClassWrapper,
-    MovableClassWrapper { sized_type_path: RustPath },
+    MovableClassWrapper {
+        sized_type_path: RustPath,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -164,6 +192,14 @@ impl RustFunctionKind {
         }
     }
 
+    pub fn is_ffi_wrapper(&self) -> bool {
+        if let RustFunctionKind::FfiWrapper(_) = self {
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn is_ffi_function(&self) -> bool {
         if let RustFunctionKind::FfiFunction = self {
             true
@@ -382,15 +418,16 @@ pub enum RustSpecialModuleKind {
     SizedTypes,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum RustModuleKind {
     Special(RustSpecialModuleKind),
     CppNamespace,
     CppNestedTypes,
+    CppHeader { include_file: String },
 }
 
 impl RustModuleKind {
-    pub fn is_in_separate_file(self) -> bool {
+    pub fn is_in_separate_file(&self) -> bool {
         match self {
             RustModuleKind::Special(kind) => match kind {
                 RustSpecialModuleKind::CrateRoot => true,
@@ -400,10 +437,11 @@ impl RustModuleKind {
             },
             RustModuleKind::CppNamespace { .. } => true,
             RustModuleKind::CppNestedTypes { .. } => false,
+            RustModuleKind::CppHeader { .. } => true,
         }
     }
 
-    pub fn is_cpp_nested_types(self) -> bool {
+    pub fn is_cpp_nested_types(&self) -> bool {
         if let RustModuleKind::CppNestedTypes { .. } = self {
             true
         } else {
@@ -506,15 +544,67 @@ pub struct RustQtReceiverImpl {
     pub receiver_type: RustQtReceiverType,
 }
 
+/// Conversion from an enum to a flags type (`QFlags` or `cpp_core::Flags`)
+/// and `BitOr` implementation for the enum.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RustFlagEnumImpl {
+    /// Path to the Rust type of the enum
     pub enum_path: RustPath,
+    /// Flags type for the enum (`QFlags<E>` or `cpp_core::Flags<E, T>`)
+    pub flags_type: RustType,
+    /// Integer type used to store the value of the flags type
+    pub int_type: RustType,
+}
+
+/// Trait for overriding virtual methods of a C++ subclass wrapper
+/// and its implementation for the subclass wrapper's Rust struct.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustSubclassOverridesImpl {
+    /// Path to the struct of the subclass wrapper
+    pub target_path: RustPath,
+    /// Path to the generated trait
+    pub trait_path: RustPath,
+}
+
+/// `Display` implementation based on C++'s
+/// `operator<<(std::ostream&, const T&)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustOstreamDisplayImpl {
+    /// Path to the Rust struct of the class
+    pub target_path: RustPath,
+    /// Path to the FFI function that returns the output of `operator<<`
+    pub ffi_function_path: RustPath,
+}
+
+/// Implementation of `Hash` for a class that has
+/// a `std::hash<T>` specialization or a `qHash(const T&)` overload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustHashImpl {
+    /// Path to the Rust struct of the class
+    pub target_path: RustPath,
+    /// Path to the FFI function that returns the hash of a value
+    pub ffi_function_path: RustPath,
+}
+
+/// Implementation of `cpp_core::SharedPtrTarget` or `cpp_core::UniquePtrTarget`
+/// for a class used in `std::shared_ptr<T>`, `std::weak_ptr<T>` or `std::unique_ptr<T>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustSmartPointerImpl {
+    /// Path to the Rust struct of the class
+    pub target_path: RustPath,
+    /// Path to the FFI function that returns the table of smart pointer functions
+    pub ffi_function_path: RustPath,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RustExtraImplKind {
     FlagEnum(RustFlagEnumImpl),
     QtReceiverImpl(RustQtReceiverImpl),
+    SubclassOverrides(RustSubclassOverridesImpl),
+    OstreamDisplay(RustOstreamDisplayImpl),
+    Hash(RustHashImpl),
+    SharedPtrTarget(RustSmartPointerImpl),
+    UniquePtrTarget(RustSmartPointerImpl),
 }
 
 impl RustExtraImplKind {
@@ -534,6 +624,41 @@ impl RustExtraImplKind {
                     false
                 }
             }
+            RustExtraImplKind::SubclassOverrides(_) => {
+                if let RustExtraImplKind::SubclassOverrides(_) = other {
+                    true
+                } else {
+                    false
+                }
+            }
+            RustExtraImplKind::OstreamDisplay(_) => {
+                if let RustExtraImplKind::OstreamDisplay(_) = other {
+                    true
+                } else {
+                    false
+                }
+            }
+            RustExtraImplKind::Hash(_) => {
+                if let RustExtraImplKind::Hash(_) = other {
+                    true
+                } else {
+                    false
+                }
+            }
+            RustExtraImplKind::SharedPtrTarget(_) => {
+                if let RustExtraImplKind::SharedPtrTarget(_) = other {
+                    true
+                } else {
+                    false
+                }
+            }
+            RustExtraImplKind::UniquePtrTarget(_) => {
+                if let RustExtraImplKind::UniquePtrTarget(_) = other {
+                    true
+                } else {
+                    false
+                }
+            }
         }
     }
 }
@@ -562,6 +687,8 @@ pub enum RustItem {
     Module(RustModule),
     Struct(RustStruct),
     EnumValue(RustEnumValue),
+    Constant(RustConstant),
+    TypeAlias(RustTypeAlias),
     TraitImpl(RustTraitImpl),
     ExtraImpl(RustExtraImpl),
     Function(RustFunction),
@@ -574,6 +701,8 @@ impl RustItem {
             RustItem::Module(data) => Some(&data.path),
             RustItem::Struct(data) => Some(&data.path),
             RustItem::EnumValue(data) => Some(&data.path),
+            RustItem::Constant(data) => Some(&data.path),
+            RustItem::TypeAlias(data) => Some(&data.path),
             RustItem::Function(data) => Some(&data.path),
             RustItem::Reexport(data) => Some(&data.path),
             RustItem::TraitImpl(_) | RustItem::ExtraImpl(_) => None,
@@ -627,6 +756,20 @@ impl RustItem {
             None
         }
     }
+    pub fn as_constant_ref(&self) -> Option<&RustConstant> {
+        if let RustItem::Constant(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+    pub fn as_type_alias_ref(&self) -> Option<&RustTypeAlias> {
+        if let RustItem::TypeAlias(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
     pub fn as_reexport_ref(&self) -> Option<&RustReexport> {
         if let RustItem::Reexport(value) = self {
             Some(value)
@@ -679,6 +822,20 @@ impl RustItem {
                     false
                 }
             }
+            RustItem::Constant(_) => {
+                if let RustItem::Constant(_) = other {
+                    true
+                } else {
+                    false
+                }
+            }
+            RustItem::TypeAlias(_) => {
+                if let RustItem::TypeAlias(_) = other {
+                    true
+                } else {
+                    false
+                }
+            }
             RustItem::TraitImpl(data) => {
                 if let RustItem::TraitImpl(other) = other {
                     data.extra_kind == other.extra_kind
@@ -787,6 +944,8 @@ impl RustItem {
             RustItem::Module(data) => format!("mod {}", data.path.full_name(None)),
             RustItem::Struct(data) => format!("struct {}", data.path.full_name(None)),
             RustItem::EnumValue(data) => format!("enum value {}", data.path.full_name(None)),
+            RustItem::Constant(data) => format!("const {}", data.path.full_name(None)),
+            RustItem::TypeAlias(data) => format!("type {}", data.path.full_name(None)),
             RustItem::TraitImpl(data) => format!(
                 "impl {} for {}",
                 rust_common_type_to_code(&data.trait_type, None),
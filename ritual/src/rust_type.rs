@@ -153,20 +153,94 @@ pub enum RustToFfiTypeConversion {
     CppBoxToPtr,
     /// `QBox<T>` to `*mut T`
     QBoxToPtr,
+    /// `cpp_core::RefOrBox<T>` to `*const T`
+    RefOrBoxToPtr,
+    /// `CppBox<U>` or `QBox<U>` to `*mut T`, where `U` is a generic parameter
+    /// of the function that implements `StaticUpcast<T>`
+    /// (only for arguments that transfer ownership to the C++ side)
+    UpcastBoxToPtr {
+        box_conversion: Box<RustToFfiTypeConversion>,
+        type_parameter: String,
+    },
+    /// `cpp_core::SharedPtr<T>`, `cpp_core::WeakPtr<T>` or `cpp_core::UniquePtr<T>`
+    /// to a pointer to the C++ smart pointer object
+    SmartPtrToPtr {
+        api_type: RustType,
+    },
     /// `qt_core::flags::Flags<T>` to `c_int`
     QFlagsToUInt {
         api_type: RustType,
     },
+    /// `cpp_core::Flags<T>` to an integer type
+    FlagsToInt {
+        api_type: RustType,
+    },
     /// `()` to any type
     UnitToAnything,
     /// Primitive to another primitive using `as`
     AsCast {
         api_type: RustType,
     },
+    /// Native Rust enum (see `Config::set_native_enums`) to its underlying integer type
+    NativeEnumToInt {
+        api_type: RustType,
+    },
     /// Rust public type has an additional reference (`&`)
     RefTo(Box<RustToFfiTypeConversion>),
     ImplCastInto(Box<RustToFfiTypeConversion>),
     ClosureToCallback(Box<RustClosureToCallbackConversion>),
+    /// Rust public type is `Result<T, cpp_core::CppException>`, where `T`
+    /// is produced by the inner conversion (only for return types
+    /// of functions that catch C++ exceptions)
+    CppExceptionResult(Box<RustToFfiTypeConversion>),
+    /// Rust public type contains values of out-parameters in addition to
+    /// the value produced by the inner conversion (only for return types)
+    OutParams(Box<RustOutParamsConversion>),
+}
+
+/// An out-parameter removed from the arguments of a Rust function.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RustOutParam {
+    /// Index of the argument in the FFI function
+    pub ffi_index: usize,
+    /// Name of the local variable that holds the value
+    pub name: String,
+    /// Type of the value (the argument's FFI type is a pointer to this type)
+    pub value_type: RustType,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RustOutParamsConversion {
+    /// Conversion of the FFI function's return value
+    pub return_conversion: RustToFfiTypeConversion,
+    /// Out-parameters returned in a tuple after the return value
+    pub values: Vec<RustOutParam>,
+    /// Out-parameter that indicates success. If it's present,
+    /// the public type is wrapped in `Option`.
+    pub ok_flag: Option<RustOutParam>,
+}
+
+impl RustOutParamsConversion {
+    /// Returns the public type of the value returned
+    /// along with out-parameters, if it's not `()`.
+    fn api_type(&self, ffi_type: &RustType) -> Result<RustType> {
+        let inner = RustFinalType::new(ffi_type.clone(), self.return_conversion.clone())?;
+        let mut types = Vec::new();
+        if !inner.api_type.is_unit() {
+            types.push(inner.api_type);
+        }
+        types.extend(self.values.iter().map(|value| value.value_type.clone()));
+        let value_type = if types.len() == 1 {
+            types.pop().unwrap()
+        } else {
+            RustType::Tuple(types)
+        };
+        Ok(if self.ok_flag.is_some() {
+            RustType::new_option(value_type)
+        } else {
+            value_type
+        })
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -280,6 +354,24 @@ impl RustFinalType {
                     generic_arguments: Some(vec![target.clone()]),
                 })
             }
+            RustToFfiTypeConversion::RefOrBoxToPtr => {
+                let target = ffi_type.pointer_like_to_target()?;
+                RustType::Common(RustCommonType {
+                    path: RustPath::from_good_str("cpp_core::RefOrBox"),
+                    generic_arguments: Some(vec![target]),
+                })
+            }
+            RustToFfiTypeConversion::UpcastBoxToPtr {
+                box_conversion,
+                type_parameter,
+            } => {
+                let intermediate =
+                    RustFinalType::new(ffi_type.clone(), (**box_conversion).clone())?;
+                let mut box_type = intermediate.api_type.as_common()?.clone();
+                box_type.generic_arguments =
+                    Some(vec![RustType::GenericParameter(type_parameter.clone())]);
+                RustType::Common(box_type)
+            }
             RustToFfiTypeConversion::QPtrToPtr => {
                 let target = ffi_type.pointer_like_to_target()?;
                 RustType::Common(RustCommonType {
@@ -288,7 +380,10 @@ impl RustFinalType {
                 })
             }
             RustToFfiTypeConversion::QFlagsToUInt { api_type }
-            | RustToFfiTypeConversion::AsCast { api_type } => api_type.clone(),
+            | RustToFfiTypeConversion::FlagsToInt { api_type }
+            | RustToFfiTypeConversion::AsCast { api_type }
+            | RustToFfiTypeConversion::NativeEnumToInt { api_type }
+            | RustToFfiTypeConversion::SmartPtrToPtr { api_type } => api_type.clone(),
             RustToFfiTypeConversion::UnitToAnything => RustType::unit(),
             RustToFfiTypeConversion::RefTo(conversion) => {
                 let intermediate = RustFinalType::new(ffi_type.clone(), (**conversion).clone())?;
@@ -305,6 +400,18 @@ impl RustFinalType {
             RustToFfiTypeConversion::ClosureToCallback { .. } => {
                 RustType::GenericParameter("T".into())
             }
+            RustToFfiTypeConversion::CppExceptionResult(conversion) => {
+                let intermediate = RustFinalType::new(ffi_type.clone(), (**conversion).clone())?;
+                let exception_type = RustType::Common(RustCommonType {
+                    path: RustPath::from_good_str("cpp_core::CppException"),
+                    generic_arguments: None,
+                });
+                RustType::Common(RustCommonType {
+                    path: RustPath::from_good_str("std::result::Result"),
+                    generic_arguments: Some(vec![intermediate.api_type, exception_type]),
+                })
+            }
+            RustToFfiTypeConversion::OutParams(conversion) => conversion.api_type(&ffi_type)?,
         };
         Ok(RustFinalType {
             api_type,
@@ -545,6 +652,7 @@ impl RustType {
                 if path == &RustPath::from_good_str("cpp_core::Ptr")
                     || path == &RustPath::from_good_str("cpp_core::Ref")
                     || path == &RustPath::from_good_str("cpp_core::CppBox")
+                    || path == &RustPath::from_good_str("cpp_core::RefOrBox")
                 {
                     let arg = &generic_arguments.as_ref().unwrap()[0];
                     return arg.caption(context, strategy);
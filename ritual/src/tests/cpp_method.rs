@@ -42,7 +42,9 @@ pub fn empty_regular_method() -> CppFunction {
         allows_variadic_arguments: false,
         operator: None,
         declaration_code: None,
+        deprecation: None,
         cast: None,
+        return_nullability: None,
     }
 }
 
@@ -62,6 +64,7 @@ fn argument_types_equal2() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     assert!(!method1.argument_types_equal(&method2));
     assert!(!method2.argument_types_equal(&method1));
@@ -75,11 +78,13 @@ fn argument_types_equal3() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     method2.arguments.push(CppFunctionArgument {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "x".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     assert!(method1.argument_types_equal(&method2));
     assert!(method2.argument_types_equal(&method1));
@@ -93,11 +98,13 @@ fn argument_types_equal4() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     method2.arguments.push(CppFunctionArgument {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: true,
+        nullability: None,
     });
     assert!(method1.argument_types_equal(&method2));
     assert!(method2.argument_types_equal(&method1));
@@ -111,6 +118,7 @@ fn argument_types_equal5() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     method2.arguments.push(CppFunctionArgument {
         argument_type: CppType::Enum {
@@ -118,6 +126,7 @@ fn argument_types_equal5() {
         },
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     assert!(!method1.argument_types_equal(&method2));
     assert!(!method2.argument_types_equal(&method1));
@@ -131,6 +140,7 @@ fn argument_types_equal6() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     method2.arguments.push(CppFunctionArgument {
         argument_type: CppType::new_pointer(
@@ -139,6 +149,7 @@ fn argument_types_equal6() {
         ),
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     assert!(!method1.argument_types_equal(&method2));
     assert!(!method2.argument_types_equal(&method1));
@@ -151,6 +162,7 @@ fn argument_types_equal7() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     };
     let mut method2 = empty_regular_method();
     method1.arguments.push(int.clone());
@@ -169,11 +181,44 @@ fn argument_types_equal8() {
     assert!(method2.argument_types_equal(&method1));
 }
 
+#[test]
+fn rvalue_reference_overload() {
+    let class_type = CppType::Class(CppPath::from_good_str("QRectF"));
+    let mut method1 = empty_regular_method();
+    method1.arguments.push(CppFunctionArgument {
+        argument_type: CppType::new_reference(true, class_type.clone()),
+        name: "arg1".to_string(),
+        has_default_value: false,
+        nullability: None,
+    });
+    let mut method2 = method1.clone();
+    method2.arguments[0].argument_type = CppType::PointerLike {
+        kind: CppPointerLikeTypeKind::RValueReference,
+        is_const: false,
+        target: Box::new(class_type.clone()),
+    };
+    assert!(method2.is_rvalue_reference_overload_of(&method1));
+    assert!(!method1.is_rvalue_reference_overload_of(&method2));
+    assert!(!method1.is_rvalue_reference_overload_of(&method1));
+
+    method1.arguments[0].argument_type = CppType::new_reference(false, class_type);
+    assert!(!method2.is_rvalue_reference_overload_of(&method1));
+}
+
 fn to_ffi(function: &CppFunction, force_stack: Option<CppPath>) -> CppFfiFunction {
+    to_ffi_with_exceptions(function, force_stack, false)
+}
+
+fn to_ffi_with_exceptions(
+    function: &CppFunction,
+    force_stack: Option<CppPath>,
+    catch_exceptions: bool,
+) -> CppFfiFunction {
     let movable_types = force_stack.into_iter().collect_vec();
     crate::cpp_ffi_generator::to_ffi_method(
         NewFfiFunctionKind::Function {
             cpp_function: function.clone(),
+            catch_exceptions,
         },
         &movable_types,
         &mut crate::cpp_ffi_generator::FfiNameProvider::testing(),
@@ -206,6 +251,7 @@ fn c_signature_simple_func() {
         },
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     let r = to_ffi(&method1, None);
     assert!(r.arguments.len() == 1);
@@ -226,6 +272,31 @@ fn c_signature_simple_func() {
     );
 }
 
+#[test]
+fn c_signature_catch_exceptions() {
+    let mut method1 = empty_regular_method();
+    method1.return_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Int);
+    method1.arguments.push(CppFunctionArgument {
+        argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+        name: "arg1".to_string(),
+        has_default_value: false,
+        nullability: None,
+    });
+    let r = to_ffi_with_exceptions(&method1, None, true);
+    assert_eq!(r.arguments.len(), 2);
+    assert_eq!(r.arguments[0].meaning, CppFfiArgumentMeaning::Argument(0));
+    assert_eq!(r.arguments[1].name, "ritual_exception");
+    assert_eq!(r.arguments[1].meaning, CppFfiArgumentMeaning::Exception);
+    assert_eq!(
+        r.arguments[1].argument_type.ffi_type(),
+        &CppType::new_pointer(
+            false,
+            CppType::new_pointer(false, CppType::BuiltInNumeric(CppBuiltInNumericType::Char))
+        )
+    );
+    assert_eq!(r.return_type.ffi_type(), &method1.return_type);
+}
+
 #[test]
 fn c_signature_method_with_this() {
     let mut method1 = empty_regular_method();
@@ -236,6 +307,7 @@ fn c_signature_method_with_this() {
         argument_type: CppType::Class(CppPath::from_good_str("MyClass2")),
         name: "my_arg".to_string(),
         has_default_value: false,
+        nullability: None,
     });
 
     assert!(!method1.is_constructor());
@@ -288,6 +360,7 @@ fn c_signature_static_method() {
         },
         name: "arg1".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     let r = to_ffi(&method1, None);
     assert!(r.arguments.len() == 1);
@@ -322,6 +395,7 @@ fn c_signature_constructor() {
         ),
         name: "arg1".to_string(),
         has_default_value: true,
+        nullability: None,
     });
 
     assert!(method1.is_constructor());
@@ -463,6 +537,7 @@ fn c_signature_method_returning_class() {
         argument_type: CppType::Class(CppPath::from_good_str("MyClass2")),
         name: "my_arg".to_string(),
         has_default_value: false,
+        nullability: None,
     });
     let r_stack = to_ffi(&method1, Some(CppPath::from_good_str("MyClass3")));
     assert!(r_stack.arguments.len() == 3);
@@ -613,16 +688,20 @@ fn short_text1() {
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 name: "arg1".to_string(),
                 has_default_value: false,
+                nullability: None,
             },
             CppFunctionArgument {
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Double),
                 name: "arg2".to_string(),
                 has_default_value: true,
+                nullability: None,
             },
         ],
         allows_variadic_arguments: false,
         cast: None,
+        return_nullability: None,
         declaration_code: None,
+        deprecation: None,
     };
     assert_eq!(
         method.short_text(),
@@ -14,12 +14,23 @@ struct ParserCppData {
     types: Vec<CppTypeDeclaration>,
     bases: Vec<CppBaseSpecifier>,
     fields: Vec<CppClassField>,
+    variables: Vec<CppGlobalVariable>,
+    macro_constants: Vec<CppMacroConstant>,
+    type_aliases: Vec<CppTypeAlias>,
     methods: Vec<CppFunction>,
     enum_values: Vec<CppEnumValue>,
     namespaces: Vec<CppPath>,
+    docs: Vec<(CppPath, String)>,
 }
 
 fn run_parser(code: &'static str) -> ParserCppData {
+    run_parser_with_config(code, |_| {})
+}
+
+fn run_parser_with_config(
+    code: &'static str,
+    modify_config: impl FnOnce(&mut Config),
+) -> ParserCppData {
     let dir = tempdir::TempDir::new("test_cpp_parser_run").unwrap();
 
     let mut workspace = Workspace::new(dir.path().into()).unwrap();
@@ -40,6 +51,7 @@ fn run_parser(code: &'static str) -> ParserCppData {
     config.add_include_directive(include_name);
     config.set_cpp_build_paths(paths);
     config.add_target_include_path(include_file_path);
+    modify_config(&mut config);
 
     processor::process(&mut workspace, &config, &["cpp_parser".into()], None).unwrap();
 
@@ -63,6 +75,21 @@ fn run_parser(code: &'static str) -> ParserCppData {
             .filter_map(|item| item.item.as_field_ref())
             .cloned()
             .collect(),
+        variables: database
+            .cpp_items()
+            .filter_map(|item| item.item.as_global_variable_ref())
+            .cloned()
+            .collect(),
+        macro_constants: database
+            .cpp_items()
+            .filter_map(|item| item.item.as_macro_constant_ref())
+            .cloned()
+            .collect(),
+        type_aliases: database
+            .cpp_items()
+            .filter_map(|item| item.item.as_type_alias_ref())
+            .cloned()
+            .collect(),
         enum_values: database
             .cpp_items()
             .filter_map(|item| item.item.as_enum_value_ref())
@@ -78,6 +105,13 @@ fn run_parser(code: &'static str) -> ParserCppData {
             .filter_map(|item| item.item.as_namespace_ref())
             .map(|ns| ns.path.clone())
             .collect(),
+        docs: database
+            .cpp_items()
+            .filter_map(|item| {
+                let doc = database.find_doc_for(&item.id).unwrap()?;
+                Some((item.item.path()?.clone(), doc.item.html.clone()))
+            })
+            .collect(),
     }
 }
 
@@ -97,10 +131,13 @@ fn simple_func() {
                 name: "x".to_string(),
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 has_default_value: false,
+                nullability: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("int func1 ( int x )".to_string()),
+            deprecation: None,
         }
     );
 }
@@ -127,10 +164,13 @@ fn simple_func_with_default_value() {
                 name: "x".to_string(),
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 has_default_value: true,
+                nullability: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("bool func1 ( int x = 42 )".to_string()),
+            deprecation: None,
         }
     );
 }
@@ -192,10 +232,13 @@ fn functions_with_class_arg() {
                 name: "x".to_string(),
                 argument_type: CppType::Class(CppPath::from_good_str("Magic")),
                 has_default_value: false,
+                nullability: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("bool func1 ( Magic x )".to_string()),
+            deprecation: None,
         }
     );
     assert_eq!(
@@ -212,10 +255,13 @@ fn functions_with_class_arg() {
                     CppType::Class(CppPath::from_good_str("Magic"))
                 ),
                 has_default_value: false,
+                nullability: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("bool func1 ( Magic * x )".to_string()),
+            deprecation: None,
         }
     );
     assert_eq!(
@@ -232,10 +278,13 @@ fn functions_with_class_arg() {
                     CppType::Class(CppPath::from_good_str("Magic"))
                 ),
                 has_default_value: false,
+                nullability: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("bool func2 ( const Magic & )".to_string()),
+            deprecation: None,
         }
     );
 }
@@ -285,10 +334,13 @@ fn variadic_func() {
                     CppType::BuiltInNumeric(CppBuiltInNumericType::Char)
                 ),
                 has_default_value: false,
+                nullability: None,
             }],
             allows_variadic_arguments: true,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("int my_printf ( const char * format , ... )".to_string()),
+            deprecation: None,
         }
     );
 }
@@ -332,10 +384,13 @@ fn free_template_func() {
                     name: "T".into(),
                 }),
                 has_default_value: false,
+                nullability: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("template < typename T > T abs ( T value )".to_string()),
+            deprecation: None,
         }
     );
 }
@@ -367,16 +422,20 @@ fn free_func_operator_sub() {
                         name: "a".to_string(),
                         argument_type: CppType::Class(CppPath::from_good_str("C1")),
                         has_default_value: false,
+                        nullability: None,
                     },
                     CppFunctionArgument {
                         name: "b".to_string(),
                         argument_type: CppType::Class(CppPath::from_good_str("C1")),
                         has_default_value: false,
+                        nullability: None,
                     },
                 ],
                 allows_variadic_arguments: false,
                 cast: None,
+                return_nullability: None,
                 declaration_code: Some("C1 operator - ( C1 a , C1 b )".to_string()),
+                deprecation: None,
             }
         );
     }
@@ -422,10 +481,13 @@ fn simple_class_method() {
                 name: "x".to_string(),
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 has_default_value: false,
+                nullability: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("int func1 ( int x )".to_string()),
+            deprecation: None,
         }
     );
 }
@@ -616,10 +678,13 @@ fn template_class_method() {
                 name: "index".to_string(),
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 has_default_value: false,
+                nullability: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
+            return_nullability: None,
             declaration_code: Some("T get ( int index )".to_string()),
+            deprecation: None,
         }
     );
     assert_eq!(
@@ -745,6 +810,16 @@ fn template_class_template_method() {
     );
 }
 
+/// Returns the underlying type chosen by the compiler for an unscoped enum
+/// without a fixed type and without negative values.
+fn default_enum_type() -> CppType {
+    if cfg!(windows) {
+        CppType::BuiltInNumeric(CppBuiltInNumericType::Int)
+    } else {
+        CppType::BuiltInNumeric(CppBuiltInNumericType::UInt)
+    }
+}
+
 #[test]
 fn simple_enum() {
     let data = run_parser(
@@ -757,7 +832,13 @@ fn simple_enum() {
     );
     assert_eq!(data.types.len(), 1);
     assert_eq!(data.types[0].path, CppPath::from_good_str("Enum1"));
-    assert_eq!(data.types[0].kind, CppTypeDeclarationKind::Enum);
+    assert_eq!(
+        data.types[0].kind,
+        CppTypeDeclarationKind::Enum {
+            underlying_type: default_enum_type(),
+            is_scoped: false,
+        }
+    );
     assert_eq!(
         data.enum_values,
         vec![
@@ -788,7 +869,13 @@ fn simple_enum2() {
     );
     assert_eq!(data.types.len(), 1);
     assert_eq!(data.types[0].path, CppPath::from_good_str("ns1::Enum1"));
-    assert_eq!(data.types[0].kind, CppTypeDeclarationKind::Enum);
+    assert_eq!(
+        data.types[0].kind,
+        CppTypeDeclarationKind::Enum {
+            underlying_type: default_enum_type(),
+            is_scoped: false,
+        }
+    );
     assert_eq!(
         data.enum_values,
         vec![
@@ -809,6 +896,87 @@ fn simple_enum2() {
     assert_eq!(data.namespaces, vec![CppPath::from_good_str("ns1")]);
 }
 
+#[test]
+fn enum_underlying_types() {
+    let data = run_parser(
+        "
+        enum class Small : unsigned char {
+            A = 1,
+            B = 255
+        };
+        enum Big : long long {
+            Min = -9223372036854775807LL,
+            Max = 9223372036854775807LL
+        };
+        ",
+    );
+    assert_eq!(data.types.len(), 2);
+    assert_eq!(data.types[0].path, CppPath::from_good_str("Small"));
+    assert_eq!(
+        data.types[0].kind,
+        CppTypeDeclarationKind::Enum {
+            underlying_type: CppType::BuiltInNumeric(CppBuiltInNumericType::UChar),
+            is_scoped: true,
+        }
+    );
+    assert_eq!(data.types[1].path, CppPath::from_good_str("Big"));
+    assert_eq!(
+        data.types[1].kind,
+        CppTypeDeclarationKind::Enum {
+            underlying_type: CppType::BuiltInNumeric(CppBuiltInNumericType::LongLong),
+            is_scoped: false,
+        }
+    );
+    assert_eq!(
+        data.enum_values,
+        vec![
+            CppEnumValue {
+                value: 1,
+                path: CppPath::from_good_str("Small::A"),
+            },
+            CppEnumValue {
+                value: 255,
+                path: CppPath::from_good_str("Small::B"),
+            },
+            CppEnumValue {
+                value: -9_223_372_036_854_775_807,
+                path: CppPath::from_good_str("Big::Min"),
+            },
+            CppEnumValue {
+                value: 9_223_372_036_854_775_807,
+                path: CppPath::from_good_str("Big::Max"),
+            },
+        ]
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn enum_unsigned_value() {
+    let data = run_parser(
+        "
+        enum Large {
+            A = 0xFFFFFFFF
+        };
+        ",
+    );
+    assert_eq!(data.types.len(), 1);
+    assert_eq!(
+        data.types[0].kind,
+        CppTypeDeclarationKind::Enum {
+            underlying_type: CppType::BuiltInNumeric(CppBuiltInNumericType::UInt),
+            is_scoped: false,
+        }
+    );
+    assert_eq!(
+        data.enum_values,
+        vec![CppEnumValue {
+            value: 4_294_967_295,
+            path: CppPath::from_good_str("Large::A"),
+        }]
+    );
+}
+
 #[test]
 fn template_instantiation() {
     let data = run_parser(
@@ -1003,7 +1171,65 @@ fn non_type_template_parameter() {
         };
         ",
     );
-    assert!(data.types.is_empty());
+    assert_eq!(data.types.len(), 1);
+    assert_eq!(
+        data.types[0].path,
+        CppPath::from_item(CppPathItem {
+            name: "QAtomicOpsSupport".into(),
+            template_arguments: Some(vec![CppType::TemplateParameter(CppTemplateParameter {
+                nested_level: 0,
+                index: 0,
+                name: "Type1".into(),
+            })]),
+        })
+    );
+}
+
+#[test]
+fn integral_template_arguments() {
+    let data = run_parser(
+        "
+        template<class T, int N>
+        class Array {
+        public:
+            T get(int index);
+        };
+        Array<int, 4> make_array();
+        ",
+    );
+    let template_array = CppPath::from_item(CppPathItem {
+        name: "Array".into(),
+        template_arguments: Some(vec![
+            CppType::TemplateParameter(CppTemplateParameter {
+                nested_level: 0,
+                index: 0,
+                name: "T".into(),
+            }),
+            CppType::TemplateParameter(CppTemplateParameter {
+                nested_level: 0,
+                index: 1,
+                name: "N".into(),
+            }),
+        ]),
+    });
+    assert_eq!(data.types.len(), 1);
+    assert_eq!(data.types[0].path, template_array);
+
+    assert_eq!(data.methods.len(), 2);
+    assert_eq!(
+        data.methods[0].path,
+        template_array.join(CppPathItem::from_good_str("get"))
+    );
+    assert_eq!(
+        data.methods[1].return_type,
+        CppType::Class(CppPath::from_item(CppPathItem {
+            name: "Array".into(),
+            template_arguments: Some(vec![
+                CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+                CppType::IntegralConstant(4),
+            ]),
+        }))
+    );
 }
 
 #[test]
@@ -1220,3 +1446,261 @@ fn elaborated_enum_type() {
         }
     );
 }
+
+#[test]
+fn simple_union() {
+    let data = run_parser(
+        "
+        union U {
+            int i;
+            float f;
+        };
+        U make_u(int i);
+        ",
+    );
+
+    assert_eq!(
+        data.types,
+        vec![CppTypeDeclaration {
+            path: CppPath::from_good_str("U"),
+            kind: CppTypeDeclarationKind::Union,
+            deprecation: None,
+        }]
+    );
+    assert_eq!(data.fields.len(), 2);
+    assert_eq!(data.fields[0].path, CppPath::from_good_str("U::i"));
+    assert_eq!(data.fields[1].path, CppPath::from_good_str("U::f"));
+    assert_eq!(data.methods.len(), 1);
+    assert_eq!(
+        data.methods[0].return_type,
+        CppType::Class(CppPath::from_good_str("U"))
+    );
+}
+
+#[test]
+fn type_aliases() {
+    let data = run_parser(
+        "
+        typedef unsigned int QRgb;
+        namespace ns {
+            using Callback = void (*)(int);
+        }
+        class A {
+        public:
+            typedef int size_type;
+            size_type size();
+        private:
+            typedef int private_type;
+        };
+        struct B {};
+        typedef struct B B;
+        ",
+    );
+
+    assert_eq!(data.type_aliases.len(), 3);
+    assert_eq!(
+        data.type_aliases[0],
+        CppTypeAlias {
+            path: CppPath::from_good_str("QRgb"),
+            target_type: CppType::BuiltInNumeric(CppBuiltInNumericType::UInt),
+        }
+    );
+    assert_eq!(
+        data.type_aliases[1].path,
+        CppPath::from_good_str("ns::Callback")
+    );
+    assert!(data.type_aliases[1].target_type.is_function_pointer());
+    assert_eq!(
+        data.type_aliases[2],
+        CppTypeAlias {
+            path: CppPath::from_good_str("A::size_type"),
+            target_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+        }
+    );
+
+    assert_eq!(data.methods.len(), 1);
+    assert_eq!(
+        data.methods[0].return_type,
+        CppType::BuiltInNumeric(CppBuiltInNumericType::Int)
+    );
+}
+
+#[test]
+fn global_variables() {
+    let data = run_parser(
+        "
+        class A {
+        public:
+            static int field1;
+        };
+        extern const int kVersion;
+        namespace ns {
+            extern A default_a;
+        }
+        ",
+    );
+
+    assert_eq!(data.fields.len(), 1);
+    assert_eq!(data.variables.len(), 2);
+    assert_eq!(
+        data.variables[0],
+        CppGlobalVariable {
+            path: CppPath::from_good_str("kVersion"),
+            variable_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+            is_const: true,
+        }
+    );
+    assert_eq!(
+        data.variables[1],
+        CppGlobalVariable {
+            path: CppPath::from_good_str("ns::default_a"),
+            variable_type: CppType::Class(CppPath::from_good_str("A")),
+            is_const: false,
+        }
+    );
+}
+
+#[test]
+fn macro_constants() {
+    let code = "
+        #define VERSION_MAJOR 5
+        #define VERSION_STR \"5.0\"
+        #define SCALE (-1.5f)
+        #define FLAGS 0xFFFFFFFFu
+        #define ADD(a, b) ((a) + (b))
+        #define COMBINED (VERSION_MAJOR | 1)
+        #define EMPTY
+        ";
+    let data = run_parser(code);
+    assert!(data.macro_constants.is_empty());
+
+    let data = run_parser_with_config(code, |config| config.set_parse_macro_constants(true));
+    let include_file = "myfakelib.h".to_string();
+    assert_eq!(
+        data.macro_constants,
+        vec![
+            CppMacroConstant {
+                path: CppPath::from_good_str("VERSION_MAJOR"),
+                include_file: include_file.clone(),
+                value: CppMacroValue::Integer {
+                    value: 5,
+                    is_negative: false,
+                    value_type: CppBuiltInNumericType::Int,
+                },
+            },
+            CppMacroConstant {
+                path: CppPath::from_good_str("VERSION_STR"),
+                include_file: include_file.clone(),
+                value: CppMacroValue::String("5.0".to_string()),
+            },
+            CppMacroConstant {
+                path: CppPath::from_good_str("SCALE"),
+                include_file: include_file.clone(),
+                value: CppMacroValue::FloatingPoint {
+                    value: "-1.5".to_string(),
+                    value_type: CppBuiltInNumericType::Float,
+                },
+            },
+            CppMacroConstant {
+                path: CppPath::from_good_str("FLAGS"),
+                include_file,
+                value: CppMacroValue::Integer {
+                    value: 0xFFFF_FFFF,
+                    is_negative: false,
+                    value_type: CppBuiltInNumericType::UInt,
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn deprecated_items() {
+    let data = run_parser(
+        "
+        class [[deprecated(\"use C2\")]] C1 {
+        public:
+            [[deprecated]] void f1();
+            void f2();
+        };
+        __attribute__((deprecated(\"use \" \"f4\"))) int f3();
+        ",
+    );
+    assert_eq!(data.types.len(), 1);
+    assert_eq!(
+        data.types[0].deprecation,
+        Some(CppDeprecation {
+            message: Some("use C2".to_string()),
+        })
+    );
+    assert_eq!(data.methods.len(), 3);
+    assert_eq!(data.methods[0].path, CppPath::from_good_str("C1::f1"));
+    assert_eq!(
+        data.methods[0].deprecation,
+        Some(CppDeprecation { message: None })
+    );
+    assert_eq!(data.methods[1].path, CppPath::from_good_str("C1::f2"));
+    assert_eq!(data.methods[1].deprecation, None);
+    assert_eq!(data.methods[2].path, CppPath::from_good_str("f3"));
+    assert_eq!(
+        data.methods[2].deprecation,
+        Some(CppDeprecation {
+            message: Some("use f4".to_string()),
+        })
+    );
+}
+
+#[test]
+fn doc_comments() {
+    let code = "
+        /// A class.
+        class C1 {
+        public:
+            /**
+             * \\brief Returns the sum.
+             * \\param a first value
+             * \\return a + 1
+             */
+            int f1(int a);
+            int f2();
+        };
+    ";
+    let data = run_parser_with_config(code, |config| config.set_parse_doc_comments(true));
+    assert_eq!(
+        data.docs,
+        vec![
+            (CppPath::from_good_str("C1"), "<p>A class.</p>".to_string()),
+            (
+                CppPath::from_good_str("C1::f1"),
+                "<p>Returns the sum.</p><p>Parameters:</p>\
+                 <ul><li><code>a</code>: first value</li></ul><p>Returns: a + 1</p>"
+                    .to_string()
+            ),
+        ]
+    );
+
+    let data = run_parser(code);
+    assert!(data.docs.is_empty());
+}
+
+#[test]
+fn nullability() {
+    let data = run_parser(
+        "
+        class A {};
+        A* _Nullable find(A* _Nonnull parent, const char* _Nullable name, int* count);
+        ",
+    );
+    assert_eq!(data.methods.len(), 1);
+    let function = &data.methods[0];
+    assert_eq!(function.return_nullability, Some(CppNullability::Nullable));
+    assert_eq!(
+        function.arguments[0].nullability,
+        Some(CppNullability::NonNull)
+    );
+    assert_eq!(
+        function.arguments[1].nullability,
+        Some(CppNullability::Nullable)
+    );
+    assert_eq!(function.arguments[2].nullability, None);
+}
@@ -2,7 +2,7 @@ use crate::cpp_data::{CppPath, CppPathItem};
 use crate::cpp_ffi_data::CppToFfiTypeConversion;
 use crate::cpp_ffi_generator::ffi_type;
 use crate::cpp_type::{
-    CppBuiltInNumericType, CppFunctionPointerType, CppSpecificNumericType,
+    CppBuiltInNumericType, CppFunctionPointerType, CppPointerLikeTypeKind, CppSpecificNumericType,
     CppSpecificNumericTypeKind, CppTemplateParameter, CppType, CppTypeRole,
 };
 
@@ -205,6 +205,31 @@ fn class_mut_ref() {
     }
 }
 
+#[test]
+fn class_rvalue_ref() {
+    let type1 = CppType::PointerLike {
+        kind: CppPointerLikeTypeKind::RValueReference,
+        is_const: false,
+        target: Box::new(CppType::Class(CppPath::from_good_str("QRectF"))),
+    };
+    assert_eq!(type1.is_class(), false);
+    assert_eq!(type1.is_rvalue_reference(), true);
+    assert_eq!(type1.to_cpp_code(None).unwrap(), "QRectF &&");
+
+    let ffi_arg = ffi_type(&type1, CppTypeRole::NotReturnType).unwrap();
+    assert_eq!(ffi_arg.original_type(), &type1);
+    assert_eq!(
+        ffi_arg.ffi_type(),
+        &CppType::new_pointer(false, CppType::Class(CppPath::from_good_str("QRectF")))
+    );
+    assert_eq!(
+        ffi_arg.conversion(),
+        &CppToFfiTypeConversion::RValueReferenceToPointer
+    );
+
+    assert!(ffi_type(&type1, CppTypeRole::ReturnType).is_err());
+}
+
 #[test]
 fn class_mut_ptr() {
     let type1 = CppType::new_pointer(false, CppType::Class(CppPath::from_good_str("QObject")));
@@ -360,6 +385,66 @@ fn function1() {
     assert_type_to_ffi_unchanged(&type1);
 }
 
+#[test]
+fn std_function() {
+    let string_ref =
+        CppType::new_reference(true, CppType::Class(CppPath::from_good_str("QString")));
+    let type1 = CppType::StdFunction(CppFunctionPointerType {
+        allows_variadic_arguments: false,
+        return_type: Box::new(CppType::BuiltInNumeric(CppBuiltInNumericType::Int)),
+        arguments: vec![
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+            string_ref.clone(),
+        ],
+    });
+    assert_eq!(type1.is_class(), false);
+    assert_eq!(type1.is_function_pointer(), false);
+    assert_eq!(
+        type1.to_cpp_code(None).unwrap(),
+        "std::function< int(int, QString const &) >"
+    );
+    assert_eq!(type1.ascii_caption(), "std_function");
+
+    let callback_type = CppType::FunctionPointer(CppFunctionPointerType {
+        allows_variadic_arguments: false,
+        return_type: Box::new(CppType::BuiltInNumeric(CppBuiltInNumericType::Int)),
+        arguments: vec![
+            CppType::new_pointer(false, CppType::Void),
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+            CppType::new_pointer(true, CppType::Class(CppPath::from_good_str("QString"))),
+        ],
+    });
+    for type2 in &[type1.clone(), CppType::new_reference(true, type1.clone())] {
+        let ffi_arg = ffi_type(type2, CppTypeRole::NotReturnType).unwrap();
+        assert_eq!(ffi_arg.original_type(), type2);
+        assert_eq!(ffi_arg.ffi_type(), &callback_type);
+        if let CppToFfiTypeConversion::StdFunctionToCallback {
+            arguments,
+            return_type,
+        } = ffi_arg.conversion()
+        {
+            assert_eq!(arguments.len(), 2);
+            assert_eq!(arguments[1].original_type(), &string_ref);
+            assert_eq!(
+                arguments[1].conversion(),
+                &CppToFfiTypeConversion::ReferenceToPointer
+            );
+            assert_eq!(return_type.conversion(), &CppToFfiTypeConversion::NoChange);
+        } else {
+            panic!("unexpected conversion: {:?}", ffi_arg.conversion());
+        }
+        assert!(ffi_type(type2, CppTypeRole::ReturnType).is_err());
+    }
+
+    // class values can't be passed to callbacks
+    let type3 = CppType::StdFunction(CppFunctionPointerType {
+        allows_variadic_arguments: false,
+        return_type: Box::new(CppType::Void),
+        arguments: vec![CppType::Class(CppPath::from_good_str("QString"))],
+    });
+    assert!(ffi_type(&type3, CppTypeRole::NotReturnType).is_err());
+}
+
 #[test]
 fn instantiate1() {
     let type1 = CppType::new_reference(
@@ -380,3 +465,34 @@ fn instantiate1() {
         )
     );
 }
+
+#[test]
+fn integral_template_argument() {
+    let template_type = CppType::Class(CppPath::from_item(CppPathItem {
+        name: "Array".into(),
+        template_arguments: Some(vec![
+            CppType::TemplateParameter(CppTemplateParameter {
+                nested_level: 0,
+                index: 0,
+                name: "T".into(),
+            }),
+            CppType::TemplateParameter(CppTemplateParameter {
+                nested_level: 0,
+                index: 1,
+                name: "N".into(),
+            }),
+        ]),
+    }));
+    let type1 = template_type
+        .instantiate(
+            0,
+            &[
+                CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+                CppType::IntegralConstant(-4),
+            ],
+        )
+        .unwrap();
+    assert_eq!(type1.to_cpp_code(None).unwrap(), "Array< int, -4 >");
+    assert_eq!(type1.ascii_caption(), "Array_int_minus_4");
+    assert!(ffi_type(&CppType::IntegralConstant(4), CppTypeRole::NotReturnType).is_err());
+}
@@ -14,9 +14,24 @@
 // placement new statements require this
 #include <new>
 
-// for exit()
+// for exit() and malloc()
 #include <cstdlib>
 
+// for catching exceptions in wrappers
+#include <cstring>
+#include <exception>
+
+// for ritual::ostream_to_string
+#include <sstream>
+#include <string>
+
+// for ritual::std_hash and std::function arguments
+#include <functional>
+
+// for moving values into rvalue reference arguments and smart pointers
+#include <memory>
+#include <utility>
+
 #ifdef _WIN32
     #define RITUAL_EXPORT __declspec(dllexport)
 #else
@@ -30,6 +45,43 @@
     }
 
 namespace ritual {
+    // Returns a copy of `text` allocated with `malloc`.
+    // The Rust side takes ownership of the copy and frees it.
+    inline char* malloc_string(const char* text) {
+        char* result = static_cast<char*>(malloc(strlen(text) + 1));
+        if (!result) {
+            abort();
+        }
+        strcpy(result, text);
+        return result;
+    }
+
+    // Returns a copy of the exception message allocated with `malloc`.
+    inline char* exception_message(const char* message) {
+        return malloc_string(message);
+    }
+
+    // Returns the output of `operator<<` for `value` allocated with `malloc`.
+    template<typename T>
+    char* ostream_to_string(const T& value) {
+        std::ostringstream stream;
+        stream << value;
+        return malloc_string(stream.str().c_str());
+    }
+
+    // Returns the hash of `value` calculated by a `std::hash` specialization.
+    template<typename T>
+    size_t std_hash(const T& value) {
+        return std::hash<T>()(value);
+    }
+
+    // Returns the hash of `value` calculated by a `qHash` overload
+    // found by argument-dependent lookup.
+    template<typename T>
+    size_t q_hash(const T& value) {
+        return qHash(value);
+    }
+
     // Calls destructor of `T` class. This template function
     // is necessary because it's not possible to use `x->~T()`
     // syntax directly if `T` contains `::`.
@@ -67,6 +119,143 @@ namespace ritual {
         void (*m_deleter)(void*);
         T m_callback;
     };
+
+    // Function object that calls `callback` through `function`
+    // that converts the arguments and the return value.
+    // `data` is passed to the callback and deleted with `deleter`
+    // when the last copy of the object is destroyed.
+    // It's used to pass Rust closures to `std::function` arguments.
+    template<class T, class F>
+    class CallbackFunction {
+    public:
+        CallbackFunction(T callback, void (*deleter)(void*), void* data, F function)
+        : m_callback(callback), m_data(data, deleter), m_function(function) {}
+
+        template<class... Args>
+        auto operator()(Args&&... args) const
+        -> decltype(std::declval<const F&>()(
+            std::declval<T>(), static_cast<void*>(nullptr), std::forward<Args>(args)...)) {
+            return m_function(m_callback, m_data.get(), std::forward<Args>(args)...);
+        }
+
+    private:
+        T m_callback;
+        std::shared_ptr<void> m_data;
+        F m_function;
+    };
+
+    template<class T, class F>
+    CallbackFunction<T, F> callback_function(
+        T callback, void (*deleter)(void*), void* data, F function) {
+        return CallbackFunction<T, F>(callback, deleter, data, function);
+    }
+
+    // Functions operating on `std::shared_ptr<T>` and `std::weak_ptr<T>` objects
+    // allocated with `new`. The layout must match `cpp_core::SharedPtrFunctions`.
+    struct SharedPtrFunctions {
+        void* (*get)(const void* ptr);
+        void* (*clone)(const void* ptr);
+        void (*destroy)(void* ptr);
+        void* (*to_void)(const void* ptr, void* target);
+        void* (*from_void)(void* void_ptr);
+        void* (*downgrade)(const void* ptr);
+        void* (*upgrade)(const void* weak_ptr);
+        void* (*clone_weak)(const void* weak_ptr);
+        void (*destroy_weak)(void* weak_ptr);
+    };
+
+    template<typename T>
+    class SharedPtrFunctionsImpl {
+    public:
+        static void* get(const void* ptr) {
+            return static_cast<const std::shared_ptr<T>*>(ptr)->get();
+        }
+        static void* clone(const void* ptr) {
+            return new std::shared_ptr<T>(*static_cast<const std::shared_ptr<T>*>(ptr));
+        }
+        static void destroy(void* ptr) {
+            delete static_cast<std::shared_ptr<T>*>(ptr);
+        }
+        // Returns a new `std::shared_ptr<void>` that shares ownership with `ptr`
+        // and stores `target`.
+        static void* to_void(const void* ptr, void* target) {
+            return new std::shared_ptr<void>(
+                *static_cast<const std::shared_ptr<T>*>(ptr), target);
+        }
+        // Converts `std::shared_ptr<void>` returned by `to_void` of any type
+        // and deletes it. The stored pointer must point to a `T` object.
+        static void* from_void(void* void_ptr) {
+            std::unique_ptr<std::shared_ptr<void> > owned(
+                static_cast<std::shared_ptr<void>*>(void_ptr));
+            return new std::shared_ptr<T>(std::static_pointer_cast<T>(*owned));
+        }
+        static void* downgrade(const void* ptr) {
+            return new std::weak_ptr<T>(*static_cast<const std::shared_ptr<T>*>(ptr));
+        }
+        static void* upgrade(const void* weak_ptr) {
+            return new std::shared_ptr<T>(static_cast<const std::weak_ptr<T>*>(weak_ptr)->lock());
+        }
+        static void* clone_weak(const void* weak_ptr) {
+            return new std::weak_ptr<T>(*static_cast<const std::weak_ptr<T>*>(weak_ptr));
+        }
+        static void destroy_weak(void* weak_ptr) {
+            delete static_cast<std::weak_ptr<T>*>(weak_ptr);
+        }
+    };
+
+    // Returns `SharedPtrFunctions` for `T`. The return type is `const void*`
+    // because the struct is not known to the Rust side of FFI.
+    template<typename T>
+    const void* shared_ptr_functions() {
+        static const SharedPtrFunctions functions = {
+            &SharedPtrFunctionsImpl<T>::get,
+            &SharedPtrFunctionsImpl<T>::clone,
+            &SharedPtrFunctionsImpl<T>::destroy,
+            &SharedPtrFunctionsImpl<T>::to_void,
+            &SharedPtrFunctionsImpl<T>::from_void,
+            &SharedPtrFunctionsImpl<T>::downgrade,
+            &SharedPtrFunctionsImpl<T>::upgrade,
+            &SharedPtrFunctionsImpl<T>::clone_weak,
+            &SharedPtrFunctionsImpl<T>::destroy_weak,
+        };
+        return &functions;
+    }
+
+    // Functions operating on `std::unique_ptr<T>` objects
+    // allocated with `new`. The layout must match `cpp_core::UniquePtrFunctions`.
+    struct UniquePtrFunctions {
+        void* (*get)(const void* ptr);
+        void* (*release)(void* ptr);
+        void (*destroy)(void* ptr);
+    };
+
+    template<typename T>
+    class UniquePtrFunctionsImpl {
+    public:
+        static void* get(const void* ptr) {
+            return static_cast<const std::unique_ptr<T>*>(ptr)->get();
+        }
+        // Releases the owned object and deletes `ptr`.
+        static void* release(void* ptr) {
+            std::unique_ptr<std::unique_ptr<T> > owned(static_cast<std::unique_ptr<T>*>(ptr));
+            return owned->release();
+        }
+        static void destroy(void* ptr) {
+            delete static_cast<std::unique_ptr<T>*>(ptr);
+        }
+    };
+
+    // Returns `UniquePtrFunctions` for `T`. The return type is `const void*`
+    // because the struct is not known to the Rust side of FFI.
+    template<typename T>
+    const void* unique_ptr_functions() {
+        static const UniquePtrFunctions functions = {
+            &UniquePtrFunctionsImpl<T>::get,
+            &UniquePtrFunctionsImpl<T>::release,
+            &UniquePtrFunctionsImpl<T>::destroy,
+        };
+        return &functions;
+    }
 }
 
 #endif // RITUAL_GLOBAL_H
@@ -0,0 +1,10 @@
+// Class generated by ritual.
+// See the template at "ritual/templates/c_lib/subclass_wrapper.h".
+{namespace_begin}class {class_name} : public {base_class} {{
+public:
+{constructors}
+    {class_name}(const {class_name}&) = delete;
+    {class_name}& operator=(const {class_name}&) = delete;
+{methods}
+private:
+{fields}}};{namespace_end}
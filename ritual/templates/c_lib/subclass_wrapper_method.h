@@ -0,0 +1,12 @@
+    {signature}{const_text} override {{
+        auto callback = m_{name}_callback.get();
+        if (callback) {{
+            return {callback_call};
+        }}
+        {fallback}
+    }}
+
+    void set_{name}_callback({callback_arg}, void (*deleter)(void*), void* data) {{
+        m_{name}_callback.set(callback, deleter, data);
+    }}
+{base_caller}